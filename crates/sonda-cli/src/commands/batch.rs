@@ -0,0 +1,455 @@
+use rayon::prelude::*;
+use serde::Serialize;
+use sonda_core::adjust::Adjustments;
+use sonda_core::classify::outcome::{ClassificationResult, ParseWarning};
+use sonda_core::error::SondaError;
+use sonda_core::i18n::Locale;
+use sonda_core::parsing::ParsedReports;
+use sonda_core::revision::{find_superseded, superseded_message, ReportVersion};
+use std::path::{Path, PathBuf};
+
+/// File extensions picked up when a directory is given as input.
+const SUPPORTED_EXTENSIONS: &[&str] = &["pdf", "xlsx", "csv", "json"];
+
+/// Outcome of classifying one file in a batch.
+#[derive(Serialize)]
+pub struct FileClassification {
+    pub path: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<ClassificationResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Outcome of parsing one file in a batch.
+#[derive(Serialize)]
+pub struct FileParse {
+    pub path: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parsed: Option<ParsedReports>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Number of samples that ended up in one overall category of one ruleset.
+#[derive(Serialize)]
+pub struct CategoryCount {
+    pub ruleset_name: String,
+    pub category: String,
+    pub samples: usize,
+}
+
+#[derive(Serialize)]
+pub struct BatchSummary {
+    pub files: usize,
+    pub files_failed: usize,
+    pub samples: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<CategoryCount>,
+}
+
+#[derive(Serialize)]
+pub struct BatchClassification {
+    pub files: Vec<FileClassification>,
+    pub summary: BatchSummary,
+    /// Warnings about the batch as a whole, not one file.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ParseWarning>,
+}
+
+#[derive(Serialize)]
+pub struct BatchParse {
+    pub files: Vec<FileParse>,
+    pub summary: BatchSummary,
+}
+
+/// Expand input arguments (files, directories and glob patterns) into a sorted
+/// list of files. Directories contribute their PDF, XLSX, CSV and JSON files.
+pub fn expand_inputs(args: &[String]) -> Result<Vec<PathBuf>, SondaError> {
+    let mut paths = Vec::new();
+
+    for arg in args {
+        let path = Path::new(arg);
+        if path.is_dir() {
+            let mut found: Vec<PathBuf> = std::fs::read_dir(path)?
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.is_file() && has_supported_extension(p))
+                .collect();
+            found.sort();
+            paths.extend(found);
+        } else if is_glob(arg) {
+            let pattern = glob::glob(arg)
+                .map_err(|e| SondaError::InvalidInput(format!("bad pattern '{}': {}", arg, e)))?;
+            let mut found: Vec<PathBuf> = pattern
+                .filter_map(|p| p.ok())
+                .filter(|p| p.is_file())
+                .collect();
+            found.sort();
+            if found.is_empty() {
+                return Err(SondaError::InvalidInput(format!(
+                    "no files match '{}'",
+                    arg
+                )));
+            }
+            paths.extend(found);
+        } else {
+            paths.push(path.to_path_buf());
+        }
+    }
+
+    if paths.is_empty() {
+        return Err(SondaError::InvalidInput(
+            "no PDF, XLSX, CSV or JSON files found in the given inputs".into(),
+        ));
+    }
+
+    Ok(paths)
+}
+
+/// True when the arguments name exactly one file, so the single-file output
+/// shape should be kept.
+pub fn is_single_file(args: &[String]) -> bool {
+    args.len() == 1 && !is_glob(&args[0]) && !Path::new(&args[0]).is_dir()
+}
+
+/// Run `f` over all paths in parallel, keeping input order.
+pub fn run_parallel<T, F>(
+    paths: &[PathBuf],
+    jobs: Option<usize>,
+    f: F,
+) -> Result<Vec<Result<T, SondaError>>, SondaError>
+where
+    T: Send,
+    F: Fn(&Path) -> Result<T, SondaError> + Sync,
+{
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs.unwrap_or(0))
+        .build()
+        .map_err(|e| SondaError::InvalidInput(format!("failed to start worker threads: {e}")))?;
+    Ok(pool.install(|| paths.par_iter().map(|p| f(p)).collect()))
+}
+
+/// Combine per-file classification outcomes and count samples per category.
+/// Samples whose report another file reissues are dropped with a warning.
+/// Adjustments aimed at a sample no file holds are warned about once.
+pub fn collect_classifications(
+    paths: &[PathBuf],
+    outcomes: Vec<Result<ClassificationResult, SondaError>>,
+    adjustments: &Adjustments,
+    locale: Locale,
+) -> BatchClassification {
+    let mut files: Vec<FileClassification> = paths
+        .iter()
+        .zip(outcomes)
+        .map(|(path, outcome)| match outcome {
+            Ok(result) => FileClassification {
+                path: path.clone(),
+                result: Some(result),
+                error: None,
+            },
+            Err(e) => FileClassification {
+                path: path.clone(),
+                result: None,
+                error: Some(e.to_string()),
+            },
+        })
+        .collect();
+    let warnings = unknown_sample_warnings(&files, adjustments, locale);
+    drop_superseded(&mut files, locale);
+
+    let mut categories: Vec<CategoryCount> = Vec::new();
+    let mut samples = 0;
+    for sample in files
+        .iter()
+        .filter_map(|f| f.result.as_ref())
+        .flat_map(|r| &r.samples)
+    {
+        samples += 1;
+        for rs in &sample.ruleset_results {
+            match categories
+                .iter_mut()
+                .find(|c| c.ruleset_name == rs.ruleset_name && c.category == rs.overall_category)
+            {
+                Some(c) => c.samples += 1,
+                None => categories.push(CategoryCount {
+                    ruleset_name: rs.ruleset_name.clone(),
+                    category: rs.overall_category.clone(),
+                    samples: 1,
+                }),
+            }
+        }
+    }
+
+    // Group counts by ruleset, keeping rulesets and categories in first-seen order.
+    let mut ruleset_order: Vec<String> = Vec::new();
+    for c in &categories {
+        if !ruleset_order.contains(&c.ruleset_name) {
+            ruleset_order.push(c.ruleset_name.clone());
+        }
+    }
+    categories.sort_by_key(|c| ruleset_order.iter().position(|r| *r == c.ruleset_name));
+
+    let summary = BatchSummary {
+        files: files.len(),
+        files_failed: files.iter().filter(|f| f.error.is_some()).count(),
+        samples,
+        categories,
+    };
+    BatchClassification {
+        files,
+        summary,
+        warnings,
+    }
+}
+
+/// Adjustments aimed at a sample that no file of the batch holds. Files
+/// are classified with `adjustments_span_inputs`, so they don't warn alone.
+fn unknown_sample_warnings(
+    files: &[FileClassification],
+    adjustments: &Adjustments,
+    locale: Locale,
+) -> Vec<ParseWarning> {
+    let ids: Vec<&str> = files
+        .iter()
+        .filter_map(|f| f.result.as_ref())
+        .flat_map(|r| &r.samples)
+        .flat_map(|s| [Some(s.sample_id.as_str()), s.lab_report_id.as_deref()])
+        .flatten()
+        .collect();
+    adjustments
+        .unknown_samples(&ids)
+        .into_iter()
+        .map(|u| ParseWarning {
+            message: locale.format(
+                "warning.adjustment_unknown_sample",
+                &[("substance", &u.substance), ("sample", &u.sample_id)],
+            ),
+            sample_id: Some(u.sample_id),
+        })
+        .collect()
+}
+
+/// Remove samples replaced by a newer report in another file, warning in the
+/// file they are removed from.
+fn drop_superseded(files: &mut [FileClassification], locale: Locale) {
+    // (file, sample) position of every classified sample, in input order.
+    let positions: Vec<(usize, usize)> = files
+        .iter()
+        .enumerate()
+        .filter_map(|(f, file)| file.result.as_ref().map(|r| (f, r.samples.len())))
+        .flat_map(|(f, count)| (0..count).map(move |s| (f, s)))
+        .collect();
+    let versions: Vec<ReportVersion> = positions
+        .iter()
+        .map(|&(f, s)| ReportVersion::of_sample(&files[f].result.as_ref().unwrap().samples[s]))
+        .collect();
+    let drops: Vec<((usize, usize), ParseWarning)> = find_superseded(&versions)
+        .iter()
+        .map(|s| {
+            let newer = &files[positions[s.kept].0].path;
+            let warning = ParseWarning {
+                sample_id: Some(s.sample_id.clone()),
+                message: locale.format(
+                    "warning.kept_in_file",
+                    &[
+                        ("warning", &superseded_message(&versions, s, locale)),
+                        ("file", &newer.display()),
+                    ],
+                ),
+            };
+            (positions[s.dropped], warning)
+        })
+        .collect();
+
+    // Remove from the back so earlier sample indices stay valid.
+    for ((f, s), warning) in drops.into_iter().rev() {
+        let result = files[f].result.as_mut().unwrap();
+        let dropped = result.samples.remove(s);
+        // Its trace goes too, unless the file holds another sample by that name.
+        if !result
+            .samples
+            .iter()
+            .any(|s| s.sample_id == dropped.sample_id)
+        {
+            let trace = &mut result.trace;
+            trace.entries.retain(|e| e.sample_id != dropped.sample_id);
+            trace.decisions.retain(|d| d.sample_id != dropped.sample_id);
+        }
+        result.warnings.push(warning);
+    }
+}
+
+/// Combine per-file parse outcomes.
+pub fn collect_parses(
+    paths: &[PathBuf],
+    outcomes: Vec<Result<ParsedReports, SondaError>>,
+) -> BatchParse {
+    let files: Vec<FileParse> = paths
+        .iter()
+        .zip(outcomes)
+        .map(|(path, outcome)| match outcome {
+            Ok(parsed) => FileParse {
+                path: path.clone(),
+                parsed: Some(parsed),
+                error: None,
+            },
+            Err(e) => FileParse {
+                path: path.clone(),
+                parsed: None,
+                error: Some(e.to_string()),
+            },
+        })
+        .collect();
+
+    let summary = BatchSummary {
+        files: files.len(),
+        files_failed: files.iter().filter(|f| f.error.is_some()).count(),
+        samples: files
+            .iter()
+            .filter_map(|f| f.parsed.as_ref())
+            .map(|p| p.reports.len())
+            .sum(),
+        categories: vec![],
+    };
+    BatchParse { files, summary }
+}
+
+fn is_glob(arg: &str) -> bool {
+    arg.contains(['*', '?', '['])
+}
+
+fn has_supported_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| SUPPORTED_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sonda_core::classify::outcome::SampleResult;
+    use sonda_core::model::{AnalysisRow, AnalysisValue, Unit};
+    use sonda_core::trace::{
+        build_entry_trace, TraceBundle, TraceDecision, TraceDecisionTarget, TraceSeverity,
+        TraceVisibility,
+    };
+
+    #[test]
+    fn expand_inputs_reads_directories_and_globs() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["b.pdf", "a.PDF", "c.json", "notes.txt"] {
+            std::fs::write(dir.path().join(name), b"").unwrap();
+        }
+        let dir_arg = dir.path().to_string_lossy().to_string();
+
+        let from_dir = expand_inputs(std::slice::from_ref(&dir_arg)).unwrap();
+        let names: Vec<_> = from_dir
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, vec!["a.PDF", "b.pdf", "c.json"]);
+
+        let from_glob = expand_inputs(&[format!("{dir_arg}/*.pdf")]).unwrap();
+        assert_eq!(from_glob.len(), 1);
+
+        assert!(expand_inputs(&[format!("{dir_arg}/*.xlsx")]).is_err());
+        assert!(!is_single_file(&[dir_arg]));
+    }
+
+    fn result(samples: &[(&str, &str, Option<u32>)]) -> ClassificationResult {
+        let mut trace = TraceBundle::default();
+        for &(sample_id, _, _) in samples {
+            let row = AnalysisRow {
+                raw_name: "Bly (Pb)".into(),
+                normalized_name: "bly".into(),
+                value: AnalysisValue::Measured("10".parse().unwrap()),
+                unit: Unit::MgPerKgTs,
+                uncertainty: None,
+                loq: None,
+                source_line: None,
+            };
+            trace.entries.push(build_entry_trace(
+                sample_id,
+                0,
+                &row,
+                "mg/kg TS",
+                &[],
+                Locale::En,
+            ));
+            trace.decisions.push(TraceDecision {
+                decision_id: format!("dec_{sample_id}"),
+                sample_id: sample_id.into(),
+                ruleset_name: "NV".into(),
+                target: TraceDecisionTarget::RulesetOverall,
+                substance: None,
+                category: "KM".into(),
+                reason: String::new(),
+                severity: TraceSeverity::Info,
+                visibility: TraceVisibility::Auto,
+                steps: vec![],
+            });
+        }
+        ClassificationResult {
+            samples: samples
+                .iter()
+                .map(|&(sample_id, report, revision)| SampleResult {
+                    sample_id: sample_id.into(),
+                    lab_report_id: Some(report.into()),
+                    matrix: None,
+                    revision,
+                    supersedes: None,
+                    ruleset_results: vec![],
+                })
+                .collect(),
+            warnings: vec![],
+            adjustments: vec![],
+            trace,
+        }
+    }
+
+    #[test]
+    fn collect_drops_samples_of_replaced_reports() {
+        let paths = [
+            PathBuf::from("ar-001.pdf"),
+            PathBuf::from("ar-001-rev2.pdf"),
+        ];
+        let outcomes = vec![
+            Ok(result(&[("P1", "AR-001", None), ("P2", "AR-001", None)])),
+            Ok(result(&[("P1", "AR-001", Some(2))])),
+        ];
+        let batch = collect_classifications(&paths, outcomes, &Adjustments::default(), Locale::En);
+
+        let old = batch.files[0].result.as_ref().unwrap();
+        let ids: Vec<_> = old.samples.iter().map(|s| s.sample_id.as_str()).collect();
+        assert_eq!(ids, ["P2"]);
+        assert_eq!(old.warnings.len(), 1);
+        assert!(old.warnings[0].message.contains("AR-001 rev. 2"));
+        assert!(old.warnings[0].message.contains("ar-001-rev2.pdf"));
+        assert!(old.trace.entries.iter().all(|e| e.sample_id == "P2"));
+        assert!(old.trace.decisions.iter().all(|d| d.sample_id == "P2"));
+        assert_eq!(old.trace.entries.len(), 1);
+        assert_eq!(old.trace.decisions.len(), 1);
+        assert_eq!(batch.summary.samples, 2);
+    }
+
+    #[test]
+    fn collect_warns_about_adjustments_for_samples_in_no_file() {
+        let paths = [PathBuf::from("a.pdf"), PathBuf::from("b.pdf")];
+        let outcomes = vec![
+            Ok(result(&[("P1", "AR-001", None)])),
+            Ok(result(&[("P2", "AR-002", None)])),
+        ];
+        let adjustments = Adjustments {
+            exclusions: vec![
+                sonda_core::adjust::parse_exclusion("P2:bly", "x").unwrap(),
+                sonda_core::adjust::parse_exclusion("P9:bly", "x").unwrap(),
+            ],
+            ..Default::default()
+        };
+        let batch = collect_classifications(&paths, outcomes, &adjustments, Locale::En);
+
+        assert_eq!(batch.warnings.len(), 1);
+        assert_eq!(batch.warnings[0].sample_id.as_deref(), Some("P9"));
+    }
+}
@@ -1,109 +1,234 @@
-use sonda_core::extraction::pdftotext::PdftotextExtractor;
-use sonda_core::model::AnalysisReport;
+use sonda_core::adjust::{self, Adjustments};
+use sonda_core::classify::outcome::{ClassificationResult, ParseWarning};
+use sonda_core::classify::sensitivity::Variation;
+use sonda_core::extraction::mapping::load_mapping;
+use sonda_core::extraction::ocr::AutoExtractor;
+use sonda_core::i18n::Locale;
+use sonda_core::input::{self, InputFormat};
 use sonda_core::rules::builtin;
 use sonda_core::rules::schema::RuleSetDef;
 use sonda_core::ClassifyOptions;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use crate::commands::{batch, parse};
 use crate::output;
 
-fn parse_reports_json(
-    json_bytes: &[u8],
-) -> Result<Vec<AnalysisReport>, sonda_core::error::SondaError> {
-    let reports = serde_json::from_slice::<Vec<AnalysisReport>>(json_bytes)?;
-    Ok(reports)
-}
-
-fn looks_like_json(bytes: &[u8]) -> bool {
-    bytes
-        .iter()
-        .copied()
-        .find(|b| !b.is_ascii_whitespace())
-        .map(|b| b == b'[' || b == b'{')
-        .unwrap_or(false)
-}
-
+#[allow(clippy::too_many_arguments)]
 pub fn run(
-    input_file: PathBuf,
+    inputs: Vec<String>,
     rule_files: Vec<PathBuf>,
     presets: Vec<String>,
     output_format: &str,
+    output_file: Option<PathBuf>,
     show_all: bool,
     verbose: bool,
+    jobs: Option<usize>,
+    mapping: Option<PathBuf>,
+    sensitivity: Option<Variation>,
+    adjustments: Adjustments,
+    locale: Locale,
 ) -> Result<(), sonda_core::error::SondaError> {
-    // Load rulesets
-    let mut rulesets: Vec<RuleSetDef> = Vec::new();
-    let mut options = ClassifyOptions::default();
+    if output_format == "xlsx" && output_file.is_none() {
+        return Err(sonda_core::error::SondaError::InvalidInput(
+            "--output xlsx requires --out <FILE>".into(),
+        ));
+    }
 
-    // Default to all presets if no presets or custom rules specified.
-    // The engine filters by matrix automatically.
-    let effective_presets = if presets.is_empty() && rule_files.is_empty() {
-        builtin::PRESETS.iter().map(|s| s.to_string()).collect()
-    } else {
-        presets
+    let (rulesets, mut options) = load_rulesets(&rule_files, presets, locale)?;
+    options.sensitivity = sensitivity;
+    options.adjustments = adjustments;
+    // In a batch, an adjustment may target a sample in another file.
+    options.adjustments_span_inputs = !batch::is_single_file(&inputs);
+    let mapping = mapping.as_deref().map(load_mapping).transpose()?;
+    let classify_file = |path: &Path| match mapping {
+        Some(ref mapping) => {
+            let parsed = parse::parse_input_with(path, Some(mapping))?;
+            sonda_core::classify_reports(&parsed.reports, &rulesets, &options)
+        }
+        None => classify_input(path, &rulesets, &options),
     };
 
-    // Load presets (separating HP-based from threshold-based)
-    for preset in &effective_presets {
-        if builtin::is_hp_preset(preset) {
-            options.include_hp = true;
-        } else {
-            let rs = builtin::load_preset(preset)?;
-            rulesets.push(rs);
+    if batch::is_single_file(&inputs) {
+        let result = classify_file(Path::new(&inputs[0]))?;
+
+        // Output
+        match (output_format, output_file) {
+            ("xlsx", Some(path)) => output::xlsx::write(&[&result], &path, locale)?,
+            ("csv", path) => write_text(output::csv::format_classification(&[&result]), path)?,
+            ("html", path) => write_text(output::html::render(&[&result], locale), path)?,
+            ("json", _) => output::json::print(&result)?,
+            _ => output::table::print(&result, show_all, verbose, locale),
         }
+        return Ok(());
     }
 
-    // Load custom rule files
-    for path in &rule_files {
-        let rs = sonda_core::rules::load_ruleset(path)?;
-        rulesets.push(rs);
-    }
+    // Batch: classify every file in parallel; one failing file does not abort the batch.
+    let paths = batch::expand_inputs(&inputs)?;
+    let outcomes = batch::run_parallel(&paths, jobs, classify_file)?;
+    let result = batch::collect_classifications(&paths, outcomes, &options.adjustments, locale);
 
-    if rulesets.is_empty() && !options.include_hp {
-        return Err(sonda_core::error::SondaError::RulesetInvalid(
-            "no rulesets specified".into(),
-        ));
+    let results: Vec<_> = result
+        .files
+        .iter()
+        .filter_map(|f| f.result.as_ref())
+        .collect();
+
+    match (output_format, output_file) {
+        ("xlsx", Some(path)) => {
+            output::xlsx::write(&results, &path, locale)?;
+            print_errors(&result);
+        }
+        ("csv", path) => {
+            write_text(output::csv::format_classification(&results), path)?;
+            print_errors(&result);
+        }
+        ("html", path) => {
+            write_text(output::html::render(&results, locale), path)?;
+            print_errors(&result);
+        }
+        ("json", _) => output::json::print(&result)?,
+        _ => output::table::print_batch(&result, show_all, verbose, locale),
     }
 
-    // Determine input type by extension; also allow extension-less JSON files.
-    let ext_is_json = input_file
-        .extension()
-        .map(|ext| ext.eq_ignore_ascii_case("json"))
-        .unwrap_or(false);
-    let ext_is_xlsx = input_file
-        .extension()
-        .map(|ext| ext.eq_ignore_ascii_case("xlsx"))
-        .unwrap_or(false);
-    let input_bytes = std::fs::read(&input_file)?;
-    let should_parse_json = ext_is_json || (!ext_is_xlsx && looks_like_json(&input_bytes));
-
-    let result = if ext_is_xlsx {
-        // Parse Sweco XLSX and classify.
-        let parsed = sonda_core::parse_sweco_xlsx(&input_bytes)?;
-        sonda_core::classify_reports(&parsed.reports, &rulesets, &options)?
-    } else if should_parse_json {
-        // Load pre-parsed reports from JSON.
-        // Expected shape: top-level array of AnalysisReport.
-        let reports = parse_reports_json(&input_bytes)?;
-        sonda_core::classify_reports(&reports, &rulesets, &options)?
-    } else {
-        // Parse and classify PDF.
-        let extractor = PdftotextExtractor::new();
-        sonda_core::classify_pdf(&input_bytes, &extractor, &rulesets, &options)?
-    };
+    Ok(())
+}
 
-    // Output
-    match output_format {
-        "json" => output::json::print(&result)?,
-        _ => output::table::print(&result, show_all, verbose),
+/// Failed files are not part of workbook, CSV or HTML output; report them on the terminal.
+fn print_errors(result: &batch::BatchClassification) {
+    for file in result.files.iter().filter(|f| f.error.is_some()) {
+        eprintln!(
+            "{}: {}",
+            file.path.display(),
+            file.error.as_deref().unwrap_or("")
+        );
     }
+}
 
+/// Write text output to a file, or to stdout if no file was given.
+fn write_text(
+    contents: String,
+    path: Option<PathBuf>,
+) -> Result<(), sonda_core::error::SondaError> {
+    match path {
+        Some(path) => std::fs::write(path, contents)?,
+        None => print!("{contents}"),
+    }
     Ok(())
 }
 
+/// Build manual adjustments from `--override`/`--exclude` specs.
+///
+/// The author defaults to the current user so the audit trail always names someone.
+pub fn build_adjustments(
+    overrides: &[String],
+    exclusions: &[String],
+    justification: Option<String>,
+    author: Option<String>,
+) -> Result<Adjustments, sonda_core::error::SondaError> {
+    let justification = justification.unwrap_or_default();
+    let adjustments = Adjustments {
+        overrides: overrides
+            .iter()
+            .map(|spec| adjust::parse_override(spec, &justification))
+            .collect::<Result<_, _>>()?,
+        exclusions: exclusions
+            .iter()
+            .map(|spec| adjust::parse_exclusion(spec, &justification))
+            .collect::<Result<_, _>>()?,
+        author: author
+            .or_else(|| std::env::var("USER").ok())
+            .or_else(|| std::env::var("USERNAME").ok()),
+    };
+    adjustments.validate()?;
+    Ok(adjustments)
+}
+
+/// Load presets and custom rule files into rulesets and engine options.
+///
+/// Defaults to all presets if no presets or custom rules are specified.
+pub fn load_rulesets(
+    rule_files: &[PathBuf],
+    presets: Vec<String>,
+    locale: Locale,
+) -> Result<(Vec<RuleSetDef>, ClassifyOptions), sonda_core::error::SondaError> {
+    let custom = rule_files
+        .iter()
+        .map(|path| sonda_core::rules::load_ruleset(path))
+        .collect::<Result<Vec<_>, _>>()?;
+    build_rulesets(presets, custom, locale)
+}
+
+/// Combine presets with already loaded custom rulesets.
+///
+/// Defaults to all presets if no presets or custom rulesets are given.
+pub fn build_rulesets(
+    presets: Vec<String>,
+    custom: Vec<RuleSetDef>,
+    locale: Locale,
+) -> Result<(Vec<RuleSetDef>, ClassifyOptions), sonda_core::error::SondaError> {
+    // The engine filters by matrix automatically.
+    let (rulesets, include_hp) = builtin::resolve(&presets, custom)?;
+    let options = ClassifyOptions {
+        include_hp,
+        locale,
+        ..Default::default()
+    };
+
+    Ok((rulesets, options))
+}
+
+/// Classify an input file (PDF, Sweco XLSX, Eurofins export or pre-parsed JSON).
+pub fn classify_input(
+    input_file: &Path,
+    rulesets: &[RuleSetDef],
+    options: &ClassifyOptions,
+) -> Result<ClassificationResult, sonda_core::error::SondaError> {
+    let input_bytes = std::fs::read(input_file)?;
+    classify_bytes(input_file, &input_bytes, rulesets, options)
+}
+
+/// Classify input already in memory. `name` is only used to detect the format.
+pub fn classify_bytes(
+    name: &Path,
+    input_bytes: &[u8],
+    rulesets: &[RuleSetDef],
+    options: &ClassifyOptions,
+) -> Result<ClassificationResult, sonda_core::error::SondaError> {
+    match InputFormat::detect(name, input_bytes) {
+        InputFormat::SwecoXlsx => {
+            // Parse Sweco XLSX and classify.
+            let parsed = sonda_core::parse_sweco_xlsx(input_bytes)?;
+            sonda_core::classify_reports(&parsed.reports, rulesets, options)
+        }
+        InputFormat::EurofinsExport => {
+            let parsed = sonda_core::parse_eurofins_export(input_bytes)?;
+            sonda_core::classify_reports(&parsed.reports, rulesets, options)
+        }
+        InputFormat::ReportsJson => {
+            // Load pre-parsed reports from JSON.
+            // Expected shape: top-level array of AnalysisReport.
+            let parsed = input::parse_reports_json(input_bytes)?;
+            let mut result = sonda_core::classify_reports(&parsed.reports, rulesets, options)?;
+            // Dates dropped while loading would otherwise go unnoticed.
+            result
+                .warnings
+                .extend(parsed.warnings.iter().map(|w| ParseWarning {
+                    sample_id: w.sample_id.clone(),
+                    message: w.message(options.locale),
+                }));
+            Ok(result)
+        }
+        InputFormat::Pdf => {
+            // Parse and classify PDF.
+            let extractor = AutoExtractor::default();
+            sonda_core::classify_pdf(input_bytes, &extractor, rulesets, options)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::parse_reports_json;
+    use super::*;
 
     #[test]
     fn parse_reports_json_accepts_array_shape() {
@@ -126,8 +251,37 @@ mod tests {
   }
 ]
 "#;
-        let reports = parse_reports_json(json).expect("array JSON should parse");
+        let reports = input::parse_reports_json(json)
+            .expect("array JSON should parse")
+            .reports;
         assert_eq!(reports.len(), 1);
         assert_eq!(reports[0].header.sample_id.as_deref(), Some("P001"));
     }
+
+    #[test]
+    fn classifies_eurofins_xlsx_export() {
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        let sheet = workbook.add_worksheet();
+        let rows = [
+            ["Provmärkning", "Matris", "Parameter", "Resultat", "Enhet"],
+            ["P1", "Jord", "Bly (Pb)", "450", "mg/kg TS"],
+            ["P2", "Jord", "Bly (Pb)", "<1,0", "mg/kg TS"],
+        ];
+        for (r, row) in rows.iter().enumerate() {
+            for (c, cell) in row.iter().enumerate() {
+                sheet.write_string(r as u32, c as u16, *cell).unwrap();
+            }
+        }
+        let bytes = workbook.save_to_buffer().unwrap();
+        let name = Path::new("export.xlsx");
+        assert_eq!(
+            InputFormat::detect(name, &bytes),
+            InputFormat::EurofinsExport
+        );
+
+        let rulesets = vec![builtin::load_preset("nv").unwrap()];
+        let result = classify_bytes(name, &bytes, &rulesets, &ClassifyOptions::default()).unwrap();
+        assert_eq!(result.samples.len(), 2);
+        assert_eq!(result.samples[0].sample_id, "P1");
+    }
 }
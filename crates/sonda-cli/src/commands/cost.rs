@@ -0,0 +1,30 @@
+use sonda_core::disposal;
+use sonda_core::i18n::Locale;
+use std::path::PathBuf;
+
+use crate::commands::classify::{classify_input, load_rulesets};
+use crate::output;
+
+pub fn run(
+    input_file: PathBuf,
+    prices_file: PathBuf,
+    quantities_file: PathBuf,
+    rule_files: Vec<PathBuf>,
+    presets: Vec<String>,
+    output_format: &str,
+    locale: Locale,
+) -> Result<(), sonda_core::error::SondaError> {
+    let prices = disposal::load_price_list(&prices_file)?;
+    let quantities = disposal::load_quantities(&quantities_file)?;
+
+    let (rulesets, options) = load_rulesets(&rule_files, presets, locale)?;
+    let result = classify_input(&input_file, &rulesets, &options)?;
+    let estimate = disposal::estimate_costs(&result, &quantities, &prices)?;
+
+    match output_format {
+        "json" => output::json::print(&estimate)?,
+        _ => output::table::print_cost(&estimate, locale),
+    }
+
+    Ok(())
+}
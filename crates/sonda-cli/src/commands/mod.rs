@@ -1,3 +1,9 @@
+pub mod batch;
 pub mod classify;
+pub mod cost;
 pub mod parse;
+pub mod project;
+pub mod report;
+pub mod review;
 pub mod rules;
+pub mod serve;
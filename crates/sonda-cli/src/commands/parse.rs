@@ -1,34 +1,51 @@
-use sonda_core::extraction::pdftotext::PdftotextExtractor;
-use std::path::PathBuf;
+use sonda_core::extraction::mapping::{load_mapping, ColumnMapping};
+use sonda_core::extraction::ocr::AutoExtractor;
+use sonda_core::i18n::Locale;
+use sonda_core::input::InputFormat;
+use sonda_core::model::AnalysisReport;
+use sonda_core::parsing::ParsedReports;
+use std::path::{Path, PathBuf};
 
+use crate::commands::batch;
 use crate::output;
 
 pub fn run(
-    input_file: PathBuf,
+    inputs: Vec<String>,
     output_format: &str,
     output_file: Option<PathBuf>,
+    jobs: Option<usize>,
+    mapping: Option<PathBuf>,
+    locale: Locale,
 ) -> Result<(), sonda_core::error::SondaError> {
-    let input_bytes = std::fs::read(&input_file)?;
-
-    let is_xlsx = input_file
-        .extension()
-        .map(|ext| ext.eq_ignore_ascii_case("xlsx"))
-        .unwrap_or(false);
-
-    let parsed = if is_xlsx {
-        sonda_core::parse_sweco_xlsx(&input_bytes)?
-    } else {
-        let extractor = PdftotextExtractor::new();
-        sonda_core::parse_pdf(&input_bytes, &extractor)?
-    };
+    let mapping = mapping.as_deref().map(load_mapping).transpose()?;
+    if !batch::is_single_file(&inputs) {
+        return run_batch(
+            &inputs,
+            output_format,
+            output_file,
+            jobs,
+            mapping.as_ref(),
+            locale,
+        );
+    }
+    let parsed = parse_input_with(Path::new(&inputs[0]), mapping.as_ref())?;
 
     let output_str = match output_format {
         // Use the same JSON shape that `sonda classify` consumes.
         "json" => serde_json::to_string_pretty(&parsed.reports)?,
-        _ => output::table::format_parsed(&parsed),
+        "csv" => output::csv::format_parsed(&parsed.reports.iter().collect::<Vec<_>>()),
+        _ => output::table::format_parsed(&parsed, locale),
     };
 
     match output_file {
+        Some(path) if output_format == "csv" => {
+            std::fs::write(&path, output_str)?;
+            eprintln!(
+                "Parsed {} sample(s), written to {}",
+                parsed.reports.len(),
+                path.display()
+            );
+        }
         Some(path) => {
             // Always write JSON when saving to file.
             // The file format is a top-level array of AnalysisReport.
@@ -41,7 +58,7 @@ pub fn run(
             );
             if !parsed.warnings.is_empty() {
                 for w in &parsed.warnings {
-                    eprintln!("  warning: {}", w.reason);
+                    eprintln!("  warning: {}", w.message(locale));
                 }
             }
             if !parsed.skipped_lines.is_empty() {
@@ -51,6 +68,7 @@ pub fn run(
                 );
             }
         }
+        None if output_format == "csv" => print!("{output_str}"),
         None => {
             println!("{output_str}");
         }
@@ -58,3 +76,135 @@ pub fn run(
 
     Ok(())
 }
+
+pub fn parse_input(input_file: &Path) -> Result<ParsedReports, sonda_core::error::SondaError> {
+    let input_bytes = std::fs::read(input_file)?;
+    let format = InputFormat::detect(input_file, &input_bytes);
+    let extractor = AutoExtractor::default();
+    sonda_core::input::parse_input(format, &input_bytes, &extractor)
+}
+
+/// Like [`parse_input`], reading workbooks with `mapping` when one is given.
+pub fn parse_input_with(
+    input_file: &Path,
+    mapping: Option<&ColumnMapping>,
+) -> Result<ParsedReports, sonda_core::error::SondaError> {
+    match mapping {
+        Some(mapping) if is_workbook(input_file) => {
+            sonda_core::parse_mapped_xlsx(&std::fs::read(input_file)?, mapping)
+        }
+        _ => parse_input(input_file),
+    }
+}
+
+fn is_workbook(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("xlsx"))
+}
+
+/// Parse several files in parallel into one combined output.
+fn run_batch(
+    inputs: &[String],
+    output_format: &str,
+    output_file: Option<PathBuf>,
+    jobs: Option<usize>,
+    mapping: Option<&ColumnMapping>,
+    locale: Locale,
+) -> Result<(), sonda_core::error::SondaError> {
+    let paths = batch::expand_inputs(inputs)?;
+    let outcomes = batch::run_parallel(&paths, jobs, |path| parse_input_with(path, mapping))?;
+    let result = batch::collect_parses(&paths, outcomes);
+
+    let reports: Vec<&AnalysisReport> = result
+        .files
+        .iter()
+        .filter_map(|f| f.parsed.as_ref())
+        .flat_map(|p| &p.reports)
+        .collect();
+
+    match output_file {
+        Some(path) => {
+            // Write the combined reports in the same shape `sonda classify` consumes,
+            // or as one long-format table.
+            let contents = match output_format {
+                "csv" => output::csv::format_parsed(&reports),
+                _ => serde_json::to_string_pretty(&reports)?,
+            };
+            std::fs::write(&path, contents)?;
+            eprintln!(
+                "Parsed {} sample(s) from {} file(s), written to {}",
+                reports.len(),
+                result.summary.files - result.summary.files_failed,
+                path.display()
+            );
+            for f in &result.files {
+                if let Some(ref err) = f.error {
+                    eprintln!("  error: {}: {}", f.path.display(), err);
+                }
+            }
+        }
+        None => match output_format {
+            "json" => output::json::print(&result)?,
+            "csv" => {
+                print!("{}", output::csv::format_parsed(&reports));
+                print_errors(&result.files);
+            }
+            _ => println!("{}", output::table::format_parsed_batch(&result, locale)),
+        },
+    }
+
+    Ok(())
+}
+
+/// Report failed files on stderr for output formats that have no place for errors.
+fn print_errors(files: &[batch::FileParse]) {
+    for f in files {
+        if let Some(ref err) = f.error {
+            eprintln!("{}: {}", f.path.display(), err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sonda_core::extraction::mapping::parse_mapping_str;
+
+    #[test]
+    fn parses_wide_workbook_with_mapping() {
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        let sheet = workbook.add_worksheet();
+        sheet.write_string(0, 0, "Prov").unwrap();
+        sheet.write_string(1, 0, "Bly").unwrap();
+        for (col, (id, lead)) in [("S1", "35"), ("S2", "<5,0"), ("S3", "210")]
+            .into_iter()
+            .enumerate()
+        {
+            sheet.write_string(0, col as u16 + 1, id).unwrap();
+            match lead.parse::<f64>() {
+                Ok(value) => sheet.write_number(1, col as u16 + 1, value).unwrap(),
+                Err(_) => sheet.write_string(1, col as u16 + 1, lead).unwrap(),
+            };
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mall.xlsx");
+        workbook.save(&path).unwrap();
+
+        let mapping = parse_mapping_str(
+            r#"{ "samples": { "layout": "columns", "name_column": "A", "first_row": 2, "sample_id_row": 1 } }"#,
+        )
+        .unwrap();
+        let parsed = parse_input_with(&path, Some(&mapping)).unwrap();
+        let ids: Vec<_> = parsed
+            .reports
+            .iter()
+            .map(|r| r.header.sample_id.as_deref().unwrap())
+            .collect();
+        assert_eq!(ids, ["S1", "S2", "S3"]);
+        assert!(parsed.reports[1].rows[0].value.is_below_detection());
+
+        // Without a mapping the workbook is taken for a Sweco file.
+        assert!(parse_input_with(&path, None).is_err());
+    }
+}
@@ -0,0 +1,81 @@
+use sonda_core::extraction::ocr::AutoExtractor;
+use sonda_core::i18n::Locale;
+use sonda_core::project::{self, InputState, ParseCache};
+use std::path::{Path, PathBuf};
+
+use crate::output;
+
+pub fn run(
+    manifest_path: PathBuf,
+    output_format: &str,
+    force: bool,
+    show_all: bool,
+    verbose: bool,
+    locale: Option<Locale>,
+) -> Result<(), sonda_core::error::SondaError> {
+    let mut manifest = project::load_manifest(&manifest_path)?;
+    // --lang overrides the language set in the manifest.
+    if let Some(locale) = locale {
+        manifest.locale = locale;
+    }
+    let base_dir = base_dir(&manifest_path);
+    let cache_path = cache_path(&manifest_path, manifest.cache.as_deref());
+
+    let mut cache = if force {
+        ParseCache::default()
+    } else {
+        ParseCache::load(&cache_path)
+    };
+
+    let extractor = AutoExtractor::default();
+    let result = project::run_project(&manifest, &base_dir, &extractor, &mut cache)?;
+    cache.save(&cache_path)?;
+
+    if let Some(ref out) = manifest.output {
+        let path = base_dir.join(out);
+        std::fs::write(&path, serde_json::to_string_pretty(&result)?)?;
+        eprintln!("Project result written to {}", path.display());
+    }
+
+    match output_format {
+        "json" => output::json::print(&result)?,
+        _ => output::table::print_project(&result, show_all, verbose, manifest.locale),
+    }
+
+    Ok(())
+}
+
+pub fn status(manifest_path: PathBuf) -> Result<(), sonda_core::error::SondaError> {
+    let manifest = project::load_manifest(&manifest_path)?;
+    let base_dir = base_dir(&manifest_path);
+    let cache = ParseCache::load(&cache_path(&manifest_path, manifest.cache.as_deref()));
+
+    println!("Project: {}\n", manifest.name);
+    for input in project::input_states(&manifest, &base_dir, &cache) {
+        let state = match input.state {
+            InputState::Cached => "unchanged",
+            InputState::Parsed => "needs parsing",
+            InputState::Failed => "unreadable",
+        };
+        println!("  {:<14} {}", state, input.path.display());
+        if let Some(ref err) = input.error {
+            println!("                 {}", err);
+        }
+    }
+
+    Ok(())
+}
+
+fn base_dir(manifest_path: &Path) -> PathBuf {
+    manifest_path
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_default()
+}
+
+fn cache_path(manifest_path: &Path, configured: Option<&Path>) -> PathBuf {
+    match configured {
+        Some(path) => base_dir(manifest_path).join(path),
+        None => project::default_cache_path(manifest_path),
+    }
+}
@@ -0,0 +1,78 @@
+use sonda_core::clp::database::clp_database;
+use sonda_core::extraction::ocr::AutoExtractor;
+use sonda_core::i18n::Locale;
+use sonda_core::input::{self, InputFormat};
+use sonda_core::model::Date;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::commands::classify::load_rulesets;
+use crate::output::pdf::{self, ReportData, ReportTemplate};
+
+pub fn run(
+    input_file: PathBuf,
+    template_file: Option<PathBuf>,
+    rule_files: Vec<PathBuf>,
+    presets: Vec<String>,
+    output_file: PathBuf,
+    locale: Locale,
+) -> Result<(), sonda_core::error::SondaError> {
+    let (template, base_dir) = match template_file {
+        Some(ref path) => (
+            pdf::load_template(path)?,
+            path.parent().unwrap_or(Path::new(".")).to_path_buf(),
+        ),
+        None => (ReportTemplate::default(), PathBuf::from(".")),
+    };
+
+    let (rulesets, options) = load_rulesets(&rule_files, presets, locale)?;
+
+    // Parse first so the memo can show report headers and parse warnings.
+    let bytes = std::fs::read(&input_file)?;
+    let format = InputFormat::detect(&input_file, &bytes);
+    let parsed = input::parse_input(format, &bytes, &AutoExtractor::default())?;
+    let result = sonda_core::classify_reports(&parsed.reports, &rulesets, &options)?;
+
+    let mut warnings: Vec<String> = parsed.warnings.iter().map(|w| w.message(locale)).collect();
+    if !parsed.skipped_lines.is_empty() {
+        warnings.push(locale.format(
+            "cli.pdf.unread_lines",
+            &[("count", &parsed.skipped_lines.len())],
+        ));
+    }
+    warnings.extend(result.warnings.iter().map(|w| w.message.clone()));
+
+    let data = ReportData {
+        reports: &parsed.reports,
+        result: &result,
+        rulesets: rulesets
+            .iter()
+            .map(|rs| (rs.name.clone(), rs.version.clone()))
+            .collect(),
+        hp_database_version: options.include_hp.then(|| clp_database().version.clone()),
+        warnings,
+        date: today(),
+        locale,
+    };
+
+    let bytes = pdf::render(&data, &template, &base_dir)?;
+    std::fs::write(&output_file, bytes)?;
+    eprintln!(
+        "Report for {} sample(s) written to {}",
+        result.samples.len(),
+        output_file.display()
+    );
+
+    Ok(())
+}
+
+/// Today's date (UTC) as YYYY-MM-DD.
+fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() / 86_400)
+        .unwrap_or(0) as i64;
+    Date::from_unix_days(days)
+        .map(|date| date.to_string())
+        .unwrap_or_default()
+}
@@ -0,0 +1,54 @@
+use sonda_core::clp::database;
+use sonda_core::error::SondaError;
+use sonda_core::i18n::Locale;
+use std::collections::BTreeSet;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+use crate::commands::classify::load_rulesets;
+use crate::commands::parse::parse_input;
+use crate::review::{self, state::Review};
+
+pub fn run(
+    input_file: PathBuf,
+    output_file: Option<PathBuf>,
+    rule_files: Vec<PathBuf>,
+    presets: Vec<String>,
+    locale: Locale,
+) -> Result<(), SondaError> {
+    if !std::io::stdout().is_terminal() {
+        return Err(SondaError::InvalidInput(
+            "sonda review needs an interactive terminal".into(),
+        ));
+    }
+
+    let (rulesets, options) = load_rulesets(&rule_files, presets, locale)?;
+    let mut known: BTreeSet<String> = rulesets
+        .iter()
+        .flat_map(|rs| rs.rules.iter().map(|r| r.substance.clone()))
+        .collect();
+    if options.include_hp {
+        // Substances the HP engine can speciate into CLP compounds.
+        let speciation = database::speciation_table();
+        known.extend(speciation.metals.iter().map(|m| m.substance.clone()));
+        known.extend(speciation.pah_direct.iter().map(|p| p.substance.clone()));
+    }
+
+    let parsed = parse_input(&input_file)?;
+    if parsed.reports.is_empty() {
+        return Err(SondaError::InvalidInput(format!(
+            "{} holds no reports to review",
+            input_file.display()
+        )));
+    }
+    // Never overwrite the input by default, even when it is JSON already.
+    let out = output_file.unwrap_or_else(|| input_file.with_extension("reviewed.json"));
+
+    let mut review = Review::new(parsed, known, locale);
+    review::run(&mut review, &input_file.display().to_string(), &out)?;
+
+    if review.dirty {
+        eprintln!("{}", locale.text("cli.review.discarded"));
+    }
+    Ok(())
+}
@@ -1,12 +1,13 @@
+use sonda_core::i18n::Locale;
 use sonda_core::rules::builtin;
 use std::path::Path;
 
-pub fn list() -> Result<(), sonda_core::error::SondaError> {
-    println!("Available predefined rulesets:\n");
+pub fn list(locale: Locale) -> Result<(), sonda_core::error::SondaError> {
+    println!("{}:\n", locale.text("cli.rules.available"));
     for name in builtin::PRESETS {
         if builtin::is_hp_preset(name) {
             println!("  {:<8} Farligt avfall (HP-bedömning)", name);
-            println!("           CLP/HP-based hazardous waste classification per EU Regulation 1357/2014");
+            println!("           {}", locale.text("cli.rules.fa_summary"));
             println!();
         } else {
             let rs = builtin::load_preset(name)?;
@@ -15,7 +16,7 @@ pub fn list() -> Result<(), sonda_core::error::SondaError> {
                 None => String::new(),
             };
             println!("  {:<8} {} (v{}){}", name, rs.name, rs.version, matrix_info);
-            if let Some(ref desc) = rs.description {
+            if let Some(desc) = rs.description_in(locale) {
                 println!("           {}", desc);
             }
             println!();
@@ -24,33 +25,41 @@ pub fn list() -> Result<(), sonda_core::error::SondaError> {
     Ok(())
 }
 
-pub fn explain(preset: &str) -> Result<(), sonda_core::error::SondaError> {
+pub fn explain(preset: &str, locale: Locale) -> Result<(), sonda_core::error::SondaError> {
     if builtin::is_hp_preset(preset) {
-        return explain_fa();
+        return explain_fa(locale);
     }
 
     let rs = builtin::load_preset(preset)?;
 
-    println!("{} (version {})\n", rs.name, rs.version);
+    println!(
+        "{}\n",
+        locale.format(
+            "cli.rules.heading",
+            &[("name", &rs.name), ("version", &rs.version)]
+        )
+    );
 
-    if let Some(ref desc) = rs.description {
+    if let Some(desc) = rs.description_in(locale) {
         println!("{}\n", desc);
     }
 
     let material = match rs.matrix.as_deref() {
-        Some("jord") => "soil",
-        Some("asfalt") => "asphalt",
-        _ => "samples",
+        Some("jord") => locale.text("cli.rules.material.soil"),
+        Some("asfalt") => locale.text("cli.rules.material.asphalt"),
+        _ => locale.text("cli.rules.material.samples"),
     };
     println!(
-        "This ruleset classifies {} into {} categories:\n",
-        material,
-        rs.categories.len()
+        "{}\n",
+        locale.format(
+            "cli.rules.classifies_into",
+            &[("material", &material), ("count", &rs.categories.len())],
+        )
     );
 
     for cat in &rs.categories {
         print!("  {}", cat);
-        if let Some(desc) = rs.category_descriptions.get(cat) {
+        if let Some(desc) = rs.category_description_in(cat, locale) {
             println!(" -- {}", desc);
         } else {
             println!();
@@ -58,11 +67,10 @@ pub fn explain(preset: &str) -> Result<(), sonda_core::error::SondaError> {
         println!();
     }
 
-    println!("If any substance exceeds all thresholds, the soil does not meet");
-    println!("either standard and requires further assessment.\n");
+    println!("{}\n", locale.text("cli.rules.exceeds_all"));
 
     // Print thresholds table
-    println!("Thresholds:");
+    println!("{}:", locale.text("cli.rules.thresholds"));
     println!();
 
     // Find max substance name length for alignment
@@ -74,11 +82,15 @@ pub fn explain(preset: &str) -> Result<(), sonda_core::error::SondaError> {
         .unwrap_or(20);
 
     // Header
-    print!("  {:<width$}", "Substance", width = max_name_len + 2);
+    print!(
+        "  {:<width$}",
+        locale.text("cli.substance"),
+        width = max_name_len + 2
+    );
     for cat in &rs.categories {
         print!("  {:<10}", cat);
     }
-    println!("  Unit");
+    println!("  {}", locale.text("cli.unit"));
     println!(
         "  {}",
         "-".repeat(max_name_len + 2 + (rs.categories.len() * 12) + 10)
@@ -102,28 +114,58 @@ pub fn explain(preset: &str) -> Result<(), sonda_core::error::SondaError> {
     Ok(())
 }
 
-fn explain_fa() -> Result<(), sonda_core::error::SondaError> {
+fn explain_fa(locale: Locale) -> Result<(), sonda_core::error::SondaError> {
     println!("Farligt avfall (HP-bedömning)\n");
-    println!("CLP/HP-based hazardous waste classification per EU Regulation 1357/2014");
-    println!("and Commission Regulation 2017/997 (HP14 ecotoxic).\n");
-    println!("This preset evaluates waste against the Hazardous Properties (HP) criteria.");
-    println!("Result is binary: FA (hazardous waste) or Icke FA (non-hazardous waste).\n");
-    println!("Evaluated HP criteria:\n");
-    println!("  HP4   Irritant              Summation: H315, H319 >= 20%");
-    println!("  HP5   STOT SE/RE            Individual: H370 >= 1%, H371 >= 10%");
-    println!("                              Summation: H372 >= 1%, H373 >= 10%");
-    println!("  HP6   Acute Toxicity        Summation per route and category");
-    println!("  HP7   Carcinogenic          Individual: H350 >= 0.1%, H351 >= 1%");
-    println!("  HP8   Corrosive             Summation: H314 >= 5%");
-    println!("  HP10  Toxic for repro.      Individual: H360 >= 0.3% (SCL: Pb 0.03%)");
-    println!("                              Individual: H361 >= 0.3%");
-    println!("  HP11  Mutagenic             Individual: H340 >= 0.1%, H341 >= 1%");
-    println!("  HP13  Sensitising           Individual: H317/H334 >= 10%");
-    println!("  HP14  Ecotoxic              Multiple summation checks with M-factors\n");
-    println!("Speciation: metals are converted to worst-case CLP compounds using");
-    println!("molecular weight conversion factors (e.g., As -> As2O3 x 1.32).");
-    println!("Concentrations are converted from mg/kg TS to % w/w (divide by 10000).\n");
-    println!("Below-detection values contribute 0 to summation checks.\n");
+    println!("{}\n", locale.text("cli.rules.fa_intro"));
+    println!("{}:\n", locale.text("cli.rules.fa_criteria"));
+
+    let ind = locale.text("cli.rules.individual");
+    let sum = locale.text("cli.rules.summation");
+    let criteria = [
+        ("HP4", vec![format!("{sum}: H315, H319 >= 20%")]),
+        (
+            "HP5",
+            vec![
+                format!("{ind}: H370 >= 1%, H371 >= 10%"),
+                format!("{sum}: H372 >= 1%, H373 >= 10%"),
+            ],
+        ),
+        ("HP6", vec![locale.text("cli.rules.hp6_limits").to_string()]),
+        ("HP7", vec![format!("{ind}: H350 >= 0.1%, H351 >= 1%")]),
+        ("HP8", vec![format!("{sum}: H314 >= 5%")]),
+        (
+            "HP10",
+            vec![
+                format!("{ind}: H360 >= 0.3% (SCL: Pb 0.03%)"),
+                format!("{ind}: H361 >= 0.3%"),
+            ],
+        ),
+        ("HP11", vec![format!("{ind}: H340 >= 0.1%, H341 >= 1%")]),
+        ("HP13", vec![format!("{ind}: H317/H334 >= 10%")]),
+        (
+            "HP14",
+            vec![locale.text("cli.rules.hp14_limits").to_string()],
+        ),
+    ];
+    let name = |id: &str| locale.text(&format!("hp.{id}.name")).to_string();
+    let width = criteria
+        .iter()
+        .map(|(id, _)| name(id).chars().count())
+        .max()
+        .unwrap_or(20);
+
+    for (id, limits) in &criteria {
+        for (i, limit) in limits.iter().enumerate() {
+            let (id, name) = if i == 0 {
+                (*id, name(id))
+            } else {
+                ("", String::new())
+            };
+            println!("  {:<5} {:<width$}  {}", id, name, limit);
+        }
+    }
+    println!();
+    println!("{}\n", locale.text("cli.rules.fa_speciation"));
 
     Ok(())
 }
@@ -148,6 +190,10 @@ Top-level fields:
                 (object, optional)  Map of category name to human-readable
                                     description. Used by `sonda rules explain`.
   rules         (array, required)   List of substance rules (see below)
+  translations  (object, optional)  Description texts in other languages,
+                                    keyed by language ("en", "sv"). Each entry
+                                    may set "description" and
+                                    "category_descriptions". Shown with --lang.
 
 Each rule in the "rules" array:
   substance     (string, required)  Canonical substance name (lowercase).
@@ -215,20 +261,7 @@ pub fn validate(file: &Path) -> Result<(), sonda_core::error::SondaError> {
     println!("  Categories: {}", rs.categories.join(", "));
     println!("  Rules: {} substances", rs.rules.len());
 
-    // Check for potential issues (warnings, not errors)
-    let mut warnings = Vec::new();
-    for rule in &rs.rules {
-        // Warn if not all categories have thresholds
-        for cat in &rs.categories {
-            if !rule.thresholds.contains_key(cat) {
-                warnings.push(format!(
-                    "substance '{}' has no threshold for category '{}'",
-                    rule.substance, cat
-                ));
-            }
-        }
-    }
-
+    let warnings = sonda_core::rules::ruleset_warnings(&rs);
     if !warnings.is_empty() {
         println!("\nWarnings:");
         for w in &warnings {
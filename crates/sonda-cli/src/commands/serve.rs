@@ -0,0 +1,19 @@
+use sonda_core::error::SondaError;
+use sonda_core::i18n::Locale;
+use std::sync::Arc;
+
+use crate::serve;
+
+pub fn run(host: &str, port: u16, jobs: Option<usize>, locale: Locale) -> Result<(), SondaError> {
+    let server = serve::bind(&format!("{host}:{port}"))?;
+    let addr = server.server_addr();
+    let jobs = jobs.unwrap_or_else(|| {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(4)
+    });
+    eprintln!("Listening on http://{addr} ({jobs} workers)");
+    eprintln!("  BBox viewer: http://{addr}/viewer/");
+    serve::run(Arc::new(server), jobs, locale);
+    Ok(())
+}
@@ -1,7 +1,11 @@
 mod commands;
 mod output;
+mod review;
+mod serve;
 
 use clap::{Parser, Subcommand};
+use sonda_core::classify::sensitivity::Variation;
+use sonda_core::i18n::Locale;
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -11,30 +15,60 @@ use std::path::PathBuf;
     about = "Waste classification tool for contaminated soil and asphalt"
 )]
 struct Cli {
+    /// Language of reasons, warnings and headings: en (default) or sv
+    #[arg(long, global = true, value_name = "LANG")]
+    lang: Option<Locale>,
+
     #[command(subcommand)]
     command: Commands,
 }
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Parse a lab report (PDF or Sweco XLSX) into structured data (without classifying)
+    /// Parse lab reports (PDF, Sweco XLSX or Eurofins export) into structured data (without classifying)
     Parse {
-        /// Path to PDF or Sweco XLSX file
-        input_file: PathBuf,
+        /// PDF, XLSX or CSV file(s), directories or glob patterns
+        #[arg(required = true, value_name = "INPUT")]
+        inputs: Vec<String>,
 
-        /// Output format: table (default) or json
+        /// Output format: table (default), json or csv
         #[arg(short, long, default_value = "table")]
         output: String,
 
-        /// Write parsed output to a JSON file
+        /// Write parsed output to a file (JSON, or CSV with --output csv)
         #[arg(short = 'O', long = "out", value_name = "FILE")]
         out: Option<PathBuf>,
+
+        /// Number of files to process in parallel (default: number of CPUs)
+        #[arg(short, long, value_name = "N")]
+        jobs: Option<usize>,
+        /// Column mapping (JSON) describing the layout of spreadsheet inputs
+        #[arg(long, value_name = "FILE")]
+        mapping: Option<PathBuf>,
     },
-    /// Classify a lab report (PDF or pre-parsed JSON)
-    Classify {
-        /// Path to PDF or pre-parsed JSON file
+    /// Review and correct a parsed report interactively, then save it as JSON for classify
+    Review {
+        /// Path to PDF, Sweco XLSX, Eurofins export or pre-parsed JSON file
         input_file: PathBuf,
 
+        /// Where to save the corrected reports (default: <INPUT stem>.reviewed.json)
+        #[arg(short = 'O', long = "out", value_name = "FILE")]
+        out: Option<PathBuf>,
+
+        /// Custom JSON rule file(s) used to flag unrecognised substances
+        #[arg(short, long = "rules", value_name = "FILE")]
+        rules: Vec<PathBuf>,
+
+        /// Predefined ruleset(s): nv, asfalt, fa (default: all presets if no --rules/--preset given)
+        #[arg(short, long = "preset", value_name = "NAME")]
+        preset: Vec<String>,
+    },
+    /// Classify lab reports (PDF, Sweco XLSX, Eurofins export or pre-parsed JSON)
+    Classify {
+        /// PDF, XLSX, CSV or JSON file(s), directories or glob patterns
+        #[arg(required = true, value_name = "INPUT")]
+        inputs: Vec<String>,
+
         /// Custom JSON rule file(s)
         #[arg(short, long = "rules", value_name = "FILE")]
         rules: Vec<PathBuf>,
@@ -43,10 +77,14 @@ enum Commands {
         #[arg(short, long = "preset", value_name = "NAME")]
         preset: Vec<String>,
 
-        /// Output format: table (default) or json
+        /// Output format: table (default), json, csv, html or xlsx
         #[arg(short, long, default_value = "table")]
         output: String,
 
+        /// Output file (required for xlsx, optional for csv and html)
+        #[arg(short = 'O', long = "out", value_name = "FILE")]
+        out: Option<PathBuf>,
+
         /// Show all substances, not just exceedances
         #[arg(long)]
         show_all: bool,
@@ -54,6 +92,100 @@ enum Commands {
         /// Show detailed per-substance reasoning
         #[arg(long)]
         verbose: bool,
+
+        /// Number of files to process in parallel (default: number of CPUs)
+        #[arg(short, long, value_name = "N")]
+        jobs: Option<usize>,
+
+        /// Column mapping (JSON) describing the layout of spreadsheet inputs
+        #[arg(long, value_name = "FILE")]
+        mapping: Option<PathBuf>,
+
+        /// Vary measured values by a percentage (e.g. 10%) or by "uncertainty"
+        /// and report substances that could change the overall category
+        #[arg(long, value_name = "VARIATION")]
+        sensitivity: Option<Variation>,
+
+        /// Override a value before classification: [SAMPLE:]SUBSTANCE=VALUE (e.g. P1:bly=170)
+        #[arg(long = "override", value_name = "OVERRIDE", requires = "justification")]
+        overrides: Vec<String>,
+
+        /// Exclude a substance before classification: [SAMPLE:]SUBSTANCE
+        #[arg(long, value_name = "EXCLUSION", requires = "justification")]
+        exclude: Vec<String>,
+
+        /// Why the overrides/exclusions were made (required with --override/--exclude)
+        #[arg(long, value_name = "TEXT")]
+        justification: Option<String>,
+
+        /// Who made the overrides/exclusions (default: current user)
+        #[arg(long, value_name = "NAME")]
+        by: Option<String>,
+    },
+    /// Estimate disposal tonnage and cost per category from a classified report
+    Cost {
+        /// Path to PDF, Sweco XLSX, Eurofins export or pre-parsed JSON file
+        input_file: PathBuf,
+
+        /// Price list of receiving facilities (JSON)
+        #[arg(long, value_name = "FILE")]
+        prices: PathBuf,
+
+        /// Mass or volume per sample (JSON)
+        #[arg(short, long, value_name = "FILE")]
+        quantities: PathBuf,
+
+        /// Custom JSON rule file(s)
+        #[arg(short, long = "rules", value_name = "FILE")]
+        rules: Vec<PathBuf>,
+
+        /// Predefined ruleset(s): nv, asfalt, fa (default: all presets if no --rules/--preset given)
+        #[arg(short, long = "preset", value_name = "NAME")]
+        preset: Vec<String>,
+
+        /// Output format: table (default) or json
+        #[arg(short, long, default_value = "table")]
+        output: String,
+    },
+    /// Render a PDF classification memo for a lab report
+    Report {
+        /// Path to PDF, Sweco XLSX, Eurofins export or pre-parsed JSON file
+        input_file: PathBuf,
+
+        /// Layout template with company, logo and signature block (JSON)
+        #[arg(short, long, value_name = "FILE")]
+        template: Option<PathBuf>,
+
+        /// Custom JSON rule file(s)
+        #[arg(short, long = "rules", value_name = "FILE")]
+        rules: Vec<PathBuf>,
+
+        /// Predefined ruleset(s): nv, asfalt, fa (default: all presets if no --rules/--preset given)
+        #[arg(short, long = "preset", value_name = "NAME")]
+        preset: Vec<String>,
+
+        /// Output PDF file
+        #[arg(short = 'O', long = "out", value_name = "FILE")]
+        out: PathBuf,
+    },
+    /// Classify every report of a project described by a manifest file
+    Project {
+        #[command(subcommand)]
+        action: ProjectAction,
+    },
+    /// Serve parsing, classification and ruleset endpoints as JSON over HTTP
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+
+        /// Port to listen on
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+
+        /// Number of requests handled concurrently (default: number of CPUs)
+        #[arg(short, long, value_name = "N")]
+        jobs: Option<usize>,
     },
     /// Manage and inspect rulesets
     Rules {
@@ -62,6 +194,36 @@ enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+enum ProjectAction {
+    /// Parse changed inputs, classify all samples and write the combined result
+    Run {
+        /// Path to project manifest (JSON)
+        manifest: PathBuf,
+
+        /// Output format: table (default) or json
+        #[arg(short, long, default_value = "table")]
+        output: String,
+
+        /// Re-parse every input, ignoring the parse cache
+        #[arg(long)]
+        force: bool,
+
+        /// Show all substances, not just exceedances
+        #[arg(long)]
+        show_all: bool,
+
+        /// Show detailed per-substance reasoning
+        #[arg(long)]
+        verbose: bool,
+    },
+    /// Show which inputs changed since the last run
+    Status {
+        /// Path to project manifest (JSON)
+        manifest: PathBuf,
+    },
+}
+
 #[derive(Subcommand)]
 enum RulesAction {
     /// List predefined rulesets
@@ -82,24 +244,85 @@ enum RulesAction {
 
 fn main() {
     let cli = Cli::parse();
+    let locale = cli.lang.unwrap_or_default();
 
     let result = match cli.command {
         Commands::Parse {
-            input_file,
+            inputs,
             output,
             out,
-        } => commands::parse::run(input_file, &output, out),
-        Commands::Classify {
+            jobs,
+            mapping,
+        } => commands::parse::run(inputs, &output, out, jobs, mapping, locale),
+        Commands::Review {
             input_file,
+            out,
+            rules,
+            preset,
+        } => commands::review::run(input_file, out, rules, preset, locale),
+        Commands::Classify {
+            inputs,
             rules,
             preset,
             output,
+            out,
             show_all,
             verbose,
-        } => commands::classify::run(input_file, rules, preset, &output, show_all, verbose),
+            jobs,
+            mapping,
+            sensitivity,
+            overrides,
+            exclude,
+            justification,
+            by,
+        } => commands::classify::build_adjustments(&overrides, &exclude, justification, by)
+            .and_then(|adjustments| {
+                commands::classify::run(
+                    inputs,
+                    rules,
+                    preset,
+                    &output,
+                    out,
+                    show_all,
+                    verbose,
+                    jobs,
+                    mapping,
+                    sensitivity,
+                    adjustments,
+                    locale,
+                )
+            }),
+        Commands::Cost {
+            input_file,
+            prices,
+            quantities,
+            rules,
+            preset,
+            output,
+        } => commands::cost::run(
+            input_file, prices, quantities, rules, preset, &output, locale,
+        ),
+        Commands::Report {
+            input_file,
+            template,
+            rules,
+            preset,
+            out,
+        } => commands::report::run(input_file, template, rules, preset, out, locale),
+        Commands::Project { action } => match action {
+            ProjectAction::Run {
+                manifest,
+                output,
+                force,
+                show_all,
+                verbose,
+            } => commands::project::run(manifest, &output, force, show_all, verbose, cli.lang),
+            ProjectAction::Status { manifest } => commands::project::status(manifest),
+        },
+        Commands::Serve { host, port, jobs } => commands::serve::run(&host, port, jobs, locale),
         Commands::Rules { action } => match action {
-            RulesAction::List => commands::rules::list(),
-            RulesAction::Explain { preset } => commands::rules::explain(&preset),
+            RulesAction::List => commands::rules::list(locale),
+            RulesAction::Explain { preset } => commands::rules::explain(&preset, locale),
             RulesAction::Schema => commands::rules::schema(),
             RulesAction::Validate { file } => commands::rules::validate(&file),
         },
@@ -0,0 +1,158 @@
+use sonda_core::classify::outcome::ClassificationResult;
+use sonda_core::model::{AnalysisReport, AnalysisValue, Matrix};
+
+/// Columns of `sonda parse --output csv`, one row per analysed substance.
+///
+/// These headers are part of the CLI contract; append new columns at the end.
+pub const PARSE_HEADERS: &[&str] = &[
+    "sample_id",
+    "lab_report_id",
+    "matrix",
+    "substance",
+    "raw_name",
+    "value",
+    "below_detection",
+    "unit",
+];
+
+/// Columns of `sonda classify --output csv`, one row per substance and ruleset.
+///
+/// The first columns are identical to [`PARSE_HEADERS`].
+pub const CLASSIFY_HEADERS: &[&str] = &[
+    "sample_id",
+    "lab_report_id",
+    "matrix",
+    "substance",
+    "raw_name",
+    "value",
+    "below_detection",
+    "unit",
+    "ruleset",
+    "category",
+    "uncertain",
+    "reason",
+];
+
+/// Format parsed reports as a long-format CSV table.
+pub fn format_parsed(reports: &[&AnalysisReport]) -> String {
+    let mut out = String::new();
+    push_row(&mut out, PARSE_HEADERS.iter().map(|h| h.to_string()));
+
+    for report in reports {
+        let header = &report.header;
+        let sample_id = header
+            .sample_id
+            .as_deref()
+            .or(header.lab_report_id.as_deref())
+            .unwrap_or("unknown");
+
+        for row in &report.rows {
+            push_row(
+                &mut out,
+                [
+                    sample_id.to_string(),
+                    header.lab_report_id.clone().unwrap_or_default(),
+                    matrix_str(header.matrix),
+                    row.normalized_name.clone(),
+                    row.raw_name.clone(),
+                    row.value.numeric().to_string(),
+                    row.value.is_below_detection().to_string(),
+                    row.unit.to_string(),
+                ],
+            );
+        }
+    }
+
+    out
+}
+
+/// Format classification results as a long-format CSV table.
+pub fn format_classification(results: &[&ClassificationResult]) -> String {
+    let mut out = String::new();
+    push_row(&mut out, CLASSIFY_HEADERS.iter().map(|h| h.to_string()));
+
+    for sample in results.iter().flat_map(|r| &r.samples) {
+        for rs in &sample.ruleset_results {
+            for sr in &rs.substance_results {
+                push_row(
+                    &mut out,
+                    [
+                        sample.sample_id.clone(),
+                        sample.lab_report_id.clone().unwrap_or_default(),
+                        matrix_str(sample.matrix),
+                        sr.substance.clone(),
+                        sr.raw_name.clone(),
+                        sr.value.numeric().to_string(),
+                        matches!(sr.value, AnalysisValue::BelowDetection(_)).to_string(),
+                        sr.unit.clone(),
+                        rs.ruleset_name.clone(),
+                        sr.category.clone(),
+                        sr.uncertain.to_string(),
+                        sr.reason.clone(),
+                    ],
+                );
+            }
+        }
+    }
+
+    out
+}
+
+/// Matrix in the same lowercase form used by the JSON output.
+fn matrix_str(matrix: Option<Matrix>) -> String {
+    matrix
+        .map(|m| m.to_string().to_lowercase())
+        .unwrap_or_default()
+}
+
+fn push_row(out: &mut String, fields: impl IntoIterator<Item = String>) {
+    let fields: Vec<String> = fields.into_iter().map(|f| escape(&f)).collect();
+    out.push_str(&fields.join(","));
+    out.push('\n');
+}
+
+/// Quote a field if it contains a separator, quote or line break (RFC 4180).
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reports() -> Vec<AnalysisReport> {
+        serde_json::from_str(
+            r#"[{
+                "header": { "lab": "Eurofins", "lab_report_id": "AR-1", "sample_id": "P1", "matrix": "jord" },
+                "rows": [
+                    { "raw_name": "Kadmium (Cd)", "normalized_name": "kadmium",
+                      "value": { "Measured": "0.0035" }, "unit": "mg/kg TS" },
+                    { "raw_name": "PAH, summa L", "normalized_name": "pah_l",
+                      "value": { "BelowDetection": "0.15" }, "unit": "mg/kg TS" }
+                ]
+            }]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parsed_csv_keeps_exact_decimals_and_quotes_fields() {
+        let reports = reports();
+        let csv = format_parsed(&reports.iter().collect::<Vec<_>>());
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], PARSE_HEADERS.join(","));
+        assert_eq!(
+            lines[1],
+            "P1,AR-1,jord,kadmium,Kadmium (Cd),0.0035,false,mg/kg TS"
+        );
+        assert_eq!(
+            lines[2],
+            "P1,AR-1,jord,pah_l,\"PAH, summa L\",0.15,true,mg/kg TS"
+        );
+    }
+}
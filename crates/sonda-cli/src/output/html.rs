@@ -0,0 +1,463 @@
+use sonda_core::classify::outcome::{
+    ClassificationResult, HpDetails, RuleSetResult, SampleResult, SubstanceResult,
+};
+use sonda_core::i18n::Locale;
+use sonda_core::trace::{TraceBundle, TraceDecisionTarget, TraceSeverity, TraceStep};
+use std::fmt::Write;
+
+use crate::output::table;
+
+/// Stylesheet inlined into every report so the file has no external references.
+const STYLESHEET: &str = include_str!("report.css");
+
+/// Render classification results as a single self-contained HTML document.
+///
+/// The report holds the per-sample summary, exceedance tables, HP criteria,
+/// warnings and the trace reasoning behind every decision (collapsed by default).
+pub fn render(results: &[&ClassificationResult], locale: Locale) -> String {
+    let mut out = String::new();
+    let sample_count: usize = results.iter().map(|r| r.samples.len()).sum();
+    let title = locale.text("cli.html.title");
+
+    let _ = writeln!(out, "<!doctype html>\n<html lang=\"{locale}\">\n<head>");
+    out.push_str("<meta charset=\"UTF-8\" />\n");
+    out.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\" />\n");
+    let _ = writeln!(out, "<title>{}</title>", escape(title));
+    let _ = writeln!(out, "<style>\n{STYLESHEET}</style>\n</head>\n<body>");
+    let _ = writeln!(out, "<h1>{}</h1>", escape(title));
+    let _ = writeln!(
+        out,
+        "<p class=\"meta\">{} &middot; {}</p>",
+        escape(&locale.format("cli.sample_count", &[("count", &sample_count)])),
+        escape(&locale.format(
+            "cli.generated_by",
+            &[("version", &env!("CARGO_PKG_VERSION"))]
+        ))
+    );
+
+    for result in results {
+        render_warnings(&mut out, result, locale);
+        render_adjustments(&mut out, result, locale);
+        render_overview(&mut out, &result.samples, locale);
+        for sample in &result.samples {
+            render_sample(&mut out, sample, &result.trace, locale);
+        }
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn render_warnings(out: &mut String, result: &ClassificationResult, locale: Locale) {
+    if result.warnings.is_empty() && result.trace.warnings.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(
+        out,
+        "<section class=\"warnings\">\n<h3>{}</h3>\n<ul>",
+        escape(locale.text("cli.warnings"))
+    );
+    for w in &result.warnings {
+        let _ = writeln!(
+            out,
+            "<li>{}</li>",
+            sample_prefixed(&w.sample_id, &w.message)
+        );
+    }
+    for w in &result.trace.warnings {
+        let class = match w.severity {
+            TraceSeverity::Critical => "critical",
+            TraceSeverity::Important => "important",
+            TraceSeverity::Info => "info",
+        };
+        let _ = writeln!(
+            out,
+            "<li class=\"{}\">{}</li>",
+            class,
+            sample_prefixed(&w.sample_id, &w.message)
+        );
+    }
+    out.push_str("</ul>\n</section>\n");
+}
+
+fn render_adjustments(out: &mut String, result: &ClassificationResult, locale: Locale) {
+    if result.adjustments.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(
+        out,
+        "<section class=\"warnings\">\n<h3>{}</h3>\n<ul>",
+        escape(locale.text("cli.adjustments"))
+    );
+    for adj in &result.adjustments {
+        let _ = writeln!(
+            out,
+            "<li class=\"important\">{}</li>",
+            escape(&table::format_adjustment(adj, locale))
+        );
+    }
+    out.push_str("</ul>\n</section>\n");
+}
+
+/// One row per sample and ruleset with the overall category.
+fn render_overview(out: &mut String, samples: &[SampleResult], locale: Locale) {
+    let _ = writeln!(
+        out,
+        "<h2>{}</h2>\n<table>",
+        escape(locale.text("cli.summary"))
+    );
+    header_row(
+        out,
+        locale,
+        &[
+            "cli.sample",
+            "cli.ruleset",
+            "cli.category",
+            "cli.determining_substances",
+        ],
+    );
+    for sample in samples {
+        for rs in &sample.ruleset_results {
+            let _ = writeln!(
+                out,
+                "<tr><td><a href=\"#{}\">{}</a></td><td>{}</td><td class=\"{}\">{}</td><td>{}</td></tr>",
+                anchor(&sample.sample_id),
+                escape(&sample.sample_id),
+                escape(&rs.ruleset_name),
+                category_class(rs),
+                escape(&rs.overall_category),
+                escape(&rs.determining_substances.join(", "))
+            );
+        }
+    }
+    out.push_str("</table>\n");
+}
+
+fn render_sample(out: &mut String, sample: &SampleResult, trace: &TraceBundle, locale: Locale) {
+    let _ = writeln!(
+        out,
+        "<section id=\"{}\">\n<h2>{} {}</h2>",
+        anchor(&sample.sample_id),
+        escape(locale.text("cli.sample")),
+        escape(&sample.sample_id)
+    );
+    if let Some(ref id) = sample.lab_report_id {
+        let _ = writeln!(
+            out,
+            "<p class=\"meta\">{} {}</p>",
+            escape(locale.text("cli.lab_report")),
+            escape(id)
+        );
+    }
+
+    for rs in &sample.ruleset_results {
+        let _ = writeln!(
+            out,
+            "<h3>{}: <span class=\"{}\">{}</span></h3>\n<p>{}</p>",
+            escape(&rs.ruleset_name),
+            category_class(rs),
+            escape(&rs.overall_category),
+            escape(&rs.overall_reason)
+        );
+
+        match rs.hp_details {
+            Some(ref hp) => render_hp(out, hp, locale),
+            None => render_exceedances(out, rs, locale),
+        }
+        render_reasoning(out, &sample.sample_id, rs, trace, locale);
+    }
+
+    out.push_str("</section>\n");
+}
+
+/// Substances that exceeded a threshold, followed by all substances (collapsed).
+fn render_exceedances(out: &mut String, rs: &RuleSetResult, locale: Locale) {
+    let exceedances: Vec<&SubstanceResult> = rs
+        .substance_results
+        .iter()
+        .filter(|sr| sr.exceeded_threshold.is_some())
+        .collect();
+
+    if exceedances.is_empty() {
+        let _ = writeln!(out, "<p>{}</p>", escape(locale.text("cli.no_exceedances")));
+    } else {
+        substance_table(out, &exceedances, locale);
+    }
+
+    if !rs.substance_results.is_empty() {
+        let all: Vec<&SubstanceResult> = rs.substance_results.iter().collect();
+        let _ = writeln!(
+            out,
+            "<details>\n<summary>{}</summary>",
+            escape(&locale.format("cli.all_substances", &[("count", &all.len())]))
+        );
+        substance_table(out, &all, locale);
+        out.push_str("</details>\n");
+    }
+}
+
+fn substance_table(out: &mut String, results: &[&SubstanceResult], locale: Locale) {
+    out.push_str("<table>\n");
+    header_row(
+        out,
+        locale,
+        &[
+            "cli.substance",
+            "cli.value",
+            "cli.unit",
+            "cli.category",
+            "cli.exceeded_threshold",
+        ],
+    );
+    let uncertain_title = escape(locale.text("cli.uncertain_hint"));
+    for sr in results {
+        let class = if sr.exceeded_threshold.is_some() {
+            " class=\"exceed\""
+        } else {
+            ""
+        };
+        let uncertain = if sr.uncertain {
+            format!(" <span class=\"uncertain\" title=\"{uncertain_title}\">(?)</span>")
+        } else {
+            String::new()
+        };
+        let threshold = sr
+            .exceeded_threshold
+            .map(|t| t.to_string())
+            .unwrap_or_default();
+        let _ = writeln!(
+            out,
+            "<tr{}><td>{}</td><td class=\"num\">{}</td><td>{}</td><td>{}{}</td><td class=\"num\">{}</td></tr>",
+            class,
+            escape(&sr.raw_name),
+            escape(&sr.value.to_string()),
+            escape(&sr.unit),
+            escape(&sr.category),
+            uncertain,
+            threshold
+        );
+    }
+    out.push_str("</table>\n");
+}
+
+fn render_hp(out: &mut String, hp: &HpDetails, locale: Locale) {
+    out.push_str("<table>\n");
+    header_row(
+        out,
+        locale,
+        &[
+            "cli.criterion",
+            "cli.name",
+            "cli.result",
+            "cli.contributions",
+            "cli.reason",
+        ],
+    );
+    for c in &hp.criteria_results {
+        let (class, result) = if c.triggered {
+            (" class=\"exceed\"", locale.text("cli.hp.triggered"))
+        } else {
+            ("", locale.text("cli.hp.not_triggered"))
+        };
+        let contributions: Vec<String> = c
+            .contributions
+            .iter()
+            .map(|c| {
+                let marker = if c.triggers { " &#9650;" } else { "" };
+                let text = locale.format(
+                    "cli.hp.contribution",
+                    &[
+                        ("substance", &c.substance),
+                        ("compound", &c.compound),
+                        ("h_code", &c.h_code),
+                        ("concentration", &c.concentration_pct),
+                    ],
+                );
+                format!("{}{}", escape(&text), marker)
+            })
+            .collect();
+        let _ = writeln!(
+            out,
+            "<tr{}><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            class,
+            escape(&c.hp_id),
+            escape(&c.hp_name),
+            escape(result),
+            contributions.join("<br />"),
+            escape(&c.reason)
+        );
+    }
+    out.push_str("</table>\n");
+}
+
+/// Collapsible trace reasoning: how each value was parsed and why it got its category.
+fn render_reasoning(
+    out: &mut String,
+    sample_id: &str,
+    rs: &RuleSetResult,
+    trace: &TraceBundle,
+    locale: Locale,
+) {
+    let decisions: Vec<_> = trace
+        .decisions
+        .iter()
+        .filter(|d| d.sample_id == sample_id && d.ruleset_name == rs.ruleset_name)
+        .collect();
+    if decisions.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(
+        out,
+        "<details>\n<summary>{}</summary>",
+        escape(locale.text("cli.reasoning"))
+    );
+    for d in decisions {
+        let label = match (&d.target, &d.substance) {
+            (TraceDecisionTarget::Substance, Some(s)) => s.clone(),
+            (TraceDecisionTarget::HpCriterion, Some(s)) => s.clone(),
+            _ => locale.text("cli.overall").to_string(),
+        };
+        let _ = writeln!(
+            out,
+            "<details>\n<summary>{} &rarr; {}</summary>\n<p>{}</p>\n<ol>",
+            escape(&label),
+            escape(&d.category),
+            escape(&d.reason)
+        );
+
+        // Parsing steps for the value behind a substance decision.
+        if let Some(ref substance) = d.substance {
+            let entry = trace
+                .entries
+                .iter()
+                .find(|e| e.sample_id == sample_id && &e.normalized_name == substance);
+            if let Some(entry) = entry {
+                for step in &entry.steps {
+                    render_step(out, step, locale);
+                }
+            }
+        }
+        for step in &d.steps {
+            render_step(out, step, locale);
+        }
+        out.push_str("</ol>\n</details>\n");
+    }
+    out.push_str("</details>\n");
+}
+
+fn render_step(out: &mut String, step: &TraceStep, locale: Locale) {
+    let step_type = serde_json::to_value(&step.step_type)
+        .ok()
+        .and_then(|v| {
+            v.as_str()
+                .map(|s| locale.text(&format!("trace.step.{s}")).to_string())
+        })
+        .unwrap_or_default();
+    let _ = writeln!(
+        out,
+        "<li><span class=\"step-type\">{}</span>{}</li>",
+        escape(&step_type),
+        escape(&step.message)
+    );
+}
+
+/// Table header row; `keys` are message keys.
+fn header_row(out: &mut String, locale: Locale, keys: &[&str]) {
+    out.push_str("<tr>");
+    for key in keys {
+        let _ = write!(out, "<th>{}</th>", escape(locale.text(key)));
+    }
+    out.push_str("</tr>\n");
+}
+
+fn category_class(rs: &RuleSetResult) -> &'static str {
+    let exceeds = match rs.hp_details {
+        Some(ref hp) => hp.is_hazardous,
+        None => rs
+            .lowest_category
+            .as_deref()
+            .is_some_and(|lowest| rs.overall_category != lowest),
+    };
+    if exceeds {
+        "exceed"
+    } else {
+        "ok"
+    }
+}
+
+fn sample_prefixed(sample_id: &Option<String>, message: &str) -> String {
+    match sample_id {
+        Some(id) => format!("{}: {}", escape(id), escape(message)),
+        None => escape(message),
+    }
+}
+
+/// Anchor ID for a sample section; non-alphanumeric characters become '-'.
+fn anchor(sample_id: &str) -> String {
+    let id: String = sample_id
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+    format!("sample-{id}")
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sonda_core::model::AnalysisReport;
+    use sonda_core::rules::builtin;
+    use sonda_core::ClassifyOptions;
+
+    #[test]
+    fn renders_escaped_self_contained_report() {
+        let reports: Vec<AnalysisReport> = serde_json::from_str(
+            r#"[{
+                "header": { "lab": "Eurofins", "sample_id": "P1 <a>", "matrix": "jord" },
+                "rows": [
+                    { "raw_name": "Bly (Pb)", "normalized_name": "bly",
+                      "value": { "Measured": "500" }, "unit": "mg/kg TS" },
+                    { "raw_name": "Arsenik (As)", "normalized_name": "arsenik",
+                      "value": { "BelowDetection": "0.5" }, "unit": "mg/kg TS" }
+                ]
+            }]"#,
+        )
+        .unwrap();
+        let rulesets = vec![builtin::load_preset("nv").unwrap()];
+        let options = ClassifyOptions {
+            include_hp: true,
+            ..Default::default()
+        };
+        let result = sonda_core::classify_reports(&reports, &rulesets, &options).unwrap();
+
+        let html = render(&[&result], Locale::En);
+        assert!(html.contains("<style>"));
+        assert!(!html.contains("<link") && !html.contains("<script"));
+        assert!(html.contains("Sample P1 &lt;a&gt;"));
+        assert!(html.contains("id=\"sample-P1--a-\""));
+        assert!(html.contains("<summary>Reasoning</summary>"));
+        assert!(html.contains("<td>HP10</td>"));
+
+        let html = render(&[&result], Locale::Sv);
+        assert!(html.contains("<html lang=\"sv\">"));
+        assert!(html.contains("Prov P1 &lt;a&gt;"));
+        assert!(html.contains("<summary>Motivering</summary>"));
+    }
+}
@@ -1,8 +1,8 @@
-use sonda_core::classify::outcome::ClassificationResult;
+use serde::Serialize;
 use sonda_core::error::SondaError;
 
-pub fn print(result: &ClassificationResult) -> Result<(), SondaError> {
-    let json = serde_json::to_string_pretty(result)?;
+pub fn print<T: Serialize>(value: &T) -> Result<(), SondaError> {
+    let json = serde_json::to_string_pretty(value)?;
     println!("{json}");
     Ok(())
 }
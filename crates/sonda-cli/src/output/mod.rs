@@ -1,2 +1,6 @@
+pub mod csv;
+pub mod html;
 pub mod json;
+pub mod pdf;
 pub mod table;
+pub mod xlsx;
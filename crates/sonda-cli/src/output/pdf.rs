@@ -0,0 +1,673 @@
+use printpdf::image_crate::{self, DynamicImage, GenericImageView};
+use printpdf::{
+    BuiltinFont, Color, Image, ImageTransform, IndirectFontRef, Line, Mm, PdfDocument,
+    PdfDocumentReference, PdfLayerReference, Point, Rgb,
+};
+use serde::Deserialize;
+use sonda_core::classify::outcome::ClassificationResult;
+use sonda_core::error::SondaError;
+use sonda_core::i18n::Locale;
+use sonda_core::model::AnalysisReport;
+use std::path::{Path, PathBuf};
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 20.0;
+/// Top of the body area, below the page header.
+const BODY_TOP: f32 = PAGE_HEIGHT - 36.0;
+/// Bottom of the body area, above the page footer.
+const BODY_BOTTOM: f32 = 25.0;
+const LOGO_MAX_HEIGHT: f32 = 15.0;
+const SIGNATURE_MAX_HEIGHT: f32 = 20.0;
+
+/// Layout and branding of `sonda report`, read from a JSON template file.
+///
+/// Relative image paths are resolved against the directory of the template.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ReportTemplate {
+    /// Company name in the page header.
+    #[serde(default)]
+    pub company: Option<String>,
+    /// Company logo (PNG or JPEG), drawn in the top left of every page.
+    #[serde(default)]
+    pub logo: Option<PathBuf>,
+    /// Document title. Default: "Waste classification memo" in the report language.
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Paragraph printed below the project header.
+    #[serde(default)]
+    pub introduction: Option<String>,
+    /// Replaces the default below-detection policy statement.
+    #[serde(default)]
+    pub below_detection_policy: Option<String>,
+    /// Text in the page footer (e.g., address and organisation number).
+    #[serde(default)]
+    pub footer: Option<String>,
+    #[serde(default)]
+    pub signature: Option<SignatureBlock>,
+}
+
+/// Signature block at the end of the memo.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SignatureBlock {
+    pub name: String,
+    /// Job title (e.g., "Miljökonsult").
+    #[serde(default)]
+    pub role: Option<String>,
+    /// Place printed before the date (e.g., "Stockholm").
+    #[serde(default)]
+    pub place: Option<String>,
+    /// Scanned signature (PNG or JPEG).
+    #[serde(default)]
+    pub image: Option<PathBuf>,
+}
+
+/// Everything the memo states about the classification.
+pub struct ReportData<'a> {
+    pub reports: &'a [AnalysisReport],
+    pub result: &'a ClassificationResult,
+    /// Name and version of every applied threshold ruleset.
+    pub rulesets: Vec<(String, String)>,
+    /// CLP database version, if the HP evaluation was run.
+    pub hp_database_version: Option<String>,
+    pub warnings: Vec<String>,
+    /// Issue date printed in the header and signature block.
+    pub date: String,
+    /// Language of headings and fixed texts.
+    pub locale: Locale,
+}
+
+/// Load a report template from a JSON file.
+pub fn load_template(path: &Path) -> Result<ReportTemplate, SondaError> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Render the classification memo as a paginated PDF.
+pub fn render(
+    data: &ReportData,
+    template: &ReportTemplate,
+    base_dir: &Path,
+) -> Result<Vec<u8>, SondaError> {
+    let logo = template
+        .logo
+        .as_ref()
+        .map(|p| load_image(&base_dir.join(p)))
+        .transpose()?;
+    let signature = template
+        .signature
+        .as_ref()
+        .and_then(|s| s.image.as_ref())
+        .map(|p| load_image(&base_dir.join(p)))
+        .transpose()?;
+
+    let locale = data.locale;
+    let t = |key: &'static str| locale.text(key);
+    let title = template
+        .title
+        .clone()
+        .unwrap_or_else(|| t("cli.pdf.title").into());
+    let mut w = Writer::new(&title, template, logo, locale)?;
+
+    // Project header
+    w.heading(&title, 16.0);
+    let first = |f: fn(&AnalysisReport) -> Option<&String>| {
+        data.reports
+            .iter()
+            .find_map(f)
+            .cloned()
+            .unwrap_or_else(|| "-".into())
+    };
+    let report_ids: Vec<&str> = unique(
+        data.reports
+            .iter()
+            .filter_map(|r| r.header.lab_report_id.as_deref()),
+    );
+    w.field(t("cli.project"), &first(|r| r.header.project.as_ref()));
+    w.field(t("cli.lab"), &first(|r| r.header.lab.as_ref()));
+    w.field(
+        t("cli.lab_reports"),
+        &if report_ids.is_empty() {
+            "-".to_string()
+        } else {
+            report_ids.join(", ")
+        },
+    );
+    w.field(t("cli.date"), &data.date);
+    w.gap(4.0);
+    if let Some(ref intro) = template.introduction {
+        w.paragraph(intro);
+    }
+
+    // Samples
+    w.heading(t("cli.samples"), 12.0);
+    let rows: Vec<Vec<String>> = data
+        .reports
+        .iter()
+        .map(|r| {
+            let h = &r.header;
+            vec![
+                h.sample_id
+                    .clone()
+                    .or(h.lab_report_id.clone())
+                    .unwrap_or_default(),
+                h.lab_report_id.clone().unwrap_or_default(),
+                h.date.map(|d| d.to_string()).unwrap_or_default(),
+                h.matrix.map(|m| m.to_string()).unwrap_or_default(),
+            ]
+        })
+        .collect();
+    w.table(
+        &[
+            (t("cli.sample"), 45.0),
+            (t("cli.lab_report"), 45.0),
+            (t("cli.date"), 40.0),
+            (t("cli.matrix"), 40.0),
+        ],
+        &rows,
+    );
+
+    // Categories per ruleset
+    w.heading(t("cli.classification"), 12.0);
+    let rows: Vec<Vec<String>> = data
+        .result
+        .samples
+        .iter()
+        .flat_map(|s| {
+            s.ruleset_results.iter().map(|rs| {
+                let category = if rs.substance_results.iter().any(|r| r.uncertain) {
+                    format!("{} (?)", rs.overall_category)
+                } else {
+                    rs.overall_category.clone()
+                };
+                vec![
+                    s.sample_id.clone(),
+                    rs.ruleset_name.clone(),
+                    category,
+                    rs.determining_substances.join(", "),
+                ]
+            })
+        })
+        .collect();
+    w.table(
+        &[
+            (t("cli.sample"), 30.0),
+            (t("cli.ruleset"), 55.0),
+            (t("cli.category"), 25.0),
+            (t("cli.determining_substances"), 60.0),
+        ],
+        &rows,
+    );
+    w.small(t("cli.pdf.uncertain_note"));
+
+    // HP summary
+    let hp_rows: Vec<Vec<String>> = data
+        .result
+        .samples
+        .iter()
+        .flat_map(|s| {
+            s.ruleset_results
+                .iter()
+                .filter_map(|rs| rs.hp_details.as_ref())
+                .map(|hp| {
+                    let triggered: Vec<String> = hp
+                        .criteria_results
+                        .iter()
+                        .filter(|c| c.triggered)
+                        .map(|c| format!("{} {}", c.hp_id, c.hp_name))
+                        .collect();
+                    vec![
+                        s.sample_id.clone(),
+                        t(if hp.is_hazardous { "cli.yes" } else { "cli.no" }).to_string(),
+                        if triggered.is_empty() {
+                            "-".to_string()
+                        } else {
+                            triggered.join(", ")
+                        },
+                    ]
+                })
+        })
+        .collect();
+    if !hp_rows.is_empty() {
+        w.heading(t("cli.hp.title"), 12.0);
+        w.table(
+            &[
+                (t("cli.sample"), 30.0),
+                (t("cli.hp.hazardous"), 25.0),
+                (t("cli.hp.triggered_criteria"), 115.0),
+            ],
+            &hp_rows,
+        );
+    }
+
+    // Basis of assessment
+    w.heading(t("cli.pdf.basis"), 12.0);
+    for (name, version) in &data.rulesets {
+        w.bullet(&locale.format(
+            "cli.pdf.ruleset_version",
+            &[("name", name), ("version", version)],
+        ));
+    }
+    if let Some(ref version) = data.hp_database_version {
+        w.bullet(&locale.format("cli.pdf.hp_database", &[("version", version)]));
+    }
+    w.gap(2.0);
+    w.paragraph(
+        template
+            .below_detection_policy
+            .as_deref()
+            .unwrap_or(t("cli.pdf.below_detection_policy")),
+    );
+
+    // Warnings
+    w.heading(t("cli.warnings"), 12.0);
+    if data.warnings.is_empty() {
+        w.paragraph(t("cli.pdf.no_warnings"));
+    } else {
+        for warning in &data.warnings {
+            w.bullet(warning);
+        }
+    }
+
+    if let Some(ref sig) = template.signature {
+        w.signature(sig, signature, &data.date);
+    }
+
+    w.finish()
+}
+
+fn load_image(path: &Path) -> Result<DynamicImage, SondaError> {
+    let bytes = std::fs::read(path)?;
+    image_crate::load_from_memory(&bytes)
+        .map_err(|e| SondaError::InvalidInput(format!("cannot read image {}: {e}", path.display())))
+}
+
+fn unique<'a>(items: impl Iterator<Item = &'a str>) -> Vec<&'a str> {
+    let mut out: Vec<&str> = Vec::new();
+    for item in items {
+        if !out.contains(&item) {
+            out.push(item);
+        }
+    }
+    out
+}
+
+/// Flowing-layout helper on top of printpdf: keeps a cursor and starts new
+/// pages (with header) when the body area is full.
+struct Writer<'t> {
+    doc: PdfDocumentReference,
+    layers: Vec<PdfLayerReference>,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    template: &'t ReportTemplate,
+    logo: Option<DynamicImage>,
+    locale: Locale,
+    y: f32,
+}
+
+impl<'t> Writer<'t> {
+    fn new(
+        title: &str,
+        template: &'t ReportTemplate,
+        logo: Option<DynamicImage>,
+        locale: Locale,
+    ) -> Result<Self, SondaError> {
+        let (doc, page, layer) =
+            PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+        let regular = doc
+            .add_builtin_font(BuiltinFont::Helvetica)
+            .map_err(pdf_error)?;
+        let bold = doc
+            .add_builtin_font(BuiltinFont::HelveticaBold)
+            .map_err(pdf_error)?;
+        let layer = doc.get_page(page).get_layer(layer);
+
+        let mut w = Writer {
+            doc,
+            layers: vec![],
+            regular,
+            bold,
+            template,
+            logo,
+            locale,
+            y: BODY_TOP,
+        };
+        w.start_page(layer);
+        Ok(w)
+    }
+
+    fn layer(&self) -> &PdfLayerReference {
+        self.layers.last().expect("writer always has a page")
+    }
+
+    fn start_page(&mut self, layer: PdfLayerReference) {
+        if let Some(ref logo) = self.logo {
+            place_image(
+                &layer,
+                logo,
+                MARGIN,
+                PAGE_HEIGHT - 12.0,
+                LOGO_MAX_HEIGHT,
+                60.0,
+            );
+        }
+        if let Some(ref company) = self.template.company {
+            let x = PAGE_WIDTH - MARGIN - text_width(company, 11.0);
+            layer.use_text(company, 11.0, Mm(x), Mm(PAGE_HEIGHT - 20.0), &self.bold);
+        }
+        rule(&layer, PAGE_HEIGHT - 32.0, 0.5);
+        self.layers.push(layer);
+        self.y = BODY_TOP;
+    }
+
+    fn new_page(&mut self) {
+        let (page, layer) = self
+            .doc
+            .add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+        let layer = self.doc.get_page(page).get_layer(layer);
+        self.start_page(layer);
+    }
+
+    /// Start a new page unless `height` mm fit above the footer.
+    fn ensure_space(&mut self, height: f32) {
+        if self.y - height < BODY_BOTTOM {
+            self.new_page();
+        }
+    }
+
+    fn gap(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    fn text_at(&self, text: &str, size: f32, x: f32, bold: bool) {
+        let font = if bold { &self.bold } else { &self.regular };
+        self.layer().use_text(text, size, Mm(x), Mm(self.y), font);
+    }
+
+    fn heading(&mut self, text: &str, size: f32) {
+        // Keep a heading together with at least a few lines of what follows.
+        self.ensure_space(line_height(size) + 20.0);
+        self.gap(line_height(size) + 2.0);
+        self.text_at(text, size, MARGIN, true);
+        self.gap(3.0);
+    }
+
+    fn field(&mut self, label: &str, value: &str) {
+        self.ensure_space(line_height(10.0));
+        self.gap(line_height(10.0));
+        self.text_at(label, 10.0, MARGIN, true);
+        self.text_at(value, 10.0, MARGIN + 35.0, false);
+    }
+
+    fn paragraph(&mut self, text: &str) {
+        self.lines(text, 10.0, MARGIN, PAGE_WIDTH - 2.0 * MARGIN);
+        self.gap(2.0);
+    }
+
+    fn small(&mut self, text: &str) {
+        self.lines(text, 8.0, MARGIN, PAGE_WIDTH - 2.0 * MARGIN);
+    }
+
+    fn bullet(&mut self, text: &str) {
+        self.ensure_space(line_height(10.0));
+        self.gap(line_height(10.0));
+        self.text_at("-", 10.0, MARGIN + 2.0, false);
+        self.gap(-line_height(10.0));
+        self.lines(text, 10.0, MARGIN + 6.0, PAGE_WIDTH - 2.0 * MARGIN - 6.0);
+    }
+
+    fn lines(&mut self, text: &str, size: f32, x: f32, width: f32) {
+        for line in wrap(text, width, size) {
+            self.ensure_space(line_height(size));
+            self.gap(line_height(size));
+            self.text_at(&line, size, x, false);
+        }
+    }
+
+    /// Table with a bold header row, repeated on every page it spans.
+    fn table(&mut self, columns: &[(&str, f32)], rows: &[Vec<String>]) {
+        let size = 9.0;
+        let lh = line_height(size);
+        let header = |w: &mut Self| {
+            w.ensure_space(2.0 * lh + 2.0);
+            w.gap(lh);
+            let mut x = MARGIN;
+            for (name, width) in columns {
+                w.text_at(name, size, x, true);
+                x += width;
+            }
+            w.gap(1.5);
+            rule(w.layer(), w.y, 0.3);
+        };
+
+        header(self);
+        for row in rows {
+            let cells: Vec<Vec<String>> = row
+                .iter()
+                .zip(columns)
+                .map(|(cell, (_, width))| wrap(cell, width - 2.0, size))
+                .collect();
+            let height = cells.iter().map(|c| c.len()).max().unwrap_or(1) as f32 * lh;
+            if self.y - height - 1.5 < BODY_BOTTOM {
+                self.new_page();
+                header(self);
+            }
+
+            let top = self.y;
+            let mut x = MARGIN;
+            for (lines, (_, width)) in cells.iter().zip(columns) {
+                self.y = top;
+                for line in lines {
+                    self.gap(lh);
+                    self.text_at(line, size, x, false);
+                }
+                x += width;
+            }
+            self.y = top - height - 1.5;
+            rule(self.layer(), self.y, 0.1);
+        }
+        self.gap(3.0);
+    }
+
+    fn signature(&mut self, sig: &SignatureBlock, image: Option<DynamicImage>, date: &str) {
+        self.ensure_space(SIGNATURE_MAX_HEIGHT + 30.0);
+        self.gap(10.0);
+        let place_date = match sig.place {
+            Some(ref place) => format!("{place}, {date}"),
+            None => date.to_string(),
+        };
+        self.gap(line_height(10.0));
+        self.text_at(&place_date, 10.0, MARGIN, false);
+        self.gap(2.0);
+        if let Some(ref image) = image {
+            place_image(
+                self.layer(),
+                image,
+                MARGIN,
+                self.y,
+                SIGNATURE_MAX_HEIGHT,
+                60.0,
+            );
+            self.gap(SIGNATURE_MAX_HEIGHT);
+        } else {
+            self.gap(12.0);
+        }
+        rule_at(self.layer(), self.y, MARGIN, MARGIN + 70.0, 0.3);
+        self.gap(line_height(10.0));
+        self.text_at(&sig.name, 10.0, MARGIN, true);
+        if let Some(ref role) = sig.role {
+            self.gap(line_height(10.0));
+            self.text_at(role, 10.0, MARGIN, false);
+        }
+    }
+
+    /// Write footers with page numbers and serialize the document.
+    fn finish(self) -> Result<Vec<u8>, SondaError> {
+        let total = self.layers.len();
+        for (i, layer) in self.layers.iter().enumerate() {
+            rule(layer, BODY_BOTTOM - 5.0, 0.3);
+            if let Some(ref footer) = self.template.footer {
+                layer.use_text(
+                    footer,
+                    8.0,
+                    Mm(MARGIN),
+                    Mm(BODY_BOTTOM - 10.0),
+                    &self.regular,
+                );
+            }
+            let page = self
+                .locale
+                .format("cli.pdf.page", &[("page", &(i + 1)), ("total", &total)]);
+            let x = PAGE_WIDTH - MARGIN - text_width(&page, 8.0);
+            layer.use_text(page, 8.0, Mm(x), Mm(BODY_BOTTOM - 10.0), &self.regular);
+        }
+        self.doc.save_to_bytes().map_err(pdf_error)
+    }
+}
+
+fn pdf_error(e: printpdf::Error) -> SondaError {
+    SondaError::Io(std::io::Error::other(format!("failed to write pdf: {e}")))
+}
+
+/// Draw `image` with its top-left corner at (x, top), scaled to fit the box.
+fn place_image(
+    layer: &PdfLayerReference,
+    image: &DynamicImage,
+    x: f32,
+    top: f32,
+    max_height: f32,
+    max_width: f32,
+) {
+    // printpdf places one pixel per dot at the given DPI.
+    let dpi = 300.0;
+    let (px_w, px_h) = image.dimensions();
+    let natural_w = px_w as f32 / dpi * 25.4;
+    let natural_h = px_h as f32 / dpi * 25.4;
+    let scale = (max_height / natural_h).min(max_width / natural_w);
+
+    // Drop the alpha channel; printpdf does not support soft masks for all formats.
+    let rgb = DynamicImage::ImageRgb8(image.to_rgb8());
+    Image::from_dynamic_image(&rgb).add_to_layer(
+        layer.clone(),
+        ImageTransform {
+            translate_x: Some(Mm(x)),
+            translate_y: Some(Mm(top - natural_h * scale)),
+            scale_x: Some(scale),
+            scale_y: Some(scale),
+            dpi: Some(dpi),
+            ..Default::default()
+        },
+    );
+}
+
+fn rule(layer: &PdfLayerReference, y: f32, thickness: f32) {
+    rule_at(layer, y, MARGIN, PAGE_WIDTH - MARGIN, thickness);
+}
+
+fn rule_at(layer: &PdfLayerReference, y: f32, x1: f32, x2: f32, thickness: f32) {
+    layer.set_outline_color(Color::Rgb(Rgb::new(0.4, 0.4, 0.4, None)));
+    layer.set_outline_thickness(thickness);
+    layer.add_line(Line {
+        points: vec![
+            (Point::new(Mm(x1), Mm(y)), false),
+            (Point::new(Mm(x2), Mm(y)), false),
+        ],
+        is_closed: false,
+    });
+}
+
+fn line_height(size: f32) -> f32 {
+    size * 0.3528 * 1.35
+}
+
+/// Approximate width in mm of Helvetica text (average glyph width ≈ 0.5 em).
+fn text_width(text: &str, size: f32) -> f32 {
+    text.chars().count() as f32 * size * 0.5 * 0.3528
+}
+
+/// Greedy word wrap to a column width in mm.
+fn wrap(text: &str, width: f32, size: f32) -> Vec<String> {
+    let max_chars = ((width / (size * 0.5 * 0.3528)) as usize).max(1);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let needed =
+            current.chars().count() + usize::from(!current.is_empty()) + word.chars().count();
+        if !current.is_empty() && needed > max_chars {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sonda_core::rules::builtin;
+    use sonda_core::ClassifyOptions;
+
+    #[test]
+    fn wrap_breaks_on_word_boundaries() {
+        let lines = wrap("Bly (Pb), Kadmium (Cd), Arsenik (As)", 20.0, 9.0);
+        assert!(lines.len() > 1);
+        assert_eq!(lines.join(" "), "Bly (Pb), Kadmium (Cd), Arsenik (As)");
+        assert_eq!(wrap("", 20.0, 9.0), vec![String::new()]);
+    }
+
+    #[test]
+    fn renders_multi_page_memo() {
+        let rows = (0..40)
+            .map(|i| {
+                format!(
+                    r#"{{ "raw_name": "Ämne {i}", "normalized_name": "bly",
+                        "value": {{ "Measured": "{i}" }}, "unit": "mg/kg TS" }}"#
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        let sample = |id: usize| {
+            format!(
+                r#"{{ "header": {{ "lab": "Eurofins", "sample_id": "P{id}", "matrix": "jord",
+                    "project": "Kvarteret Björken" }}, "rows": [{rows}] }}"#
+            )
+        };
+        let json = format!("[{}]", (0..30).map(sample).collect::<Vec<_>>().join(","));
+        let reports: Vec<AnalysisReport> = serde_json::from_str(&json).unwrap();
+        let nv = builtin::load_preset("nv").unwrap();
+        let options = ClassifyOptions {
+            include_hp: true,
+            ..Default::default()
+        };
+        let result = sonda_core::classify_reports(&reports, &[nv], &options).unwrap();
+
+        let data = ReportData {
+            reports: &reports,
+            result: &result,
+            rulesets: vec![("NV".into(), "2025.1".into())],
+            hp_database_version: Some("2025.1".into()),
+            warnings: vec!["1 line(s) skipped".into()],
+            date: "2026-01-01".into(),
+            locale: Locale::Sv,
+        };
+        let template = ReportTemplate {
+            company: Some("Konsult AB".into()),
+            signature: Some(SignatureBlock {
+                name: "Anna Andersson".into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let pdf = render(&data, &template, Path::new(".")).unwrap();
+        assert!(pdf.starts_with(b"%PDF"));
+        let doc = printpdf::lopdf::Document::load_mem(&pdf).unwrap();
+        assert!(doc.get_pages().len() > 1);
+    }
+}
@@ -1,8 +1,14 @@
-use sonda_core::classify::outcome::ClassificationResult;
+use crate::commands::batch::{BatchClassification, BatchParse, BatchSummary};
+use sonda_core::adjust::{AdjustmentAction, ManualAdjustment};
+use sonda_core::classify::outcome::{ClassificationResult, Margin, RuleSetResult, ThresholdMargin};
+use sonda_core::classify::sensitivity::Variation;
+use sonda_core::disposal::CostEstimate;
+use sonda_core::i18n::Locale;
 use sonda_core::parsing::ParsedReports;
+use sonda_core::project::{InputState, ProjectResult};
 
 /// Format parsed reports as a human-readable table.
-pub fn format_parsed(parsed: &ParsedReports) -> String {
+pub fn format_parsed(parsed: &ParsedReports, locale: Locale) -> String {
     let mut out = String::new();
 
     let multi_sample = parsed.reports.len() > 1;
@@ -18,29 +24,45 @@ pub fn format_parsed(parsed: &ParsedReports) -> String {
                 .as_deref()
                 .or(report.header.lab_report_id.as_deref())
                 .unwrap_or("unknown");
-            out.push_str(&format!("--- Sample: {} ---\n\n", sample_id));
+            out.push_str(&format!(
+                "--- {}: {} ---\n\n",
+                locale.text("cli.sample"),
+                sample_id
+            ));
         }
 
         // Header info
-        if let Some(ref lab) = report.header.lab {
-            out.push_str(&format!("  Lab:       {}\n", lab));
-        }
-        if let Some(ref id) = report.header.lab_report_id {
-            out.push_str(&format!("  Report ID: {}\n", id));
-        }
-        if let Some(ref id) = report.header.sample_id {
-            out.push_str(&format!("  Sample ID: {}\n", id));
-        }
-        if let Some(ref matrix) = report.header.matrix {
-            out.push_str(&format!("  Matrix:    {}\n", matrix));
-        }
-        if let Some(ref date) = report.header.date {
-            out.push_str(&format!("  Date:      {}\n", date));
+        let h = &report.header;
+        let fields = [
+            ("cli.lab", h.lab.clone()),
+            ("cli.report_id", h.lab_report_id.clone()),
+            ("cli.sample_id", h.sample_id.clone()),
+            ("cli.matrix", h.matrix.map(|m| m.to_string())),
+            ("cli.date", h.date.map(|d| d.to_string())),
+            ("cli.received", h.received.map(|d| d.to_string())),
+            (
+                "cli.analysis_period",
+                h.analysis_period.map(|p| p.to_string()),
+            ),
+            ("cli.depth", h.depth.as_ref().map(|d| d.to_string())),
+            ("cli.sampler", h.sampler.clone()),
+            ("cli.location", h.location.clone()),
+            (
+                "cli.coordinates",
+                h.coordinates.as_ref().map(|c| c.to_string()),
+            ),
+            ("cli.order_number", h.order_number.clone()),
+        ];
+        for (key, value) in fields {
+            if let Some(value) = value {
+                let label = format!("{}:", locale.text(key));
+                out.push_str(&format!("  {:<13} {}\n", label, value));
+            }
         }
         out.push('\n');
 
         if report.rows.is_empty() {
-            out.push_str("  (no substances parsed)\n");
+            out.push_str(&format!("  {}\n", locale.text("cli.no_substances")));
             continue;
         }
 
@@ -51,28 +73,29 @@ pub fn format_parsed(parsed: &ParsedReports) -> String {
             .map(|r| r.raw_name.len())
             .max()
             .unwrap_or(10)
-            .max(8);
+            .max(locale.text("cli.raw_name").chars().count());
         let max_norm = report
             .rows
             .iter()
             .map(|r| r.normalized_name.len())
             .max()
             .unwrap_or(10)
-            .max(10);
+            .max(locale.text("cli.normalized").chars().count());
         let max_val = report
             .rows
             .iter()
             .map(|r| format!("{}", r.value).len())
             .max()
             .unwrap_or(8)
-            .max(5);
+            .max(locale.text("cli.value").chars().count());
 
         // Header
         out.push_str(&format!(
-            "  {:<raw_w$}  {:<norm_w$}  {:>val_w$}  Unit\n",
-            "Raw name",
-            "Normalized",
-            "Value",
+            "  {:<raw_w$}  {:<norm_w$}  {:>val_w$}  {}\n",
+            locale.text("cli.raw_name"),
+            locale.text("cli.normalized"),
+            locale.text("cli.value"),
+            locale.text("cli.unit"),
             raw_w = max_raw,
             norm_w = max_norm,
             val_w = max_val,
@@ -99,17 +122,20 @@ pub fn format_parsed(parsed: &ParsedReports) -> String {
 
     // Warnings
     if !parsed.warnings.is_empty() {
-        out.push_str("\nWarnings:\n");
+        out.push_str(&format!("\n{}:\n", locale.text("cli.warnings")));
         for w in &parsed.warnings {
-            out.push_str(&format!("  - section {}: {}\n", w.section_index, w.reason));
+            out.push_str(&format!("  - {}\n", w.message(locale)));
         }
     }
 
     // Skipped lines
     if !parsed.skipped_lines.is_empty() {
         out.push_str(&format!(
-            "\nSkipped lines ({}):\n",
-            parsed.skipped_lines.len()
+            "\n{}:\n",
+            locale.format(
+                "cli.skipped_lines",
+                &[("count", &parsed.skipped_lines.len())]
+            )
         ));
         for sl in &parsed.skipped_lines {
             out.push_str(&format!("  - [{}] {}\n", sl.reason, sl.line_text));
@@ -119,15 +145,23 @@ pub fn format_parsed(parsed: &ParsedReports) -> String {
     out
 }
 
-pub fn print(result: &ClassificationResult, show_all: bool, verbose: bool) {
+pub fn print(result: &ClassificationResult, show_all: bool, verbose: bool, locale: Locale) {
     if !result.warnings.is_empty() {
-        println!("Warnings:\n");
+        println!("{}:\n", locale.text("cli.warnings"));
         for w in &result.warnings {
             println!("  - {}", w.message);
         }
         println!();
     }
 
+    if !result.adjustments.is_empty() {
+        println!("{}:\n", locale.text("cli.adjustments"));
+        for adj in &result.adjustments {
+            println!("  - {}", format_adjustment(adj, locale));
+        }
+        println!();
+    }
+
     let multi_sample = result.samples.len() > 1;
 
     for (i, sample) in result.samples.iter().enumerate() {
@@ -135,7 +169,11 @@ pub fn print(result: &ClassificationResult, show_all: bool, verbose: bool) {
             if i > 0 {
                 println!();
             }
-            println!("--- Sample: {} ---\n", sample.sample_id);
+            println!(
+                "--- {}: {} ---\n",
+                locale.text("cli.sample"),
+                sample.sample_id
+            );
         }
 
         for rs_result in &sample.ruleset_results {
@@ -143,15 +181,18 @@ pub fn print(result: &ClassificationResult, show_all: bool, verbose: bool) {
 
             // HP-based output
             if let Some(ref hp) = rs_result.hp_details {
-                print_hp_result(rs_result, hp, verbose);
+                print_hp_result(rs_result, hp, verbose, locale);
+                print_sensitivity(rs_result, locale);
                 continue;
             }
 
             // Threshold-based output (existing logic)
             // Overall classification
             println!(
-                "  Overall: {} ({})\n",
-                rs_result.overall_category, rs_result.overall_reason
+                "  {}: {} ({})\n",
+                locale.text("cli.overall"),
+                rs_result.overall_category,
+                rs_result.overall_reason
             );
 
             // Per-substance results
@@ -178,6 +219,11 @@ pub fn print(result: &ClassificationResult, show_all: bool, verbose: bool) {
                         );
                         if verbose {
                             println!("    {}", sr.reason);
+                            if let Some(margin) =
+                                sr.margin.as_ref().and_then(|m| format_margin(m, locale))
+                            {
+                                println!("    {}: {}", locale.text("cli.margin"), margin);
+                            }
                         }
                     }
                     println!();
@@ -195,7 +241,7 @@ pub fn print(result: &ClassificationResult, show_all: bool, verbose: bool) {
                             .collect();
 
                         if !determining.is_empty() {
-                            println!("  Determining substances:");
+                            println!("  {}:", locale.text("cli.determining_substances"));
                             for sr in &determining {
                                 let uncertain_marker = if sr.uncertain { " (?)" } else { "" };
                                 let threshold_info = match sr.exceeded_threshold {
@@ -217,26 +263,113 @@ pub fn print(result: &ClassificationResult, show_all: bool, verbose: bool) {
             if verbose {
                 if !rs_result.unmatched_rules.is_empty() {
                     println!(
-                        "  Rules without matching report data: {}",
+                        "  {}: {}",
+                        locale.text("cli.unmatched_rules"),
                         rs_result.unmatched_rules.join(", ")
                     );
                 }
                 if !rs_result.unmatched_substances.is_empty() {
                     println!(
-                        "  Report substances without rules: {}",
+                        "  {}: {}",
+                        locale.text("cli.unmatched_substances"),
                         rs_result.unmatched_substances.join(", ")
                     );
                 }
                 println!();
             }
+
+            print_sensitivity(rs_result, locale);
         }
     }
 }
 
+/// One manual adjustment, e.g. "P1: Bly (Pb) 120 -> 170 (anna: re-analysis)".
+pub fn format_adjustment(adj: &ManualAdjustment, locale: Locale) -> String {
+    let key = match adj.action {
+        AdjustmentAction::Override => "cli.adjustment.override",
+        AdjustmentAction::Exclude => "cli.adjustment.exclude",
+    };
+    let value = adj
+        .value
+        .as_ref()
+        .map(|v| v.to_string())
+        .unwrap_or_default();
+    let author = adj
+        .author
+        .as_deref()
+        .unwrap_or_else(|| locale.text("trace.unknown_author"));
+    locale.format(
+        key,
+        &[
+            ("sample", &adj.sample_id),
+            ("substance", &adj.raw_name),
+            ("original", &adj.original),
+            ("value", &value),
+            ("author", &author),
+            ("justification", &adj.justification),
+        ],
+    )
+}
+
+/// Headroom to the neighbouring thresholds, e.g. "+10 (5.88%) to > MKM, -120 (70.59%) to KM".
+fn format_margin(margin: &Margin, locale: Locale) -> Option<String> {
+    let part = |sign: char, m: &ThresholdMargin| {
+        let amount = match m.relative_pct {
+            Some(pct) => format!("{}{} ({}%)", sign, m.absolute, pct),
+            None => format!("{}{}", sign, m.absolute),
+        };
+        locale.format(
+            "cli.margin.to",
+            &[("amount", &amount), ("category", &m.category)],
+        )
+    };
+    let parts: Vec<String> = margin
+        .to_worse
+        .iter()
+        .map(|m| part('+', m))
+        .chain(margin.to_better.iter().map(|m| part('-', m)))
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+fn print_sensitivity(rs_result: &RuleSetResult, locale: Locale) {
+    let Some(sensitivity) = &rs_result.sensitivity else {
+        return;
+    };
+    let variation = match sensitivity.variation {
+        Variation::Uncertainty => locale.text("cli.sensitivity.uncertainty").to_string(),
+        v => v.to_string(),
+    };
+    println!(
+        "  {}",
+        locale.format(
+            "cli.sensitivity.title",
+            &[
+                ("variation", &variation),
+                ("low", &sensitivity.overall_low),
+                ("high", &sensitivity.overall_high),
+            ],
+        )
+    );
+    if sensitivity.flips.is_empty() {
+        println!("    {}\n", locale.text("cli.sensitivity.none"));
+        return;
+    }
+    println!("    {}:", locale.text("cli.sensitivity.flips"));
+    for f in &sensitivity.flips {
+        println!(
+            "      {} {} ({}..{}) -> {} / {}",
+            f.raw_name, f.value, f.low, f.high, f.overall_low, f.overall_high
+        );
+    }
+    println!();
+}
+
 fn print_hp_result(
     rs_result: &sonda_core::classify::outcome::RuleSetResult,
     hp: &sonda_core::classify::outcome::HpDetails,
     verbose: bool,
+    locale: Locale,
 ) {
     // Overall classification
     let triggered_ids: Vec<&str> = hp
@@ -248,21 +381,29 @@ fn print_hp_result(
 
     if hp.is_hazardous {
         println!(
-            "  Overall: {} (triggered by {})\n",
+            "  {}: {} ({})\n",
+            locale.text("cli.overall"),
             rs_result.overall_category,
-            triggered_ids.join(", ")
+            locale.format(
+                "cli.hp.triggered_by",
+                &[("criteria", &triggered_ids.join(", "))]
+            )
         );
     } else {
-        println!("  Overall: {}\n", rs_result.overall_category);
+        println!(
+            "  {}: {}\n",
+            locale.text("cli.overall"),
+            rs_result.overall_category
+        );
     }
 
     if verbose {
         // Verbose: show all criteria with details
         for cr in &hp.criteria_results {
             let status = if cr.triggered {
-                "TRIGGERED"
+                locale.text("cli.hp.triggered").to_uppercase()
             } else {
-                "not triggered"
+                locale.text("cli.hp.not_triggered").to_string()
             };
             println!("  {} ({}): {}", cr.hp_id, cr.hp_name, status);
 
@@ -294,7 +435,7 @@ fn print_hp_result(
     } else {
         // Non-verbose: only show triggered criteria
         if hp.is_hazardous {
-            println!("  Triggered HP criteria:");
+            println!("  {}:", locale.text("cli.hp.triggered_criteria"));
             for cr in &hp.criteria_results {
                 if !cr.triggered {
                     continue;
@@ -328,3 +469,259 @@ fn print_hp_result(
         }
     }
 }
+
+/// Format a batch of parsed files, one section per file.
+pub fn format_parsed_batch(batch: &BatchParse, locale: Locale) -> String {
+    let mut out = String::new();
+
+    for f in &batch.files {
+        out.push_str(&format!("### {}\n\n", f.path.display()));
+        match (&f.parsed, &f.error) {
+            (Some(parsed), _) => out.push_str(&format_parsed(parsed, locale)),
+            (None, Some(err)) => {
+                out.push_str(&format!("  {}: {}\n", locale.text("cli.error"), err))
+            }
+            (None, None) => {}
+        }
+        out.push('\n');
+    }
+
+    out.push_str(&format!("=== {} ===\n\n", locale.text("cli.batch_summary")));
+    out.push_str(&summary_counts(
+        batch.summary.files,
+        batch.summary.files_failed,
+        batch.summary.samples,
+        locale,
+    ));
+    out
+}
+
+/// "Files: N (M failed)" and "Samples: N" lines of a batch summary.
+fn summary_counts(files: usize, failed: usize, samples: usize, locale: Locale) -> String {
+    let files_label = format!("{}:", locale.text("cli.files"));
+    let samples_label = format!("{}:", locale.text("cli.samples"));
+    format!(
+        "  {:<9} {} ({})\n  {:<9} {}\n",
+        files_label,
+        files,
+        locale.format("cli.files_failed", &[("count", &failed)]),
+        samples_label,
+        samples
+    )
+}
+
+pub fn print_batch(batch: &BatchClassification, show_all: bool, verbose: bool, locale: Locale) {
+    for f in &batch.files {
+        println!("### {}\n", f.path.display());
+        match (&f.result, &f.error) {
+            (Some(result), _) => print(result, show_all, verbose, locale),
+            (None, Some(err)) => println!("  {}: {}\n", locale.text("cli.error"), err),
+            (None, None) => {}
+        }
+    }
+
+    print_batch_summary(&batch.summary, locale);
+
+    if !batch.warnings.is_empty() {
+        println!("  {}:", locale.text("cli.warnings"));
+        for w in &batch.warnings {
+            println!("    - {}", w.message);
+        }
+        println!();
+    }
+
+    let failed: Vec<_> = batch.files.iter().filter(|f| f.error.is_some()).collect();
+    if !failed.is_empty() {
+        println!("  {}:", locale.text("cli.failed_files"));
+        for f in failed {
+            println!(
+                "    {}: {}",
+                f.path.display(),
+                f.error.as_deref().unwrap_or_default()
+            );
+        }
+        println!();
+    }
+}
+
+fn print_batch_summary(summary: &BatchSummary, locale: Locale) {
+    println!("=== {} ===\n", locale.text("cli.batch_summary"));
+    println!(
+        "{}",
+        summary_counts(summary.files, summary.files_failed, summary.samples, locale)
+    );
+
+    let mut current_ruleset: Option<&str> = None;
+    for c in &summary.categories {
+        if current_ruleset != Some(c.ruleset_name.as_str()) {
+            if current_ruleset.is_some() {
+                println!();
+            }
+            println!("  {}", c.ruleset_name);
+            current_ruleset = Some(c.ruleset_name.as_str());
+        }
+        println!("    {:<16} {}", c.category, c.samples);
+    }
+    if current_ruleset.is_some() {
+        println!();
+    }
+}
+
+pub fn print_cost(estimate: &CostEstimate, locale: Locale) {
+    println!(
+        "=== {} ===\n",
+        locale.format(
+            "cli.cost.title",
+            &[
+                ("price_list", &estimate.price_list),
+                ("currency", &estimate.currency)
+            ],
+        )
+    );
+
+    let max_id = estimate
+        .samples
+        .iter()
+        .map(|s| s.sample_id.len())
+        .max()
+        .unwrap_or(6)
+        .max(locale.text("cli.sample").chars().count());
+    let max_cat = estimate
+        .samples
+        .iter()
+        .filter_map(|s| s.category.as_ref().map(|c| c.len()))
+        .max()
+        .unwrap_or(8)
+        .max(locale.text("cli.category").chars().count());
+
+    println!(
+        "  {:<id_w$}  {:<cat_w$}  {:<10}  {:>10}  {:>12}  {:>14}",
+        locale.text("cli.sample"),
+        locale.text("cli.category"),
+        locale.text("cli.waste_code"),
+        locale.text("cli.tonnes"),
+        locale.text("cli.price_per_tonne"),
+        locale.text("cli.cost"),
+        id_w = max_id,
+        cat_w = max_cat,
+    );
+    println!("  {}", "-".repeat(max_id + max_cat + 56));
+
+    for s in &estimate.samples {
+        println!(
+            "  {:<id_w$}  {:<cat_w$}  {:<10}  {:>10}  {:>12}  {:>14}",
+            s.sample_id,
+            s.category.as_deref().unwrap_or("-"),
+            s.waste_code.as_deref().unwrap_or("-"),
+            opt_decimal(s.tonnes),
+            opt_decimal(s.price_per_tonne),
+            opt_decimal(s.cost),
+            id_w = max_id,
+            cat_w = max_cat,
+        );
+    }
+    println!();
+
+    if !estimate.categories.is_empty() {
+        println!("  {}:", locale.text("cli.cost.per_category"));
+        for c in &estimate.categories {
+            println!(
+                "    {} [{}] -> {}: {}",
+                c.category,
+                c.waste_code,
+                c.facility,
+                locale.format(
+                    "cli.cost.category_totals",
+                    &[
+                        ("count", &c.sample_count),
+                        ("tonnes", &c.tonnes),
+                        ("cost", &c.cost),
+                        ("currency", &estimate.currency),
+                    ],
+                )
+            );
+        }
+        println!();
+    }
+
+    println!(
+        "  {}: {} t, {} {}\n",
+        locale.text("cli.total"),
+        estimate.total_tonnes,
+        estimate.total_cost,
+        estimate.currency
+    );
+
+    if !estimate.warnings.is_empty() {
+        println!("{}:\n", locale.text("cli.warnings"));
+        for w in &estimate.warnings {
+            println!("  - {}", w);
+        }
+        println!();
+    }
+}
+
+fn opt_decimal<T: std::fmt::Display>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_else(|| "-".into())
+}
+
+pub fn print_project(result: &ProjectResult, show_all: bool, verbose: bool, locale: Locale) {
+    println!("{}: {}\n", locale.text("cli.project"), result.name);
+
+    println!("{}:\n", locale.text("cli.inputs"));
+    for input in &result.inputs {
+        let state = match input.state {
+            InputState::Parsed => locale.text("cli.input.parsed").to_string(),
+            InputState::Cached => locale.text("cli.input.cached").to_string(),
+            InputState::Failed => locale.text("cli.input.failed").to_uppercase(),
+        };
+        println!(
+            "  {:<9} {}  ({})",
+            state,
+            input.path.display(),
+            locale.format("cli.sample_count", &[("count", &input.sample_ids.len())])
+        );
+    }
+    println!();
+
+    let max_id = result
+        .sample_info
+        .iter()
+        .map(|s| s.sample_id.len())
+        .max()
+        .unwrap_or(6)
+        .max(locale.text("cli.sample").chars().count());
+    println!(
+        "  {:<id_w$}  {:<8}  {:<12}  {}",
+        locale.text("cli.sample"),
+        locale.text("cli.lot"),
+        locale.text("cli.depth"),
+        locale.text("cli.source"),
+        id_w = max_id
+    );
+    println!("  {}", "-".repeat(max_id + 40));
+    for info in &result.sample_info {
+        let depth = match (info.depth_from_m, info.depth_to_m) {
+            (Some(from), Some(to)) => format!("{}-{}", from, to),
+            (Some(from), None) => format!("{}-", from),
+            (None, Some(to)) => format!("-{}", to),
+            (None, None) => "-".into(),
+        };
+        println!(
+            "  {:<id_w$}  {:<8}  {:<12}  {}",
+            info.sample_id,
+            info.lot.as_deref().unwrap_or("-"),
+            depth,
+            info.source.display(),
+            id_w = max_id
+        );
+    }
+    println!();
+
+    print(&result.classification, show_all, verbose, locale);
+
+    if let Some(ref cost) = result.cost {
+        println!();
+        print_cost(cost, locale);
+    }
+}
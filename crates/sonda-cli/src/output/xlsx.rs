@@ -0,0 +1,322 @@
+use rust_xlsxwriter::{Color, Format, FormatAlign, FormatBorder, Workbook, Worksheet, XlsxError};
+use sonda_core::classify::outcome::{ClassificationResult, SubstanceResult};
+use sonda_core::error::SondaError;
+use sonda_core::i18n::Locale;
+use sonda_core::model::AnalysisValue;
+use std::path::Path;
+
+/// Title written in cell A1 of every sheet, following the Sweco
+/// "AVFALLSKLASSNING@SWECO" workbook layout.
+const TITLE: &str = "AVFALLSKLASSNING";
+
+/// First row of the column headers (0-indexed). Rows above hold the title.
+const HEADER_ROW: u32 = 2;
+
+/// Fill for cells that exceed a threshold, matching the Sweco sheets.
+const EXCEEDANCE_FILL: u32 = 0xFFC7CE;
+const HEADER_FILL: u32 = 0xD9E1F2;
+
+struct Formats {
+    title: Format,
+    header: Format,
+    cell: Format,
+    exceedance: Format,
+}
+
+impl Formats {
+    fn new() -> Self {
+        let cell = Format::new().set_border(FormatBorder::Thin);
+        Formats {
+            title: Format::new().set_bold().set_font_size(14),
+            header: Format::new()
+                .set_bold()
+                .set_border(FormatBorder::Thin)
+                .set_background_color(Color::RGB(HEADER_FILL))
+                .set_align(FormatAlign::Center),
+            exceedance: cell
+                .clone()
+                .set_background_color(Color::RGB(EXCEEDANCE_FILL)),
+            cell,
+        }
+    }
+}
+
+/// Write classification results to an XLSX workbook with a summary sheet,
+/// a per-substance details sheet and a sheet of HP criteria.
+pub fn write(
+    results: &[&ClassificationResult],
+    path: &Path,
+    locale: Locale,
+) -> Result<(), SondaError> {
+    build(results, locale)
+        .and_then(|mut workbook| workbook.save(path))
+        .map_err(|e| SondaError::Io(std::io::Error::other(format!("failed to write xlsx: {e}"))))
+}
+
+fn build(results: &[&ClassificationResult], locale: Locale) -> Result<Workbook, XlsxError> {
+    let formats = Formats::new();
+    let mut workbook = Workbook::new();
+
+    write_summary(workbook.add_worksheet(), results, &formats, locale)?;
+    write_details(workbook.add_worksheet(), results, &formats, locale)?;
+    write_hp(workbook.add_worksheet(), results, &formats, locale)?;
+
+    Ok(workbook)
+}
+
+/// One row per sample and ruleset.
+fn write_summary(
+    sheet: &mut Worksheet,
+    results: &[&ClassificationResult],
+    f: &Formats,
+    locale: Locale,
+) -> Result<(), XlsxError> {
+    sheet.set_name(locale.text("cli.xlsx.summary"))?;
+    let headers = [
+        ("cli.sample", 18.0),
+        ("cli.ruleset", 36.0),
+        ("cli.category", 14.0),
+        ("cli.determining_substances", 40.0),
+        ("cli.reason", 60.0),
+    ];
+    write_headers(sheet, "cli.xlsx.summary_title", &headers, f, locale)?;
+
+    let mut row = HEADER_ROW + 1;
+    for sample in results.iter().flat_map(|r| &r.samples) {
+        for rs in &sample.ruleset_results {
+            let exceeds = rs
+                .lowest_category
+                .as_deref()
+                .is_some_and(|lowest| rs.overall_category != lowest)
+                || rs.hp_details.as_ref().is_some_and(|hp| hp.is_hazardous);
+            let fmt = if exceeds { &f.exceedance } else { &f.cell };
+
+            sheet.write_string_with_format(row, 0, &sample.sample_id, &f.cell)?;
+            sheet.write_string_with_format(row, 1, &rs.ruleset_name, &f.cell)?;
+            sheet.write_string_with_format(row, 2, &rs.overall_category, fmt)?;
+            sheet.write_string_with_format(
+                row,
+                3,
+                rs.determining_substances.join(", "),
+                &f.cell,
+            )?;
+            sheet.write_string_with_format(row, 4, &rs.overall_reason, &f.cell)?;
+            row += 1;
+        }
+    }
+
+    finish_table(sheet, row, headers.len())
+}
+
+/// One row per `SubstanceResult`; exceedances are highlighted.
+fn write_details(
+    sheet: &mut Worksheet,
+    results: &[&ClassificationResult],
+    f: &Formats,
+    locale: Locale,
+) -> Result<(), XlsxError> {
+    sheet.set_name(locale.text("cli.xlsx.details"))?;
+    let headers = [
+        ("cli.sample", 18.0),
+        ("cli.ruleset", 36.0),
+        ("cli.substance", 28.0),
+        ("cli.value", 12.0),
+        ("cli.unit", 12.0),
+        ("cli.category", 14.0),
+        ("cli.threshold", 12.0),
+        ("cli.uncertain", 10.0),
+        ("cli.reason", 60.0),
+    ];
+    write_headers(sheet, "cli.xlsx.details_title", &headers, f, locale)?;
+
+    let mut row = HEADER_ROW + 1;
+    for sample in results.iter().flat_map(|r| &r.samples) {
+        for rs in &sample.ruleset_results {
+            for sr in &rs.substance_results {
+                let fmt = if sr.exceeded_threshold.is_some() {
+                    &f.exceedance
+                } else {
+                    &f.cell
+                };
+
+                sheet.write_string_with_format(row, 0, &sample.sample_id, &f.cell)?;
+                sheet.write_string_with_format(row, 1, &rs.ruleset_name, &f.cell)?;
+                sheet.write_string_with_format(row, 2, &sr.raw_name, &f.cell)?;
+                write_value(sheet, row, 3, sr, fmt)?;
+                sheet.write_string_with_format(row, 4, &sr.unit, &f.cell)?;
+                sheet.write_string_with_format(row, 5, &sr.category, fmt)?;
+                match sr.exceeded_threshold {
+                    Some(t) => write_decimal(sheet, row, 6, &t.to_string(), fmt)?,
+                    None => {
+                        sheet.write_blank(row, 6, &f.cell)?;
+                    }
+                }
+                let uncertain = if sr.uncertain {
+                    locale.text("cli.yes")
+                } else {
+                    ""
+                };
+                sheet.write_string_with_format(row, 7, uncertain, &f.cell)?;
+                sheet.write_string_with_format(row, 8, &sr.reason, &f.cell)?;
+                row += 1;
+            }
+        }
+    }
+
+    finish_table(sheet, row, headers.len())
+}
+
+/// One row per evaluated HP criterion; triggered criteria are highlighted.
+fn write_hp(
+    sheet: &mut Worksheet,
+    results: &[&ClassificationResult],
+    f: &Formats,
+    locale: Locale,
+) -> Result<(), XlsxError> {
+    sheet.set_name(locale.text("cli.xlsx.hp"))?;
+    let headers = [
+        ("cli.sample", 18.0),
+        ("cli.criterion", 10.0),
+        ("cli.name", 28.0),
+        ("cli.hp.triggered", 10.0),
+        ("cli.contributions", 60.0),
+        ("cli.reason", 60.0),
+    ];
+    write_headers(sheet, "cli.hp.title", &headers, f, locale)?;
+
+    let mut row = HEADER_ROW + 1;
+    for sample in results.iter().flat_map(|r| &r.samples) {
+        let hp_results = sample
+            .ruleset_results
+            .iter()
+            .filter_map(|rs| rs.hp_details.as_ref());
+        for hp in hp_results {
+            for c in &hp.criteria_results {
+                let fmt = if c.triggered { &f.exceedance } else { &f.cell };
+                let contributions: Vec<String> = c
+                    .contributions
+                    .iter()
+                    .map(|c| {
+                        locale.format(
+                            "cli.hp.contribution",
+                            &[
+                                ("substance", &c.substance),
+                                ("compound", &c.compound),
+                                ("h_code", &c.h_code),
+                                ("concentration", &c.concentration_pct),
+                            ],
+                        )
+                    })
+                    .collect();
+
+                sheet.write_string_with_format(row, 0, &sample.sample_id, &f.cell)?;
+                sheet.write_string_with_format(row, 1, &c.hp_id, fmt)?;
+                sheet.write_string_with_format(row, 2, &c.hp_name, &f.cell)?;
+                let triggered = locale.text(if c.triggered { "cli.yes" } else { "cli.no" });
+                sheet.write_string_with_format(row, 3, triggered, fmt)?;
+                sheet.write_string_with_format(row, 4, contributions.join("; "), &f.cell)?;
+                sheet.write_string_with_format(row, 5, &c.reason, &f.cell)?;
+                row += 1;
+            }
+        }
+    }
+
+    finish_table(sheet, row, headers.len())
+}
+
+/// Write the sheet title and column headers. `subtitle` and header names are message keys.
+fn write_headers(
+    sheet: &mut Worksheet,
+    subtitle: &str,
+    headers: &[(&str, f64)],
+    f: &Formats,
+    locale: Locale,
+) -> Result<(), XlsxError> {
+    let title = format!("{TITLE} – {}", locale.text(subtitle));
+    sheet.write_string_with_format(0, 0, title, &f.title)?;
+    for (col, (key, width)) in headers.iter().enumerate() {
+        let col = col as u16;
+        sheet.write_string_with_format(HEADER_ROW, col, locale.text(key), &f.header)?;
+        sheet.set_column_width(col, *width)?;
+    }
+    sheet.set_freeze_panes(HEADER_ROW + 1, 0)?;
+    Ok(())
+}
+
+fn finish_table(sheet: &mut Worksheet, end_row: u32, columns: usize) -> Result<(), XlsxError> {
+    if end_row > HEADER_ROW + 1 {
+        sheet.autofilter(HEADER_ROW, 0, end_row - 1, columns as u16 - 1)?;
+    }
+    Ok(())
+}
+
+/// Measured values are written as numbers; values below the detection limit
+/// keep the lab notation ("< 0.5") as text.
+fn write_value(
+    sheet: &mut Worksheet,
+    row: u32,
+    col: u16,
+    sr: &SubstanceResult,
+    fmt: &Format,
+) -> Result<(), XlsxError> {
+    match sr.value {
+        AnalysisValue::Measured(v) => write_decimal(sheet, row, col, &v.to_string(), fmt),
+        AnalysisValue::BelowDetection(_) => {
+            sheet.write_string_with_format(row, col, sr.value.to_string(), fmt)?;
+            Ok(())
+        }
+    }
+}
+
+fn write_decimal(
+    sheet: &mut Worksheet,
+    row: u32,
+    col: u16,
+    value: &str,
+    fmt: &Format,
+) -> Result<(), XlsxError> {
+    match value.parse::<f64>() {
+        Ok(n) => sheet.write_number_with_format(row, col, n, fmt)?,
+        Err(_) => sheet.write_string_with_format(row, col, value, fmt)?,
+    };
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sonda_core::model::AnalysisReport;
+    use sonda_core::rules::builtin;
+    use sonda_core::ClassifyOptions;
+
+    #[test]
+    fn writes_summary_details_and_hp_sheets() {
+        let reports: Vec<AnalysisReport> = serde_json::from_str(
+            r#"[{
+                "header": { "lab": "Eurofins", "sample_id": "P001", "matrix": "jord" },
+                "rows": [
+                    { "raw_name": "Bly (Pb)", "normalized_name": "bly",
+                      "value": { "Measured": "500" }, "unit": "mg/kg TS" },
+                    { "raw_name": "Arsenik (As)", "normalized_name": "arsenik",
+                      "value": { "BelowDetection": "0.5" }, "unit": "mg/kg TS" }
+                ]
+            }]"#,
+        )
+        .unwrap();
+        let rulesets = vec![builtin::load_preset("nv").unwrap()];
+        let options = ClassifyOptions {
+            include_hp: true,
+            ..Default::default()
+        };
+        let result = sonda_core::classify_reports(&reports, &rulesets, &options).unwrap();
+
+        let mut workbook = build(&[&result], Locale::En).unwrap();
+        let names: Vec<_> = workbook.worksheets().iter().map(|s| s.name()).collect();
+        assert_eq!(names, vec!["Summary", "Details", "HP criteria"]);
+        assert!(workbook.save_to_buffer().unwrap().starts_with(b"PK"));
+
+        let mut workbook = build(&[&result], Locale::Sv).unwrap();
+        let names: Vec<_> = workbook.worksheets().iter().map(|s| s.name()).collect();
+        assert_eq!(names, vec!["Sammanställning", "Detaljer", "HP-kriterier"]);
+    }
+}
@@ -0,0 +1,53 @@
+//! Interactive terminal review of parsed reports.
+//!
+//! [`state`] holds the reports being corrected and reacts to key presses;
+//! [`ui`] draws it. This module runs the event loop and writes the result.
+
+pub mod state;
+mod ui;
+
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use ratatui::DefaultTerminal;
+use sonda_core::error::SondaError;
+use std::path::Path;
+
+use state::{Action, Review};
+
+/// Run the review until the user quits. Saving writes the corrected reports
+/// to `out` as a top-level array of `AnalysisReport`.
+pub fn run(review: &mut Review, file: &str, out: &Path) -> Result<(), SondaError> {
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, review, file, out);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(
+    terminal: &mut DefaultTerminal,
+    review: &mut Review,
+    file: &str,
+    out: &Path,
+) -> Result<(), SondaError> {
+    loop {
+        terminal.draw(|frame| ui::draw(frame, review, file))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match review.handle_key(key) {
+            Action::None => {}
+            Action::Save => save(review, out)?,
+            Action::SaveAndQuit => return save(review, out),
+            Action::Quit => return Ok(()),
+        }
+    }
+}
+
+fn save(review: &mut Review, out: &Path) -> Result<(), SondaError> {
+    let json = serde_json::to_string_pretty(&review.reports())?;
+    std::fs::write(out, json)?;
+    review.saved(&out.display().to_string());
+    Ok(())
+}
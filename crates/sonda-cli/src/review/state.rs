@@ -0,0 +1,617 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use sonda_core::i18n::Locale;
+use sonda_core::model::{AnalysisReport, AnalysisRow, Date, Matrix, ReportHeader, Unit};
+use sonda_core::parsing::normalize::normalize_substance;
+use sonda_core::parsing::values::parse_value;
+use sonda_core::parsing::{ParsedReports, SkippedLine};
+use std::collections::BTreeSet;
+
+/// Review state of a row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowStatus {
+    /// As parsed, not yet looked at.
+    Parsed,
+    Accepted,
+    Corrected,
+    /// Entered by hand or recovered from a skipped line.
+    Added,
+}
+
+impl RowStatus {
+    pub fn key(self) -> &'static str {
+        match self {
+            RowStatus::Parsed => "cli.review.status.parsed",
+            RowStatus::Accepted => "cli.review.status.accepted",
+            RowStatus::Corrected => "cli.review.status.corrected",
+            RowStatus::Added => "cli.review.status.added",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReviewRow {
+    pub row: AnalysisRow,
+    pub status: RowStatus,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReviewSample {
+    pub header: ReportHeader,
+    pub rows: Vec<ReviewRow>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReviewSkipped {
+    pub line: SkippedLine,
+    /// Set once the line has been turned into a row.
+    pub added: bool,
+}
+
+/// Which list the cursor keys move in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Focus {
+    Rows,
+    Skipped,
+}
+
+/// What a submitted form writes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormTarget {
+    /// Correct the row at this index of the current sample.
+    Row(usize),
+    /// Add a row, optionally recovered from the skipped line at this index.
+    NewRow(Option<usize>),
+    Header,
+}
+
+#[derive(Debug, Clone)]
+pub struct Field {
+    /// Locale key of the label.
+    pub label: &'static str,
+    pub value: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Form {
+    pub target: FormTarget,
+    pub fields: Vec<Field>,
+    pub active: usize,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Mode {
+    Browse,
+    Edit(Form),
+    /// Quit requested with unsaved changes.
+    ConfirmQuit,
+}
+
+/// What the event loop should do after a key press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    None,
+    Save,
+    SaveAndQuit,
+    Quit,
+}
+
+/// Interactive review of parsed reports: the data being corrected plus
+/// cursor and editing state. Rendering and file I/O live elsewhere.
+pub struct Review {
+    pub samples: Vec<ReviewSample>,
+    pub skipped: Vec<ReviewSkipped>,
+    /// Normalized names recognised by at least one loaded ruleset.
+    pub known: BTreeSet<String>,
+    pub sample: usize,
+    pub focus: Focus,
+    pub row_cursor: usize,
+    pub skipped_cursor: usize,
+    pub mode: Mode,
+    pub dirty: bool,
+    pub message: Option<String>,
+    pub locale: Locale,
+}
+
+impl Review {
+    pub fn new(parsed: ParsedReports, known: BTreeSet<String>, locale: Locale) -> Review {
+        let samples = parsed
+            .reports
+            .into_iter()
+            .map(|report| ReviewSample {
+                header: report.header,
+                rows: report
+                    .rows
+                    .into_iter()
+                    .map(|row| ReviewRow {
+                        row,
+                        status: RowStatus::Parsed,
+                    })
+                    .collect(),
+            })
+            .collect();
+        let skipped = parsed
+            .skipped_lines
+            .into_iter()
+            .map(|line| ReviewSkipped { line, added: false })
+            .collect();
+        Review {
+            samples,
+            skipped,
+            known,
+            sample: 0,
+            focus: Focus::Rows,
+            row_cursor: 0,
+            skipped_cursor: 0,
+            mode: Mode::Browse,
+            dirty: false,
+            message: None,
+            locale,
+        }
+    }
+
+    pub fn current(&self) -> &ReviewSample {
+        &self.samples[self.sample]
+    }
+
+    /// Whether no loaded ruleset has a rule for this normalized name.
+    pub fn is_unknown(&self, normalized_name: &str) -> bool {
+        !self.known.contains(normalized_name)
+    }
+
+    /// Indices into `skipped` of the lines shown for the current sample.
+    /// Lines without a sample are shown with every sample.
+    pub fn visible_skipped(&self) -> Vec<usize> {
+        self.skipped
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.line.report_index.is_none_or(|i| i == self.sample))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// The corrected reports, in the shape `sonda classify` consumes.
+    pub fn reports(&self) -> Vec<AnalysisReport> {
+        self.samples
+            .iter()
+            .map(|s| AnalysisReport {
+                header: s.header.clone(),
+                rows: s.rows.iter().map(|r| r.row.clone()).collect(),
+            })
+            .collect()
+    }
+
+    /// Record a successful save.
+    pub fn saved(&mut self, path: &str) {
+        self.dirty = false;
+        self.message = Some(self.locale.format(
+            "cli.review.saved",
+            &[("count", &self.samples.len()), ("path", &path)],
+        ));
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Action {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            // Like `q`; a second Ctrl-C at the prompt quits without saving.
+            if self.dirty && !matches!(self.mode, Mode::ConfirmQuit) {
+                self.mode = Mode::ConfirmQuit;
+                return Action::None;
+            }
+            return Action::Quit;
+        }
+        match std::mem::replace(&mut self.mode, Mode::Browse) {
+            Mode::Browse => self.browse_key(key),
+            Mode::Edit(form) => {
+                self.form_key(form, key);
+                Action::None
+            }
+            Mode::ConfirmQuit => match key.code {
+                KeyCode::Char('s') => Action::SaveAndQuit,
+                KeyCode::Char('q') => Action::Quit,
+                _ => Action::None,
+            },
+        }
+    }
+
+    fn browse_key(&mut self, key: KeyEvent) -> Action {
+        self.message = None;
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc if self.dirty => self.mode = Mode::ConfirmQuit,
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Char('s') => return Action::Save,
+            KeyCode::Tab => {
+                self.focus = match self.focus {
+                    Focus::Rows => Focus::Skipped,
+                    Focus::Skipped => Focus::Rows,
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.move_cursor(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_cursor(1),
+            KeyCode::Left | KeyCode::Char('[') => self.select_sample(self.sample.saturating_sub(1)),
+            KeyCode::Right | KeyCode::Char(']') => self.select_sample(self.sample + 1),
+            KeyCode::Char('a') => match self.focus {
+                Focus::Rows => self.accept(Some(self.row_cursor)),
+                Focus::Skipped => self.open_skipped_form(),
+            },
+            KeyCode::Char('A') => self.accept(None),
+            KeyCode::Char('e') | KeyCode::Enter => match self.focus {
+                Focus::Rows => self.open_row_form(),
+                Focus::Skipped => self.open_skipped_form(),
+            },
+            KeyCode::Char('n') => self.open_form(FormTarget::NewRow(None), "", "", ""),
+            KeyCode::Char('d') | KeyCode::Delete if self.focus == Focus::Rows => self.delete_row(),
+            KeyCode::Char('h') => self.open_header_form(),
+            _ => {}
+        }
+        Action::None
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        let skipped = self.visible_skipped().len();
+        let (cursor, len) = match self.focus {
+            Focus::Rows => (&mut self.row_cursor, self.samples[self.sample].rows.len()),
+            Focus::Skipped => (&mut self.skipped_cursor, skipped),
+        };
+        *cursor = cursor
+            .saturating_add_signed(delta)
+            .min(len.saturating_sub(1));
+    }
+
+    fn select_sample(&mut self, sample: usize) {
+        if sample < self.samples.len() && sample != self.sample {
+            self.sample = sample;
+            self.row_cursor = 0;
+            self.skipped_cursor = 0;
+        }
+    }
+
+    /// Accept one row, or every parsed row of the sample.
+    fn accept(&mut self, row: Option<usize>) {
+        let rows = &mut self.samples[self.sample].rows;
+        for (idx, r) in rows.iter_mut().enumerate() {
+            if row.is_none_or(|i| i == idx) && r.status == RowStatus::Parsed {
+                r.status = RowStatus::Accepted;
+                self.dirty = true;
+            }
+        }
+        if row.is_some() {
+            self.move_cursor(1);
+        }
+    }
+
+    fn delete_row(&mut self) {
+        let rows = &mut self.samples[self.sample].rows;
+        if self.row_cursor < rows.len() {
+            rows.remove(self.row_cursor);
+            self.dirty = true;
+            self.move_cursor(0);
+        }
+    }
+
+    fn open_row_form(&mut self) {
+        let Some(r) = self.current().rows.get(self.row_cursor) else {
+            return;
+        };
+        let (name, value, unit) = (
+            r.row.raw_name.clone(),
+            r.row.value.to_string(),
+            r.row.unit.to_string(),
+        );
+        self.open_form(FormTarget::Row(self.row_cursor), &name, &value, &unit);
+    }
+
+    /// Start a new row from the selected skipped line, with its first
+    /// column as the substance name.
+    fn open_skipped_form(&mut self) {
+        let Some(&idx) = self.visible_skipped().get(self.skipped_cursor) else {
+            return;
+        };
+        let text = self.skipped[idx].line.line_text.clone();
+        let name = text.split("  ").next().unwrap_or_default().trim();
+        self.open_form(FormTarget::NewRow(Some(idx)), name, "", "mg/kg TS");
+    }
+
+    fn open_form(&mut self, target: FormTarget, name: &str, value: &str, unit: &str) {
+        self.mode = Mode::Edit(Form {
+            target,
+            fields: vec![
+                field("cli.substance", name),
+                field("cli.value", value),
+                field("cli.unit", unit),
+            ],
+            active: if name.is_empty() { 0 } else { 1 },
+            error: None,
+        });
+    }
+
+    fn open_header_form(&mut self) {
+        let header = &self.current().header;
+        let matrix = header.matrix.map(|m| m.to_string()).unwrap_or_default();
+        let date = header.date.map(|d| d.to_string()).unwrap_or_default();
+        self.mode = Mode::Edit(Form {
+            target: FormTarget::Header,
+            fields: vec![
+                field(
+                    "cli.sample_id",
+                    header.sample_id.as_deref().unwrap_or_default(),
+                ),
+                field("cli.matrix", &matrix),
+                field("cli.date", &date),
+            ],
+            active: 0,
+            error: None,
+        });
+    }
+
+    fn form_key(&mut self, mut form: Form, key: KeyEvent) {
+        let last = form.fields.len() - 1;
+        match key.code {
+            KeyCode::Esc => return,
+            KeyCode::Enter => match self.submit(&form) {
+                Ok(()) => return,
+                Err(e) => form.error = Some(e),
+            },
+            KeyCode::Tab | KeyCode::Down => form.active = (form.active + 1).min(last),
+            KeyCode::BackTab | KeyCode::Up => form.active = form.active.saturating_sub(1),
+            KeyCode::Backspace => {
+                form.fields[form.active].value.pop();
+            }
+            KeyCode::Char(c) => form.fields[form.active].value.push(c),
+            _ => {}
+        }
+        self.mode = Mode::Edit(form);
+    }
+
+    fn submit(&mut self, form: &Form) -> Result<(), String> {
+        let value = |i: usize| form.fields[i].value.trim();
+        match form.target {
+            FormTarget::Header => {
+                let matrix = match value(1) {
+                    "" => None,
+                    m => Some(Matrix::from_str_loose(m).ok_or_else(|| {
+                        self.locale
+                            .format("cli.review.error.matrix", &[("value", &m)])
+                    })?),
+                };
+                let date = match value(2) {
+                    "" => None,
+                    d => Some(Date::from_str_loose(d).ok_or_else(|| {
+                        self.locale
+                            .format("cli.review.error.date", &[("value", &d)])
+                    })?),
+                };
+                let header = &mut self.samples[self.sample].header;
+                header.sample_id = non_empty(value(0));
+                header.matrix = matrix;
+                header.date = date;
+            }
+            FormTarget::Row(idx) => {
+                let row = self.parse_row(value(0), value(1), value(2))?;
+                let r = &mut self.samples[self.sample].rows[idx];
+                let changed = row.raw_name != r.row.raw_name
+                    || row.value != r.row.value
+                    || row.unit != r.row.unit;
+                if changed {
+                    if row.value != r.row.value {
+                        // The lab's uncertainty belonged to the original value.
+                        r.row.uncertainty = None;
+                    }
+                    r.row = AnalysisRow {
+                        uncertainty: r.row.uncertainty,
+                        loq: None,
+                        source_line: r.row.source_line.take(),
+                        ..row
+                    };
+                    if r.status != RowStatus::Added {
+                        r.status = RowStatus::Corrected;
+                    }
+                } else if r.status == RowStatus::Parsed {
+                    r.status = RowStatus::Accepted;
+                }
+            }
+            FormTarget::NewRow(skipped) => {
+                let mut row = self.parse_row(value(0), value(1), value(2))?;
+                if let Some(idx) = skipped {
+                    row.source_line = Some(self.skipped[idx].line.line_text.clone());
+                    self.skipped[idx].added = true;
+                }
+                let rows = &mut self.samples[self.sample].rows;
+                rows.push(ReviewRow {
+                    row,
+                    status: RowStatus::Added,
+                });
+                self.row_cursor = rows.len() - 1;
+            }
+        }
+        self.dirty = true;
+        Ok(())
+    }
+
+    fn parse_row(&self, name: &str, value: &str, unit: &str) -> Result<AnalysisRow, String> {
+        if name.is_empty() {
+            return Err(self
+                .locale
+                .text("cli.review.error.name_required")
+                .to_string());
+        }
+        let invalid = || {
+            self.locale
+                .format("cli.review.error.value", &[("value", &value)])
+        };
+        let value = parse_value(value)
+            .map_err(|_| invalid())?
+            .ok_or_else(invalid)?;
+        Ok(AnalysisRow {
+            raw_name: name.to_string(),
+            normalized_name: normalize_substance(name),
+            value,
+            unit: Unit::from_str_loose(unit),
+            uncertainty: None,
+            loq: None,
+            source_line: None,
+        })
+    }
+}
+
+fn field(label: &'static str, value: &str) -> Field {
+    Field {
+        label,
+        value: value.to_string(),
+    }
+}
+
+fn non_empty(s: &str) -> Option<String> {
+    (!s.is_empty()).then(|| s.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sonda_core::model::AnalysisValue;
+
+    fn value(s: &str) -> AnalysisValue {
+        parse_value(s).unwrap().unwrap()
+    }
+
+    fn review() -> Review {
+        let row = |name: &str, v: &str| AnalysisRow {
+            raw_name: name.into(),
+            normalized_name: normalize_substance(name),
+            value: value(v),
+            unit: Unit::MgPerKgTs,
+            uncertainty: Some(value("5").numeric()),
+            loq: None,
+            source_line: Some(format!("{name}    {v}    mg/kg TS")),
+        };
+        let parsed = ParsedReports {
+            reports: vec![
+                AnalysisReport {
+                    header: ReportHeader {
+                        sample_id: Some("P1".into()),
+                        ..Default::default()
+                    },
+                    rows: vec![row("Bly (Pb)", "120"), row("Okänt ämne", "3")],
+                },
+                AnalysisReport {
+                    header: ReportHeader::default(),
+                    rows: vec![row("Zink (Zn)", "40")],
+                },
+            ],
+            warnings: vec![],
+            skipped_lines: vec![
+                SkippedLine {
+                    line_text: "Kadmium (Cd)    0,2 0    mg/kg TS".into(),
+                    reason: "invalid number".into(),
+                    report_index: Some(0),
+                },
+                SkippedLine {
+                    line_text: "Nickel (Ni)    n.a.".into(),
+                    reason: "no parseable numeric value found".into(),
+                    report_index: Some(1),
+                },
+            ],
+        };
+        let known = ["bly", "zink", "kadmium"].map(String::from).into();
+        Review::new(parsed, known, Locale::En)
+    }
+
+    fn press(review: &mut Review, keys: &str) -> Action {
+        keys.chars()
+            .map(|c| {
+                let code = match c {
+                    '\t' => KeyCode::Tab,
+                    '\n' => KeyCode::Enter,
+                    '\u{8}' => KeyCode::Backspace,
+                    '\u{1b}' => KeyCode::Esc,
+                    c => KeyCode::Char(c),
+                };
+                review.handle_key(KeyEvent::from(code))
+            })
+            .last()
+            .unwrap_or(Action::None)
+    }
+
+    #[test]
+    fn accepts_and_corrects_rows() {
+        let mut r = review();
+        assert!(r.is_unknown(&r.current().rows[1].row.normalized_name));
+
+        press(&mut r, "a");
+        assert_eq!(r.current().rows[0].status, RowStatus::Accepted);
+        assert_eq!(r.row_cursor, 1);
+
+        // Correct the value of the second row: clear "3" and type "< 0,5".
+        press(&mut r, "e\u{8}< 0,5\n");
+        let row = &r.current().rows[1];
+        assert_eq!(row.status, RowStatus::Corrected);
+        assert_eq!(row.row.value, value("< 0.5"));
+        assert_eq!(row.row.uncertainty, None);
+        assert!(row.row.source_line.is_some());
+        assert!(r.dirty);
+    }
+
+    #[test]
+    fn invalid_value_keeps_form_open() {
+        let mut r = review();
+        press(&mut r, "e\u{8}\u{8}\u{8}abc\n");
+        match &r.mode {
+            Mode::Edit(form) => assert!(form.error.is_some()),
+            other => panic!("expected form, got {other:?}"),
+        }
+        press(&mut r, "\u{1b}");
+        assert_eq!(r.current().rows[0].row.value, value("120"));
+        assert!(!r.dirty);
+    }
+
+    #[test]
+    fn recovers_skipped_line_as_row() {
+        let mut r = review();
+        assert_eq!(r.visible_skipped(), vec![0]);
+        press(&mut r, "\ta0.2\n");
+        let row = r.current().rows.last().unwrap();
+        assert_eq!(row.status, RowStatus::Added);
+        assert_eq!(row.row.normalized_name, "kadmium");
+        assert_eq!(row.row.value, value("0.2"));
+        assert_eq!(
+            row.row.source_line.as_deref(),
+            Some("Kadmium (Cd)    0,2 0    mg/kg TS")
+        );
+        assert!(r.skipped[0].added);
+
+        // The second sample shows its own skipped line.
+        press(&mut r, "]");
+        assert_eq!(r.visible_skipped(), vec![1]);
+    }
+
+    #[test]
+    fn edits_header_and_saves_reports() {
+        let mut r = review();
+        press(&mut r, "]h\tasfalt\t2024-05-01\n");
+        let header = &r.samples[1].header;
+        assert_eq!(header.matrix, Some(Matrix::Asfalt));
+        assert_eq!(header.date, Date::new(2024, 5, 1));
+        assert_eq!(header.sample_id, None);
+
+        press(&mut r, "h\t\u{8}\u{8}\u{8}\u{8}\u{8}\u{8}lera\n");
+        assert!(matches!(r.mode, Mode::Edit(_)));
+        press(&mut r, "\u{1b}");
+
+        press(&mut r, "[d");
+        assert_eq!(r.reports()[0].rows.len(), 1);
+        assert_eq!(press(&mut r, "q"), Action::None);
+        assert!(matches!(r.mode, Mode::ConfirmQuit));
+        assert_eq!(press(&mut r, "s"), Action::SaveAndQuit);
+    }
+
+    #[test]
+    fn ctrl_c_asks_before_discarding_changes() {
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        let mut r = review();
+        assert_eq!(r.handle_key(ctrl_c), Action::Quit);
+
+        press(&mut r, "a");
+        assert_eq!(r.handle_key(ctrl_c), Action::None);
+        assert!(matches!(r.mode, Mode::ConfirmQuit));
+        assert_eq!(r.handle_key(ctrl_c), Action::Quit);
+    }
+}
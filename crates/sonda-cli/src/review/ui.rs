@@ -0,0 +1,331 @@
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, Borders, Cell, Clear, List, ListItem, ListState, Paragraph, Row, Table, TableState,
+};
+use ratatui::Frame;
+
+use super::state::{Focus, Form, FormTarget, Mode, Review, RowStatus};
+
+pub fn draw(frame: &mut Frame, review: &Review, file: &str) {
+    let [header, rows, skipped, footer] = Layout::vertical([
+        Constraint::Length(5),
+        Constraint::Min(6),
+        Constraint::Length(8),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    draw_header(frame, review, file, header);
+    draw_rows(frame, review, rows);
+    draw_skipped(frame, review, skipped);
+    draw_footer(frame, review, footer);
+
+    match &review.mode {
+        Mode::Edit(form) => draw_form(frame, review, form),
+        Mode::ConfirmQuit => draw_confirm(frame, review),
+        Mode::Browse => {}
+    }
+}
+
+fn draw_header(frame: &mut Frame, review: &Review, file: &str, area: Rect) {
+    let locale = review.locale;
+    let sample = review.current();
+    let h = &sample.header;
+    let dash = || "-".to_string();
+    let unknown = sample
+        .rows
+        .iter()
+        .filter(|r| review.is_unknown(&r.row.normalized_name))
+        .count();
+
+    let title = locale.format(
+        "cli.review.title",
+        &[
+            ("file", &file),
+            ("index", &(review.sample + 1)),
+            ("count", &review.samples.len()),
+        ],
+    );
+    let mut lines = vec![
+        Line::from(vec![
+            label(locale.text("cli.sample_id")),
+            Span::raw(h.sample_id.clone().unwrap_or_else(dash)),
+            Span::raw("   "),
+            label(locale.text("cli.report_id")),
+            Span::raw(h.lab_report_id.clone().unwrap_or_else(dash)),
+            Span::raw("   "),
+            label(locale.text("cli.lab")),
+            Span::raw(h.lab.clone().unwrap_or_else(dash)),
+        ]),
+        Line::from(vec![
+            label(locale.text("cli.matrix")),
+            Span::raw(h.matrix.map(|m| m.to_string()).unwrap_or_else(dash)),
+            Span::raw("   "),
+            label(locale.text("cli.date")),
+            Span::raw(h.date.map(|d| d.to_string()).unwrap_or_else(dash)),
+        ]),
+    ];
+    if unknown > 0 {
+        lines.push(Line::from(
+            locale
+                .format("cli.review.unknown_count", &[("count", &unknown)])
+                .fg(Color::Yellow),
+        ));
+    }
+    let block = Block::default().borders(Borders::ALL).title(title.bold());
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn draw_rows(frame: &mut Frame, review: &Review, area: Rect) {
+    let locale = review.locale;
+    let sample = review.current();
+    let header = Row::new([
+        locale.text("cli.review.status"),
+        locale.text("cli.raw_name"),
+        locale.text("cli.normalized"),
+        locale.text("cli.value"),
+        locale.text("cli.unit"),
+        locale.text("cli.review.source_line"),
+    ])
+    .style(Style::new().add_modifier(Modifier::BOLD));
+
+    let rows = sample.rows.iter().map(|r| {
+        let unknown = review.is_unknown(&r.row.normalized_name);
+        let status_style = match r.status {
+            RowStatus::Parsed => Style::new(),
+            RowStatus::Accepted => Style::new().fg(Color::Green),
+            RowStatus::Corrected | RowStatus::Added => Style::new().fg(Color::Cyan),
+        };
+        let normalized = if unknown {
+            Cell::from(format!("{} ?", r.row.normalized_name)).style(Style::new().fg(Color::Yellow))
+        } else {
+            Cell::from(r.row.normalized_name.as_str())
+        };
+        Row::new([
+            Cell::from(locale.text(r.status.key())).style(status_style),
+            Cell::from(r.row.raw_name.as_str()),
+            normalized,
+            Cell::from(r.row.value.to_string()),
+            Cell::from(r.row.unit.to_string()),
+            Cell::from(r.row.source_line.as_deref().unwrap_or_default()).dim(),
+        ])
+    });
+
+    let title = locale.format("cli.review.rows", &[("count", &sample.rows.len())]);
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Percentage(22),
+            Constraint::Percentage(18),
+            Constraint::Length(12),
+            Constraint::Length(9),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(header)
+    .block(focus_block(title, review.focus == Focus::Rows))
+    .row_highlight_style(highlight(review.focus == Focus::Rows));
+
+    let mut state = TableState::default().with_selected(Some(review.row_cursor));
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+fn draw_skipped(frame: &mut Frame, review: &Review, area: Rect) {
+    let locale = review.locale;
+    let visible = review.visible_skipped();
+    let items: Vec<ListItem> = visible
+        .iter()
+        .map(|&idx| {
+            let s = &review.skipped[idx];
+            let mut spans = vec![
+                Span::raw(s.line.line_text.as_str()),
+                Span::raw("  "),
+                Span::raw(s.line.reason.as_str()).fg(Color::Red),
+            ];
+            if s.added {
+                spans.insert(
+                    0,
+                    format!("[{}] ", locale.text("cli.review.status.added")).green(),
+                );
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let title = locale.format("cli.skipped_lines", &[("count", &visible.len())]);
+    let list = List::new(items)
+        .block(focus_block(title, review.focus == Focus::Skipped))
+        .highlight_style(highlight(review.focus == Focus::Skipped));
+    let mut state = ListState::default().with_selected(Some(review.skipped_cursor));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_footer(frame: &mut Frame, review: &Review, area: Rect) {
+    let line = match &review.message {
+        Some(message) => Line::from(message.as_str().green()),
+        None => {
+            let mut line = Line::from(review.locale.text("cli.review.help.browse").dim());
+            if review.dirty {
+                line.spans.insert(
+                    0,
+                    format!("{}  ", review.locale.text("cli.review.modified")).yellow(),
+                );
+            }
+            line
+        }
+    };
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+fn draw_form(frame: &mut Frame, review: &Review, form: &Form) {
+    let locale = review.locale;
+    let title_key = match form.target {
+        FormTarget::Row(_) => "cli.review.edit_row",
+        FormTarget::NewRow(_) => "cli.review.new_row",
+        FormTarget::Header => "cli.review.edit_header",
+    };
+    let width = form
+        .fields
+        .iter()
+        .map(|f| locale.text(f.label).chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut lines: Vec<Line> = form
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(idx, f)| {
+            let name = format!("{:width$}  ", locale.text(f.label));
+            if idx == form.active {
+                Line::from(vec![
+                    name.bold(),
+                    Span::raw(format!("{}_", f.value)).reversed(),
+                ])
+            } else {
+                Line::from(vec![Span::raw(name), Span::raw(f.value.as_str())])
+            }
+        })
+        .collect();
+    lines.push(Line::default());
+    match &form.error {
+        Some(error) => lines.push(Line::from(error.as_str().red())),
+        None => lines.push(Line::from(locale.text("cli.review.help.form").dim())),
+    }
+
+    let area = popup(frame.area(), 70, lines.len() as u16 + 2);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(locale.text(title_key).bold());
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn draw_confirm(frame: &mut Frame, review: &Review) {
+    let area = popup(frame.area(), 70, 3);
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(review.locale.text("cli.review.unsaved"))
+            .block(Block::default().borders(Borders::ALL)),
+        area,
+    );
+}
+
+fn label(text: &str) -> Span<'_> {
+    Span::raw(format!("{text}: ")).bold()
+}
+
+fn focus_block(title: String, focused: bool) -> Block<'static> {
+    let block = Block::default().borders(Borders::ALL).title(title);
+    if focused {
+        block.border_style(Style::new().fg(Color::Cyan))
+    } else {
+        block
+    }
+}
+
+fn highlight(focused: bool) -> Style {
+    if focused {
+        Style::new().add_modifier(Modifier::REVERSED)
+    } else {
+        Style::new()
+    }
+}
+
+/// A rectangle of `height` rows, `percent_x` wide, centred in `area`.
+fn popup(area: Rect, percent_x: u16, height: u16) -> Rect {
+    let [_, row, _] = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(height),
+        Constraint::Fill(1),
+    ])
+    .areas(area);
+    let [_, col, _] = Layout::horizontal([
+        Constraint::Percentage((100 - percent_x) / 2),
+        Constraint::Percentage(percent_x),
+        Constraint::Percentage((100 - percent_x) / 2),
+    ])
+    .areas(row);
+    col
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use sonda_core::i18n::Locale;
+    use sonda_core::model::{AnalysisReport, AnalysisRow, ReportHeader, Unit};
+    use sonda_core::parsing::values::parse_value;
+    use sonda_core::parsing::{ParsedReports, SkippedLine};
+
+    #[test]
+    fn renders_rows_skipped_lines_and_unknown_names() {
+        let row = |name: &str, normalized: &str| AnalysisRow {
+            raw_name: name.into(),
+            normalized_name: normalized.into(),
+            value: parse_value("< 0,5").unwrap().unwrap(),
+            unit: Unit::MgPerKgTs,
+            uncertainty: None,
+            loq: None,
+            source_line: Some(format!("{name}    <0,5    mg/kg TS")),
+        };
+        let parsed = ParsedReports {
+            reports: vec![AnalysisReport {
+                header: ReportHeader {
+                    sample_id: Some("P1".into()),
+                    ..Default::default()
+                },
+                rows: vec![row("Bly (Pb)", "bly"), row("Tallium", "tallium")],
+            }],
+            warnings: vec![],
+            skipped_lines: vec![SkippedLine {
+                line_text: "Kadmium (Cd)    n.a.".into(),
+                reason: "no parseable numeric value found".into(),
+                report_index: Some(0),
+            }],
+        };
+        let review = Review::new(parsed, ["bly".to_string()].into(), Locale::En);
+
+        let mut terminal = Terminal::new(TestBackend::new(140, 24)).unwrap();
+        terminal
+            .draw(|frame| draw(frame, &review, "report.pdf"))
+            .unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+
+        assert!(screen.contains("Review report.pdf: sample 1 of 1"));
+        assert!(screen.contains("Bly (Pb)    <0,5    mg/kg TS"));
+        assert!(screen.contains("tallium ?"));
+        assert!(screen.contains("1 name(s) not recognised"));
+        assert!(screen.contains("Kadmium (Cd)    n.a."));
+    }
+}
@@ -0,0 +1,439 @@
+//! Endpoint routing and handlers. Transport-independent so it can be tested
+//! without a socket.
+
+use serde::Serialize;
+use sonda_core::classify::sensitivity::Variation;
+use sonda_core::error::SondaError;
+use sonda_core::extraction::ocr::AutoExtractor;
+use sonda_core::i18n::Locale;
+use sonda_core::input::{self, InputFormat};
+use sonda_core::request::ClassifyRequest;
+use sonda_core::rules::builtin;
+use std::path::Path;
+use tiny_http::Method;
+
+use super::multipart::{self, Part};
+use crate::commands::classify::{build_rulesets, classify_bytes};
+
+const VIEWER_INDEX: &str = include_str!("../../../../tools/bbox-viewer/index.html");
+const VIEWER_JS: &str = include_str!("../../../../tools/bbox-viewer/main.js");
+const VIEWER_CSS: &str = include_str!("../../../../tools/bbox-viewer/styles.css");
+
+const API_PATHS: &[&str] = &[
+    "/api/health",
+    "/api/presets",
+    "/api/parse",
+    "/api/classify",
+    "/api/rules/validate",
+];
+
+pub struct ApiRequest {
+    pub method: Method,
+    /// Path including any query string.
+    pub url: String,
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct ApiResponse {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+    /// Redirect target.
+    pub location: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+struct PresetInfo {
+    name: &'static str,
+    /// "threshold" or "hp".
+    engine: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ruleset: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    matrix: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    categories: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct ErrorBody<'a> {
+    error: &'a str,
+}
+
+/// Route a request to its handler. `locale` is the server default; a `lang`
+/// query parameter or field overrides it per request.
+pub fn handle(request: &ApiRequest, locale: Locale) -> ApiResponse {
+    let (path, query) = request
+        .url
+        .split_once('?')
+        .unwrap_or((request.url.as_str(), ""));
+    let locale = query_param(query, "lang")
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(locale);
+
+    // HEAD is answered like GET; the transport drops the body.
+    let method = match &request.method {
+        Method::Head => &Method::Get,
+        method => method,
+    };
+    let result = match (method, path) {
+        (Method::Get, "/" | "/viewer") => return redirect("/viewer/"),
+        (Method::Get, "/viewer/" | "/viewer/index.html") => {
+            return asset(VIEWER_INDEX, "text/html; charset=utf-8")
+        }
+        (Method::Get, "/viewer/main.js") => {
+            return asset(VIEWER_JS, "text/javascript; charset=utf-8")
+        }
+        (Method::Get, "/viewer/styles.css") => return asset(VIEWER_CSS, "text/css; charset=utf-8"),
+        (Method::Get, "/api/health") => json(&serde_json::json!({
+            "status": "ok",
+            "version": env!("CARGO_PKG_VERSION"),
+        })),
+        (Method::Get, "/api/presets") => presets(locale),
+        (Method::Post, "/api/parse") => parse(request),
+        (Method::Post, "/api/classify") => classify(request, locale),
+        (Method::Post, "/api/rules/validate") => validate(request),
+        (_, path) if API_PATHS.contains(&path) => return error(405, "method not allowed"),
+        (_, path) => return error(404, &format!("no such endpoint: {path}")),
+    };
+    result.unwrap_or_else(|e| error(status_for(&e), &e.to_string()))
+}
+
+fn presets(locale: Locale) -> Result<ApiResponse, SondaError> {
+    let presets = builtin::PRESETS
+        .iter()
+        .map(|&name| {
+            if builtin::is_hp_preset(name) {
+                return Ok(PresetInfo {
+                    name,
+                    engine: "hp",
+                    ruleset: None,
+                    version: None,
+                    matrix: None,
+                    description: Some(locale.text("cli.rules.fa_summary").to_string()),
+                    categories: vec!["Icke FA".into(), "FA".into()],
+                });
+            }
+            let rs = builtin::load_preset(name)?;
+            Ok(PresetInfo {
+                name,
+                engine: "threshold",
+                description: rs.description_in(locale).map(str::to_string),
+                ruleset: Some(rs.name),
+                version: Some(rs.version),
+                matrix: rs.matrix,
+                categories: rs.categories,
+            })
+        })
+        .collect::<Result<Vec<_>, SondaError>>()?;
+    json(&presets)
+}
+
+/// Parse an uploaded PDF, Sweco XLSX, Eurofins export or reports JSON without classifying.
+fn parse(request: &ApiRequest) -> Result<ApiResponse, SondaError> {
+    let upload = Upload::from_request(request)?;
+    let format = InputFormat::detect(Path::new(&upload.filename), &upload.data);
+    let parsed = input::parse_input(format, &upload.data, &AutoExtractor::default())?;
+    json(&parsed)
+}
+
+/// Classify either an uploaded file or a JSON body with pre-parsed reports.
+fn classify(request: &ApiRequest, locale: Locale) -> Result<ApiResponse, SondaError> {
+    if is_json(request) {
+        let body: ClassifyRequest = serde_json::from_slice(&request.body)?;
+        let result = body.classify(locale)?;
+        return json(&result);
+    }
+
+    let upload = Upload::from_request(request)?;
+    let locale = match upload.field("lang") {
+        Some(lang) => lang.parse()?,
+        None => locale,
+    };
+    let (rulesets, mut options) = build_rulesets(upload.fields("preset"), vec![], locale)?;
+    options.sensitivity = upload
+        .field("sensitivity")
+        .map(|s| s.parse::<Variation>())
+        .transpose()?;
+    let result = classify_bytes(
+        Path::new(&upload.filename),
+        &upload.data,
+        &rulesets,
+        &options,
+    )?;
+    json(&result)
+}
+
+/// Validate a ruleset sent as the JSON body.
+fn validate(request: &ApiRequest) -> Result<ApiResponse, SondaError> {
+    let text = std::str::from_utf8(&request.body)
+        .map_err(|_| SondaError::InvalidInput("ruleset is not valid UTF-8".into()))?;
+    json(&sonda_core::rules::validation_summary(text)?)
+}
+
+/// An uploaded file with the other form fields sent along with it.
+struct Upload {
+    filename: String,
+    data: Vec<u8>,
+    fields: Vec<Part>,
+}
+
+impl Upload {
+    /// Read a `multipart/form-data` upload (file in the `file` field), or a
+    /// raw body whose format is given by its content type.
+    fn from_request(request: &ApiRequest) -> Result<Upload, SondaError> {
+        let content_type = request.content_type.as_deref().unwrap_or_default();
+        let Some(boundary) = multipart::boundary(content_type) else {
+            if request.body.is_empty() {
+                return Err(SondaError::InvalidInput("empty request body".into()));
+            }
+            return Ok(Upload {
+                filename: raw_filename(content_type).to_string(),
+                data: request.body.clone(),
+                fields: vec![],
+            });
+        };
+
+        let mut fields = multipart::parse(&request.body, &boundary)?;
+        let file = fields
+            .iter()
+            .position(|p| p.name == "file")
+            .map(|idx| fields.remove(idx))
+            .ok_or_else(|| {
+                SondaError::InvalidInput("multipart upload has no 'file' field".into())
+            })?;
+        Ok(Upload {
+            filename: file.filename.unwrap_or_else(|| "upload".into()),
+            data: file.data,
+            fields,
+        })
+    }
+
+    fn field(&self, name: &str) -> Option<String> {
+        self.fields.iter().find(|p| p.name == name).map(Part::text)
+    }
+
+    fn fields(&self, name: &str) -> Vec<String> {
+        self.fields
+            .iter()
+            .filter(|p| p.name == name)
+            .map(Part::text)
+            .collect()
+    }
+}
+
+/// File name used for format detection of a raw upload.
+fn raw_filename(content_type: &str) -> &'static str {
+    let mime = content_type.split(';').next().unwrap_or_default().trim();
+    match mime {
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet" => "upload.xlsx",
+        "text/csv" => "upload.csv",
+        "application/json" => "upload.json",
+        _ => "upload.pdf",
+    }
+}
+
+fn is_json(request: &ApiRequest) -> bool {
+    request
+        .content_type
+        .as_deref()
+        .is_some_and(|ct| ct.split(';').next().unwrap_or_default().trim() == "application/json")
+}
+
+fn query_param<'a>(query: &'a str, key: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find_map(|(k, v)| (k == key).then_some(v))
+}
+
+fn status_for(e: &SondaError) -> u16 {
+    match e {
+        SondaError::Extraction(_)
+        | SondaError::PdftotextNotFound
+        | SondaError::PdftoppmNotFound
+        | SondaError::TesseractNotFound
+        | SondaError::PdftotextFailed { .. }
+        | SondaError::Io(_) => 500,
+        _ => 400,
+    }
+}
+
+fn json<T: Serialize>(value: &T) -> Result<ApiResponse, SondaError> {
+    Ok(ApiResponse {
+        status: 200,
+        content_type: "application/json",
+        body: serde_json::to_vec_pretty(value)?,
+        location: None,
+    })
+}
+
+pub fn error(status: u16, message: &str) -> ApiResponse {
+    ApiResponse {
+        status,
+        content_type: "application/json",
+        body: serde_json::to_vec(&ErrorBody { error: message }).unwrap_or_default(),
+        location: None,
+    }
+}
+
+fn asset(contents: &'static str, content_type: &'static str) -> ApiResponse {
+    ApiResponse {
+        status: 200,
+        content_type,
+        body: contents.as_bytes().to_vec(),
+        location: None,
+    }
+}
+
+fn redirect(location: &'static str) -> ApiResponse {
+    ApiResponse {
+        status: 302,
+        content_type: "text/plain",
+        body: Vec::new(),
+        location: Some(location),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: Method, url: &str, content_type: &str, body: &[u8]) -> ApiResponse {
+        let request = ApiRequest {
+            method,
+            url: url.into(),
+            content_type: Some(content_type.into()),
+            body: body.to_vec(),
+        };
+        handle(&request, Locale::En)
+    }
+
+    fn body(response: &ApiResponse) -> serde_json::Value {
+        serde_json::from_slice(&response.body).unwrap()
+    }
+
+    const REPORTS: &str = r#"[{
+        "header": { "lab": "Eurofins", "sample_id": "P1", "matrix": "jord" },
+        "rows": [{
+            "raw_name": "Bly (Pb)", "normalized_name": "bly",
+            "value": { "Measured": "120" }, "unit": "mg/kg TS"
+        }]
+    }]"#;
+
+    #[test]
+    fn lists_presets() {
+        let response = request(Method::Get, "/api/presets?lang=sv", "", b"");
+        assert_eq!(response.status, 200);
+        let presets = body(&response);
+        let names: Vec<&str> = presets
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, builtin::PRESETS);
+        assert_eq!(presets[2]["engine"], "hp");
+    }
+
+    #[test]
+    fn classifies_json_reports_like_the_cli() {
+        let json = format!(r#"{{ "reports": {REPORTS}, "presets": ["nv"], "lang": "sv" }}"#);
+        let response = request(
+            Method::Post,
+            "/api/classify",
+            "application/json",
+            json.as_bytes(),
+        );
+        assert_eq!(
+            response.status,
+            200,
+            "{}",
+            String::from_utf8_lossy(&response.body)
+        );
+        let result = body(&response);
+        assert_eq!(
+            result["samples"][0]["ruleset_results"][0]["overall_category"],
+            "MKM"
+        );
+        assert!(result["trace"].is_object());
+    }
+
+    #[test]
+    fn classifies_multipart_upload_of_parsed_reports() {
+        let body_bytes = format!(
+            "--b\r\nContent-Disposition: form-data; name=\"preset\"\r\n\r\nnv\r\n\
+--b\r\nContent-Disposition: form-data; name=\"file\"; filename=\"parsed.json\"\r\n\r\n{REPORTS}\r\n--b--\r\n"
+        );
+        let response = request(
+            Method::Post,
+            "/api/classify",
+            "multipart/form-data; boundary=b",
+            body_bytes.as_bytes(),
+        );
+        assert_eq!(
+            response.status,
+            200,
+            "{}",
+            String::from_utf8_lossy(&response.body)
+        );
+        assert_eq!(
+            body(&response)["samples"][0]["ruleset_results"]
+                .as_array()
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn validates_rulesets() {
+        let ruleset = br#"{
+            "name": "Test", "version": "1", "categories": ["KM", "MKM"],
+            "rules": [{ "substance": "bly", "thresholds": { "KM": "50" } }]
+        }"#;
+        let response = request(
+            Method::Post,
+            "/api/rules/validate",
+            "application/json",
+            ruleset,
+        );
+        assert_eq!(response.status, 200);
+        let v = body(&response);
+        assert_eq!(v["valid"], true);
+        assert_eq!(v["warnings"].as_array().unwrap().len(), 1);
+
+        let response = request(
+            Method::Post,
+            "/api/rules/validate",
+            "application/json",
+            b"{}",
+        );
+        assert_eq!(response.status, 400);
+        assert!(body(&response)["error"].is_string());
+    }
+
+    #[test]
+    fn routes_viewer_and_errors() {
+        let response = request(Method::Get, "/", "", b"");
+        assert_eq!(
+            (response.status, response.location),
+            (302, Some("/viewer/"))
+        );
+        let response = request(Method::Get, "/viewer/main.js", "", b"");
+        assert_eq!(response.status, 200);
+        assert!(response.content_type.starts_with("text/javascript"));
+
+        assert_eq!(request(Method::Get, "/api/classify", "", b"").status, 405);
+        assert_eq!(request(Method::Get, "/nope", "", b"").status, 404);
+        assert_eq!(
+            request(Method::Post, "/api/parse", "application/pdf", b"").status,
+            400
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! Local JSON-over-HTTP API for `sonda serve`.
+//!
+//! Requests are handled by a fixed pool of worker threads sharing one
+//! listener. [`api`] routes them; responses use the same JSON shapes as the
+//! CLI's `--output json`.
+
+pub mod api;
+pub mod multipart;
+
+use sonda_core::error::SondaError;
+use sonda_core::i18n::Locale;
+use std::io::Read;
+use std::sync::Arc;
+use tiny_http::{Header, Request, Response, Server};
+
+use api::{ApiRequest, ApiResponse};
+
+/// Largest accepted request body.
+const MAX_BODY_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Bind the listener. Use port 0 to pick a free port.
+pub fn bind(addr: &str) -> Result<Server, SondaError> {
+    Server::http(addr).map_err(|e| SondaError::Io(std::io::Error::other(e)))
+}
+
+/// Serve requests on `jobs` worker threads until the process is stopped.
+pub fn run(server: Arc<Server>, jobs: usize, locale: Locale) {
+    let workers: Vec<_> = (0..jobs.max(1))
+        .map(|_| {
+            let server = Arc::clone(&server);
+            std::thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    respond(request, locale);
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        let _ = worker.join();
+    }
+}
+
+fn respond(mut request: Request, locale: Locale) {
+    let content_type = request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv("Content-Type"))
+        .map(|h| h.value.to_string());
+
+    let mut body = Vec::new();
+    let read = request
+        .as_reader()
+        .take(MAX_BODY_BYTES + 1)
+        .read_to_end(&mut body);
+    let response = match read {
+        Err(e) => api::error(400, &e.to_string()),
+        Ok(_) if body.len() as u64 > MAX_BODY_BYTES => api::error(413, "request body too large"),
+        Ok(_) => api::handle(
+            &ApiRequest {
+                method: request.method().clone(),
+                url: request.url().to_string(),
+                content_type,
+                body,
+            },
+            locale,
+        ),
+    };
+    eprintln!("{} {} {}", request.method(), request.url(), response.status);
+    // The client may have gone away; there is nobody left to tell.
+    let _ = request.respond(into_response(response));
+}
+
+fn into_response(response: ApiResponse) -> Response<std::io::Cursor<Vec<u8>>> {
+    let mut out = Response::from_data(response.body)
+        .with_status_code(response.status)
+        .with_header(header("Content-Type", response.content_type));
+    if let Some(location) = response.location {
+        out.add_header(header("Location", location));
+    }
+    out
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name, value).expect("static header is valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::net::TcpStream;
+
+    fn get(addr: &str, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "GET {path} HTTP/1.1\r\nHost: {addr}\r\nConnection: close\r\n\r\n"
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn serves_concurrent_requests_over_http() {
+        let server = Arc::new(bind("127.0.0.1:0").unwrap());
+        let addr = server.server_addr().to_ip().unwrap().to_string();
+        std::thread::spawn(move || run(server, 2, Locale::En));
+
+        let clients: Vec<_> = (0..4)
+            .map(|_| {
+                let addr = addr.clone();
+                std::thread::spawn(move || get(&addr, "/api/presets"))
+            })
+            .collect();
+        for client in clients {
+            let response = client.join().unwrap();
+            assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+            assert!(response.contains("\"engine\": \"hp\""));
+        }
+        assert!(get(&addr, "/viewer/").contains("Sonda BBox Viewer"));
+    }
+}
@@ -0,0 +1,132 @@
+//! Minimal `multipart/form-data` decoding for uploads.
+
+use sonda_core::error::SondaError;
+
+/// One field of a multipart form.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Part {
+    pub name: String,
+    /// Set for file fields.
+    pub filename: Option<String>,
+    pub data: Vec<u8>,
+}
+
+impl Part {
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.data).trim().to_string()
+    }
+}
+
+/// The boundary of a `multipart/form-data` content type, if it is one.
+pub fn boundary(content_type: &str) -> Option<String> {
+    let (mime, params) = content_type.split_once(';')?;
+    if !mime.trim().eq_ignore_ascii_case("multipart/form-data") {
+        return None;
+    }
+    params.split(';').find_map(|param| {
+        let (key, value) = param.split_once('=')?;
+        key.trim()
+            .eq_ignore_ascii_case("boundary")
+            .then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+/// Split a multipart body into its parts.
+pub fn parse(body: &[u8], boundary: &str) -> Result<Vec<Part>, SondaError> {
+    let delimiter = format!("--{boundary}");
+    let next_delimiter = format!("\r\n--{boundary}");
+    let malformed =
+        |what: &str| SondaError::InvalidInput(format!("malformed multipart body: {what}"));
+
+    let mut pos = find(body, delimiter.as_bytes(), 0).ok_or_else(|| malformed("no boundary"))?
+        + delimiter.len();
+    let mut parts = Vec::new();
+    loop {
+        if body[pos..].starts_with(b"--") {
+            return Ok(parts);
+        }
+        pos += skip_newline(&body[pos..]);
+        let headers_end =
+            find(body, b"\r\n\r\n", pos).ok_or_else(|| malformed("unterminated part headers"))?;
+        let headers = String::from_utf8_lossy(&body[pos..headers_end]);
+        let data_start = headers_end + 4;
+        let data_end = find(body, next_delimiter.as_bytes(), data_start)
+            .ok_or_else(|| malformed("missing closing boundary"))?;
+
+        let (name, filename) =
+            disposition(&headers).ok_or_else(|| malformed("part without a name"))?;
+        parts.push(Part {
+            name,
+            filename,
+            data: body[data_start..data_end].to_vec(),
+        });
+        pos = data_end + next_delimiter.len();
+    }
+}
+
+/// Field name and file name from the part's `Content-Disposition` header.
+fn disposition(headers: &str) -> Option<(String, Option<String>)> {
+    let line = headers.lines().find(|l| {
+        l.split_once(':')
+            .is_some_and(|(k, _)| k.trim().eq_ignore_ascii_case("content-disposition"))
+    })?;
+    let param = |key: &str| {
+        line.split(';').find_map(|p| {
+            let (k, v) = p.split_once('=')?;
+            (k.trim() == key).then(|| v.trim().trim_matches('"').to_string())
+        })
+    };
+    Some((param("name")?, param("filename")))
+}
+
+fn skip_newline(bytes: &[u8]) -> usize {
+    if bytes.starts_with(b"\r\n") {
+        2
+    } else if bytes.starts_with(b"\n") {
+        1
+    } else {
+        0
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|w| w == needle)
+        .map(|i| i + from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_file_and_text_fields() {
+        let body = b"--XyZ\r\n\
+Content-Disposition: form-data; name=\"preset\"\r\n\r\n\
+nv\r\n\
+--XyZ\r\n\
+Content-Disposition: form-data; name=\"file\"; filename=\"report.pdf\"\r\n\
+Content-Type: application/pdf\r\n\r\n\
+%PDF-1.7\r\n\x00binary\r\n\
+--XyZ--\r\n";
+        let boundary = boundary("multipart/form-data; boundary=\"XyZ\"").unwrap();
+        let parts = parse(body, &boundary).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].name, "preset");
+        assert_eq!(parts[0].text(), "nv");
+        assert_eq!(parts[1].filename.as_deref(), Some("report.pdf"));
+        assert_eq!(parts[1].data, b"%PDF-1.7\r\n\x00binary");
+    }
+
+    #[test]
+    fn rejects_other_content_types_and_truncated_bodies() {
+        assert_eq!(boundary("application/json"), None);
+        assert!(parse(
+            b"--XyZ\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\nx",
+            "XyZ"
+        )
+        .is_err());
+    }
+}
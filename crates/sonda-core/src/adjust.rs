@@ -0,0 +1,391 @@
+use crate::error::SondaError;
+use crate::model::{AnalysisReport, AnalysisValue};
+use crate::parsing::normalize::normalize_substance;
+use crate::parsing::values::parse_value;
+use crate::revision::same_id;
+use serde::{Deserialize, Serialize};
+
+/// Replace a reported value before classification.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValueOverride {
+    /// Sample ID (Provmärkning or lab report ID). `None` applies to every sample.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_id: Option<String>,
+    /// Normalized or raw substance name.
+    pub substance: String,
+    pub value: AnalysisValue,
+    pub justification: String,
+}
+
+/// Drop a substance from a sample before classification.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Exclusion {
+    /// Sample ID (Provmärkning or lab report ID). `None` applies to every sample.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_id: Option<String>,
+    /// Normalized or raw substance name.
+    pub substance: String,
+    pub justification: String,
+}
+
+/// Manual overrides and exclusions to apply to reports before classification.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Adjustments {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<ValueOverride>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclusions: Vec<Exclusion>,
+    /// Who made the adjustments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdjustmentAction {
+    Override,
+    Exclude,
+}
+
+/// A manual change that was applied to a sample, kept for the audit trail.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManualAdjustment {
+    pub sample_id: String,
+    /// Normalized substance name.
+    pub substance: String,
+    /// Name as it appeared in the report.
+    pub raw_name: String,
+    pub action: AdjustmentAction,
+    /// Value as reported by the lab.
+    pub original: AnalysisValue,
+    /// Value used for classification (overrides only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<AnalysisValue>,
+    pub justification: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+}
+
+/// An override or exclusion that matched nothing: its substance is missing
+/// from its sample, or its sample from the input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnmatchedAdjustment {
+    pub sample_id: String,
+    pub substance: String,
+}
+
+impl Adjustments {
+    pub fn is_empty(&self) -> bool {
+        self.overrides.is_empty() && self.exclusions.is_empty()
+    }
+
+    /// Adjustments aimed at a sample that none of `sample_ids` names, e.g. a
+    /// mistyped `P9:bly`. `apply` skips them without a trace.
+    pub fn unknown_samples(&self, sample_ids: &[&str]) -> Vec<UnmatchedAdjustment> {
+        self.overrides
+            .iter()
+            .map(|o| (&o.sample_id, &o.substance))
+            .chain(self.exclusions.iter().map(|e| (&e.sample_id, &e.substance)))
+            .filter_map(|(sample_id, substance)| {
+                let sample_id = sample_id.as_deref()?;
+                let known = sample_ids.iter().any(|id| same_id(id, sample_id));
+                (!known).then(|| UnmatchedAdjustment {
+                    sample_id: sample_id.to_string(),
+                    substance: substance.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Every adjustment must say why it was made.
+    pub fn validate(&self) -> Result<(), SondaError> {
+        let missing = self
+            .overrides
+            .iter()
+            .map(|o| (&o.substance, &o.justification))
+            .chain(
+                self.exclusions
+                    .iter()
+                    .map(|e| (&e.substance, &e.justification)),
+            )
+            .find(|(_, justification)| justification.trim().is_empty());
+        match missing {
+            Some((substance, _)) => Err(SondaError::InvalidInput(format!(
+                "manual adjustment of '{}' has no justification",
+                substance
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parse an override given as `[SAMPLE:]SUBSTANCE=VALUE`, e.g. `P1:bly=170`
+/// or `bly=< 0,5`.
+pub fn parse_override(spec: &str, justification: &str) -> Result<ValueOverride, SondaError> {
+    let (target, value) = spec.rsplit_once('=').ok_or_else(|| {
+        SondaError::InvalidInput(format!(
+            "invalid override '{}'. Expected [SAMPLE:]SUBSTANCE=VALUE",
+            spec
+        ))
+    })?;
+    let value = parse_value(value)?
+        .ok_or_else(|| SondaError::InvalidInput(format!("override '{}' has no value", spec)))?;
+    let (sample_id, substance) = parse_target(target, spec)?;
+    Ok(ValueOverride {
+        sample_id,
+        substance,
+        value,
+        justification: justification.to_string(),
+    })
+}
+
+/// Parse an exclusion given as `[SAMPLE:]SUBSTANCE`, e.g. `P1:bly`.
+pub fn parse_exclusion(spec: &str, justification: &str) -> Result<Exclusion, SondaError> {
+    let (sample_id, substance) = parse_target(spec, spec)?;
+    Ok(Exclusion {
+        sample_id,
+        substance,
+        justification: justification.to_string(),
+    })
+}
+
+fn parse_target(target: &str, spec: &str) -> Result<(Option<String>, String), SondaError> {
+    let (sample_id, substance) = match target.split_once(':') {
+        Some((sample, substance)) => (Some(sample.trim().to_string()), substance.trim()),
+        None => (None, target.trim()),
+    };
+    if substance.is_empty() || sample_id.as_deref() == Some("") {
+        return Err(SondaError::InvalidInput(format!(
+            "invalid adjustment '{}'. Expected [SAMPLE:]SUBSTANCE",
+            spec
+        )));
+    }
+    Ok((sample_id, substance.to_string()))
+}
+
+/// Apply overrides and exclusions to one report.
+///
+/// Returns the changes made, and the adjustments that targeted this sample
+/// but matched no substance in it. Adjustments for other samples are ignored.
+pub fn apply(
+    report: &mut AnalysisReport,
+    sample_id: &str,
+    adjustments: &Adjustments,
+) -> (Vec<ManualAdjustment>, Vec<UnmatchedAdjustment>) {
+    let mut applied = Vec::new();
+    let mut unmatched = Vec::new();
+    let ids = [
+        report.header.sample_id.as_deref(),
+        report.header.lab_report_id.as_deref(),
+    ];
+    // Sample IDs are typed by hand, so " p1" still targets "P1".
+    let targets_sample = |target: &Option<String>| match target {
+        None => true,
+        Some(target) => ids.iter().flatten().any(|id| same_id(id, target)),
+    };
+
+    for o in adjustments
+        .overrides
+        .iter()
+        .filter(|o| targets_sample(&o.sample_id))
+    {
+        let before = applied.len();
+        for row in report
+            .rows
+            .iter_mut()
+            .filter(|r| substance_matches(&o.substance, &r.raw_name, &r.normalized_name))
+        {
+            applied.push(ManualAdjustment {
+                sample_id: sample_id.to_string(),
+                substance: row.normalized_name.clone(),
+                raw_name: row.raw_name.clone(),
+                action: AdjustmentAction::Override,
+                original: row.value.clone(),
+                value: Some(o.value.clone()),
+                justification: o.justification.clone(),
+                author: adjustments.author.clone(),
+            });
+            row.value = o.value.clone();
+            // The lab's uncertainty belonged to the original value.
+            row.uncertainty = None;
+        }
+        if applied.len() == before {
+            unmatched.push(UnmatchedAdjustment {
+                sample_id: sample_id.to_string(),
+                substance: o.substance.clone(),
+            });
+        }
+    }
+
+    for e in adjustments
+        .exclusions
+        .iter()
+        .filter(|e| targets_sample(&e.sample_id))
+    {
+        let before = applied.len();
+        report.rows.retain(|row| {
+            if !substance_matches(&e.substance, &row.raw_name, &row.normalized_name) {
+                return true;
+            }
+            applied.push(ManualAdjustment {
+                sample_id: sample_id.to_string(),
+                substance: row.normalized_name.clone(),
+                raw_name: row.raw_name.clone(),
+                action: AdjustmentAction::Exclude,
+                original: row.value.clone(),
+                value: None,
+                justification: e.justification.clone(),
+                author: adjustments.author.clone(),
+            });
+            false
+        });
+        if applied.len() == before {
+            unmatched.push(UnmatchedAdjustment {
+                sample_id: sample_id.to_string(),
+                substance: e.substance.clone(),
+            });
+        }
+    }
+
+    (applied, unmatched)
+}
+
+/// Match a user-supplied name against a row by normalized or raw name.
+fn substance_matches(name: &str, raw_name: &str, normalized_name: &str) -> bool {
+    let name = name.trim();
+    name == normalized_name
+        || normalize_substance(name) == normalized_name
+        || name.eq_ignore_ascii_case(raw_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{AnalysisRow, ReportHeader, Unit};
+    use rust_decimal_macros::dec;
+
+    fn report() -> AnalysisReport {
+        let row = |raw: &str, norm: &str, v| AnalysisRow {
+            raw_name: raw.into(),
+            normalized_name: norm.into(),
+            value: AnalysisValue::Measured(v),
+            unit: Unit::MgPerKgTs,
+            uncertainty: Some(dec!(10)),
+            loq: None,
+            source_line: None,
+        };
+        AnalysisReport {
+            header: ReportHeader {
+                sample_id: Some("P1".into()),
+                lab_report_id: Some("AR-1".into()),
+                ..Default::default()
+            },
+            rows: vec![
+                row("Bly (Pb)", "bly", dec!(120)),
+                row("Arsenik (As)", "arsenik", dec!(8)),
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_specs() {
+        let o = parse_override("P1:bly=< 0,5", "re-analysis").unwrap();
+        assert_eq!(o.sample_id.as_deref(), Some("P1"));
+        assert_eq!(o.substance, "bly");
+        assert_eq!(o.value, AnalysisValue::BelowDetection(dec!(0.5)));
+
+        let o = parse_override("Bly (Pb)=170", "typo").unwrap();
+        assert_eq!(o.sample_id, None);
+        assert_eq!(o.value, AnalysisValue::Measured(dec!(170)));
+
+        assert!(parse_override("bly", "x").is_err());
+        assert!(parse_override("bly=abc", "x").is_err());
+        assert!(parse_exclusion(":bly", "x").is_err());
+        assert_eq!(
+            parse_exclusion("AR-1:arsenik", "x").unwrap().sample_id,
+            Some("AR-1".into())
+        );
+    }
+
+    #[test]
+    fn applies_overrides_and_exclusions() {
+        let mut r = report();
+        let adjustments = Adjustments {
+            overrides: vec![parse_override("P1:Bly (Pb)=170", "re-analysis").unwrap()],
+            exclusions: vec![
+                parse_exclusion("AR-1:arsenik", "contaminated vial").unwrap(),
+                parse_exclusion("P1:zink", "not analysed").unwrap(),
+                parse_exclusion("P2:bly", "other sample").unwrap(),
+            ],
+            author: Some("anna".into()),
+        };
+        let (applied, unmatched) = apply(&mut r, "P1", &adjustments);
+
+        assert_eq!(r.rows.len(), 1);
+        assert_eq!(r.rows[0].value, AnalysisValue::Measured(dec!(170)));
+        assert_eq!(r.rows[0].uncertainty, None);
+
+        assert_eq!(applied.len(), 2);
+        assert_eq!(applied[0].action, AdjustmentAction::Override);
+        assert_eq!(applied[0].original, AnalysisValue::Measured(dec!(120)));
+        assert_eq!(applied[0].author.as_deref(), Some("anna"));
+        assert_eq!(applied[1].action, AdjustmentAction::Exclude);
+        assert_eq!(applied[1].substance, "arsenik");
+
+        assert_eq!(
+            unmatched,
+            vec![UnmatchedAdjustment {
+                sample_id: "P1".into(),
+                substance: "zink".into()
+            }]
+        );
+    }
+
+    #[test]
+    fn finds_adjustments_for_unknown_samples() {
+        let adjustments = Adjustments {
+            overrides: vec![
+                parse_override("P9:bly=170", "typo").unwrap(),
+                parse_override("bly=170", "every sample").unwrap(),
+            ],
+            exclusions: vec![parse_exclusion("AR-1:arsenik", "x").unwrap()],
+            ..Default::default()
+        };
+        assert_eq!(
+            adjustments.unknown_samples(&["P1", "AR-1"]),
+            vec![UnmatchedAdjustment {
+                sample_id: "P9".into(),
+                substance: "bly".into()
+            }]
+        );
+    }
+
+    #[test]
+    fn sample_targets_ignore_case_and_spaces() {
+        // As written by hand in a JSON adjustments file.
+        let adjustments = Adjustments {
+            overrides: vec![ValueOverride {
+                sample_id: Some(" p1 ".into()),
+                substance: "bly".into(),
+                value: AnalysisValue::Measured(dec!(170)),
+                justification: "re-analysis".into(),
+            }],
+            exclusions: vec![parse_exclusion("ar-1:arsenik", "x").unwrap()],
+            ..Default::default()
+        };
+        let mut r = report();
+        let (applied, unmatched) = apply(&mut r, "P1", &adjustments);
+        assert_eq!(applied.len(), 2);
+        assert!(unmatched.is_empty());
+        assert!(adjustments.unknown_samples(&["P1", "AR-1"]).is_empty());
+    }
+
+    #[test]
+    fn justification_is_required() {
+        let adjustments = Adjustments {
+            exclusions: vec![parse_exclusion("bly", " ").unwrap()],
+            ..Default::default()
+        };
+        assert!(adjustments.validate().is_err());
+    }
+}
@@ -1,16 +1,30 @@
-use crate::classify::outcome::{RuleSetResult, SubstanceResult};
+use crate::classify::explain;
+use crate::classify::outcome::{
+    Comparison, ComparisonOp, DecisionPolicy, Escalation, EscalationReason, Explanation, Margin,
+    RuleSetResult, SubstanceResult, ThresholdMargin,
+};
+use crate::i18n::Locale;
 use crate::model::{AnalysisReport, AnalysisValue, Matrix};
 use crate::rules::schema::{RuleSetDef, SubstanceRuleDef};
 use rust_decimal::Decimal;
 use std::collections::HashSet;
 
 /// Classify an analysis report against one or more rulesets.
-pub fn classify(report: &AnalysisReport, rulesets: &[RuleSetDef]) -> Vec<RuleSetResult> {
-    rulesets.iter().map(|rs| classify_one(report, rs)).collect()
+///
+/// Reason strings are written in `locale`.
+pub fn classify(
+    report: &AnalysisReport,
+    rulesets: &[RuleSetDef],
+    locale: Locale,
+) -> Vec<RuleSetResult> {
+    rulesets
+        .iter()
+        .map(|rs| classify_one(report, rs, locale))
+        .collect()
 }
 
 /// Classify an analysis report against a single ruleset.
-fn classify_one(report: &AnalysisReport, ruleset: &RuleSetDef) -> RuleSetResult {
+fn classify_one(report: &AnalysisReport, ruleset: &RuleSetDef, locale: Locale) -> RuleSetResult {
     let mut substance_results = Vec::new();
     let mut matched_substances = HashSet::new();
     let mut matched_rules = HashSet::new();
@@ -53,14 +67,14 @@ fn classify_one(report: &AnalysisReport, ruleset: &RuleSetDef) -> RuleSetResult
 
         for rule in matching_rules {
             matched_rules.insert(rule.substance.clone());
-            let result = classify_substance(row, rule, &ruleset.categories);
+            let result = classify_substance(row, rule, &ruleset.categories, locale);
             substance_results.push(result);
         }
     }
 
     // Determine overall category
-    let (overall_category, overall_reason, determining) =
-        determine_overall(&substance_results, &ruleset.categories);
+    let (overall_category, overall_reason, determining, overall_explanation) =
+        determine_overall(&substance_results, &ruleset.categories, locale);
 
     // Unmatched substances (in report but no rule)
     let all_report_substances: HashSet<String> = report
@@ -87,12 +101,14 @@ fn classify_one(report: &AnalysisReport, ruleset: &RuleSetDef) -> RuleSetResult
         ruleset_name: ruleset.name.clone(),
         overall_category,
         overall_reason,
+        overall_explanation: Some(overall_explanation),
         lowest_category: ruleset.categories.first().cloned(),
         determining_substances: determining,
         substance_results,
         unmatched_substances,
         unmatched_rules,
         hp_details: None,
+        sensitivity: None,
     }
 }
 
@@ -101,13 +117,16 @@ fn classify_substance(
     row: &crate::model::AnalysisRow,
     rule: &SubstanceRuleDef,
     categories: &[String],
+    locale: Locale,
 ) -> SubstanceResult {
     let unit = rule.unit.clone().unwrap_or_else(|| "mg/kg TS".to_string());
 
     match &row.value {
-        AnalysisValue::Measured(value) => classify_measured(*value, row, rule, categories, &unit),
+        AnalysisValue::Measured(value) => {
+            classify_measured(*value, row, rule, categories, &unit, locale)
+        }
         AnalysisValue::BelowDetection(detection_limit) => {
-            classify_below_detection(*detection_limit, row, rule, categories, &unit)
+            classify_below_detection(*detection_limit, row, rule, categories, &unit, locale)
         }
     }
 }
@@ -119,91 +138,87 @@ fn classify_measured(
     rule: &SubstanceRuleDef,
     categories: &[String],
     unit: &str,
+    locale: Locale,
 ) -> SubstanceResult {
+    let mut explanation = Explanation::new(DecisionPolicy::Measured);
+    let mut exceeded = None;
+
     // Iterate categories in order (cleanest first)
-    for (i, cat) in categories.iter().enumerate() {
+    let mut category = None;
+    for cat in categories {
         if let Some(&threshold) = rule.thresholds.get(cat) {
             if value <= threshold {
                 // Classified into this category
-                let reason = if i == 0 {
-                    format!(
-                        "{}: {} {} <= {} ({}) -> classified as {}",
-                        row.raw_name, value, unit, threshold, cat, cat
-                    )
-                } else {
-                    // Find the previous category's threshold for the reason
-                    let prev_parts: Vec<String> = categories[..i]
-                        .iter()
-                        .filter_map(|prev_cat| {
-                            rule.thresholds
-                                .get(prev_cat)
-                                .map(|t| format!("{} > {}:{}", value, prev_cat, t))
-                        })
-                        .collect();
-                    format!(
-                        "{}: {} {} {} but <= {}:{} -> classified as {}",
-                        row.raw_name,
-                        value,
-                        unit,
-                        prev_parts.join(", "),
-                        cat,
-                        threshold,
-                        cat
-                    )
-                };
-
-                let exceeded = if i > 0 {
-                    categories[..i]
-                        .iter()
-                        .rev()
-                        .find_map(|prev_cat| rule.thresholds.get(prev_cat).copied())
-                } else {
-                    None
-                };
-
-                return SubstanceResult {
-                    substance: row.normalized_name.clone(),
-                    raw_name: row.raw_name.clone(),
-                    value: row.value.clone(),
-                    unit: unit.to_string(),
-                    category: cat.clone(),
-                    reason,
-                    exceeded_threshold: exceeded,
-                    uncertain: false,
-                };
+                explanation
+                    .comparisons
+                    .push(comparison(cat, value, ComparisonOp::Le, threshold));
+                category = Some(cat.clone());
+                break;
             }
+            explanation
+                .comparisons
+                .push(comparison(cat, value, ComparisonOp::Gt, threshold));
+            exceeded = Some(threshold);
         }
     }
 
-    // Exceeds all thresholds
-    let last_cat = categories.last().cloned().unwrap_or_default();
-    let exceeds_cat = format!("> {}", last_cat);
+    // Otherwise it exceeds all thresholds
+    let category = category.unwrap_or_else(|| {
+        let last_cat = categories.last().cloned().unwrap_or_default();
+        format!("> {}", last_cat)
+    });
+    let margin = measured_margin(value, &explanation.comparisons, rule, categories);
+    let mut result = substance_result(row, unit, category, exceeded, false, explanation, locale);
+    result.margin = Some(margin);
+    result
+}
 
-    let threshold_parts: Vec<String> = categories
-        .iter()
-        .filter_map(|cat| rule.thresholds.get(cat).map(|t| format!("{}:{}", cat, t)))
-        .collect();
+/// Headroom from a measured value to the thresholds on either side of its category.
+///
+/// Derived from the comparisons made: the `<=` comparison bounds the value from
+/// above, the last `>` comparison from below.
+fn measured_margin(
+    value: Decimal,
+    comparisons: &[Comparison],
+    rule: &SubstanceRuleDef,
+    categories: &[String],
+) -> Margin {
+    let threshold_margin = |category: String, threshold: Decimal| {
+        let absolute = (threshold - value).abs();
+        ThresholdMargin {
+            category,
+            threshold,
+            absolute,
+            relative_pct: (!value.is_zero())
+                .then(|| (absolute / value * Decimal::ONE_HUNDRED).round_dp(2)),
+        }
+    };
 
-    let last_threshold = categories
+    let to_worse = comparisons
+        .iter()
+        .find(|c| c.operator == ComparisonOp::Le)
+        .map(|c| {
+            let pos = categories.iter().position(|cat| *cat == c.category);
+            let next = pos.and_then(|p| {
+                categories[p + 1..]
+                    .iter()
+                    .find(|cat| rule.thresholds.contains_key(*cat))
+            });
+            let category = match next {
+                Some(cat) => cat.clone(),
+                None => format!("> {}", categories.last().cloned().unwrap_or_default()),
+            };
+            threshold_margin(category, c.threshold)
+        });
+    let to_better = comparisons
         .iter()
         .rev()
-        .find_map(|cat| rule.thresholds.get(cat).copied());
+        .find(|c| c.operator == ComparisonOp::Gt)
+        .map(|c| threshold_margin(c.category.clone(), c.threshold));
 
-    SubstanceResult {
-        substance: row.normalized_name.clone(),
-        raw_name: row.raw_name.clone(),
-        value: row.value.clone(),
-        unit: unit.to_string(),
-        category: exceeds_cat,
-        reason: format!(
-            "{}: {} {} > {} -> exceeds all thresholds",
-            row.raw_name,
-            value,
-            unit,
-            threshold_parts.join(", ")
-        ),
-        exceeded_threshold: last_threshold,
-        uncertain: false,
+    Margin {
+        to_worse,
+        to_better,
     }
 }
 
@@ -217,61 +232,97 @@ fn classify_below_detection(
     rule: &SubstanceRuleDef,
     categories: &[String],
     unit: &str,
+    locale: Locale,
 ) -> SubstanceResult {
+    let mut explanation = Explanation::new(DecisionPolicy::BelowDetectionConservative);
+
     // Check each category: if detection limit < threshold, we can confidently
     // classify into that category.
-    for cat in categories.iter() {
+    for cat in categories {
         if let Some(&threshold) = rule.thresholds.get(cat) {
             if detection_limit < threshold {
-                let reason = format!(
-                    "{}: < {} {}, detection limit below {} threshold ({}) -> classified as {}",
-                    row.raw_name, detection_limit, unit, cat, threshold, cat
-                );
-                return SubstanceResult {
-                    substance: row.normalized_name.clone(),
-                    raw_name: row.raw_name.clone(),
-                    value: row.value.clone(),
-                    unit: unit.to_string(),
-                    category: cat.clone(),
-                    reason,
-                    exceeded_threshold: None,
-                    uncertain: false,
-                };
+                explanation.comparisons.push(comparison(
+                    cat,
+                    detection_limit,
+                    ComparisonOp::Lt,
+                    threshold,
+                ));
+                explanation.escalation = escalation(&explanation.comparisons, cat);
+                return substance_result(row, unit, cat.clone(), None, false, explanation, locale);
             }
             // detection_limit >= threshold: can't confirm this category,
             // try next one
+            explanation.comparisons.push(comparison(
+                cat,
+                detection_limit,
+                ComparisonOp::Ge,
+                threshold,
+            ));
         }
     }
 
     // Detection limit exceeds all thresholds -- classify into last category but uncertain
     let last_cat = categories.last().cloned().unwrap_or_default();
     let last_threshold = rule.thresholds.get(&last_cat).copied();
+    explanation.escalation = escalation(&explanation.comparisons, &last_cat);
+    substance_result(
+        row,
+        unit,
+        last_cat,
+        last_threshold,
+        true,
+        explanation,
+        locale,
+    )
+}
 
-    // Actually, try to find the first category where detection limit < threshold
-    // If none found, classify into last category as uncertain
-    // But we've already tried all above, so we're at "exceeds all" territory
-    // However, since it's below detection, we classify into last category as uncertain
+fn comparison(
+    category: &str,
+    value: Decimal,
+    operator: ComparisonOp,
+    threshold: Decimal,
+) -> Comparison {
+    Comparison {
+        category: category.to_string(),
+        value,
+        operator,
+        threshold,
+    }
+}
 
-    let threshold_parts: Vec<String> = categories
+/// Escalation from the first category the detection limit could not confirm.
+fn escalation(comparisons: &[Comparison], to: &str) -> Option<Escalation> {
+    comparisons
         .iter()
-        .filter_map(|cat| rule.thresholds.get(cat).map(|t| format!("{}:{}", cat, t)))
-        .collect();
+        .find(|c| c.operator == ComparisonOp::Ge)
+        .filter(|c| c.category != to)
+        .map(|c| Escalation {
+            from: c.category.clone(),
+            to: to.to_string(),
+            reason: EscalationReason::DetectionLimitAtOrAboveThreshold,
+        })
+}
 
+fn substance_result(
+    row: &crate::model::AnalysisRow,
+    unit: &str,
+    category: String,
+    exceeded_threshold: Option<Decimal>,
+    uncertain: bool,
+    explanation: Explanation,
+    locale: Locale,
+) -> SubstanceResult {
     SubstanceResult {
         substance: row.normalized_name.clone(),
         raw_name: row.raw_name.clone(),
         value: row.value.clone(),
         unit: unit.to_string(),
-        category: last_cat,
-        reason: format!(
-            "{}: < {} {}, detection limit exceeds all thresholds ({}) -> uncertain",
-            row.raw_name,
-            detection_limit,
-            unit,
-            threshold_parts.join(", ")
-        ),
-        exceeded_threshold: last_threshold,
-        uncertain: true,
+        category,
+        reason: explain::substance_reason(&row.raw_name, &row.value, unit, &explanation, locale),
+        exceeded_threshold,
+        uncertain,
+        explanation: Some(explanation),
+        margin: None,
     }
 }
 
@@ -279,13 +330,13 @@ fn classify_below_detection(
 fn determine_overall(
     results: &[SubstanceResult],
     categories: &[String],
-) -> (String, String, Vec<String>) {
+    locale: Locale,
+) -> (String, String, Vec<String>, Explanation) {
+    let mut explanation = Explanation::new(DecisionPolicy::WorstSubstance);
+
     if results.is_empty() {
-        return (
-            "N/A".to_string(),
-            "No substances matched any rules".to_string(),
-            vec![],
-        );
+        let reason = explain::overall_reason("N/A", &explanation, locale);
+        return ("N/A".to_string(), reason, vec![], explanation);
     }
 
     // Find the worst category index
@@ -309,38 +360,28 @@ fn determine_overall(
         }
     }
 
-    if has_exceeds {
+    let overall_category = if has_exceeds {
         let last_cat = categories.last().cloned().unwrap_or_default();
-        let exceeds_cat = format!("> {}", last_cat);
-        let determining: Vec<String> = results
-            .iter()
-            .filter(|r| r.category.starts_with("> "))
-            .map(|r| r.raw_name.clone())
-            .collect();
-        let reason = format!("Determined by {}", determining.join(", "));
-        return (exceeds_cat, reason, determining);
-    }
-
-    let worst = worst_idx.unwrap_or(0);
-    let worst_cat = &categories[worst];
+        format!("> {}", last_cat)
+    } else {
+        categories[worst_idx.unwrap_or(0)].clone()
+    };
 
     let determining: Vec<String> = results
         .iter()
-        .filter(|r| r.category == *worst_cat)
+        .filter(|r| {
+            if has_exceeds {
+                r.category.starts_with("> ")
+            } else {
+                r.category == overall_category
+            }
+        })
         .map(|r| r.raw_name.clone())
         .collect();
 
-    let reason = if determining.len() == 1 {
-        format!("Determined by {} ({})", determining[0], worst_cat)
-    } else {
-        format!(
-            "Determined by {} substances at {} level",
-            determining.len(),
-            worst_cat
-        )
-    };
-
-    (worst_cat.clone(), reason, determining)
+    explanation.determined_by = determining.clone();
+    let reason = explain::overall_reason(&overall_category, &explanation, locale);
+    (overall_category, reason, determining, explanation)
 }
 
 #[cfg(test)]
@@ -359,6 +400,7 @@ mod tests {
             matrix: None,
             categories: vec!["KM".into(), "MKM".into()],
             category_descriptions: BTreeMap::new(),
+            translations: BTreeMap::new(),
             rules: vec![
                 SubstanceRuleDef {
                     substance: "bly".into(),
@@ -404,6 +446,9 @@ mod tests {
             normalized_name: norm.into(),
             value: val,
             unit: Unit::MgPerKgTs,
+            uncertainty: None,
+            loq: None,
+            source_line: None,
         }
     }
 
@@ -413,7 +458,7 @@ mod tests {
             row("Bly (Pb)", "bly", AnalysisValue::Measured(dec!(30))),
             row("Arsenik (As)", "arsenik", AnalysisValue::Measured(dec!(5))),
         ]);
-        let result = classify(&report, &[make_ruleset()]);
+        let result = classify(&report, &[make_ruleset()], Locale::En);
         let rs = &result[0];
         assert_eq!(rs.overall_category, "KM");
         assert!(rs.substance_results.iter().all(|r| r.category == "KM"));
@@ -425,7 +470,7 @@ mod tests {
             row("Bly (Pb)", "bly", AnalysisValue::Measured(dec!(120))),
             row("Arsenik (As)", "arsenik", AnalysisValue::Measured(dec!(5))),
         ]);
-        let result = classify(&report, &[make_ruleset()]);
+        let result = classify(&report, &[make_ruleset()], Locale::En);
         let rs = &result[0];
         assert_eq!(rs.overall_category, "MKM");
 
@@ -446,7 +491,7 @@ mod tests {
             "bly",
             AnalysisValue::Measured(dec!(200)),
         )]);
-        let result = classify(&report, &[make_ruleset()]);
+        let result = classify(&report, &[make_ruleset()], Locale::En);
         let rs = &result[0];
         assert_eq!(rs.overall_category, "> MKM");
     }
@@ -459,7 +504,7 @@ mod tests {
             "arsenik",
             AnalysisValue::BelowDetection(dec!(5)),
         )]);
-        let result = classify(&report, &[make_ruleset()]);
+        let result = classify(&report, &[make_ruleset()], Locale::En);
         let rs = &result[0];
         assert_eq!(rs.substance_results[0].category, "KM");
         assert!(!rs.substance_results[0].uncertain);
@@ -474,7 +519,7 @@ mod tests {
             "kvicksilver",
             AnalysisValue::BelowDetection(dec!(0.30)),
         )]);
-        let result = classify(&report, &[make_ruleset()]);
+        let result = classify(&report, &[make_ruleset()], Locale::En);
         let rs = &result[0];
         let hg = &rs.substance_results[0];
         assert_eq!(hg.category, "MKM");
@@ -487,7 +532,7 @@ mod tests {
             row("Bly (Pb)", "bly", AnalysisValue::Measured(dec!(30))),
             row("Unknown", "unknown", AnalysisValue::Measured(dec!(100))),
         ]);
-        let result = classify(&report, &[make_ruleset()]);
+        let result = classify(&report, &[make_ruleset()], Locale::En);
         let rs = &result[0];
         assert!(rs.unmatched_substances.contains(&"unknown".to_string()));
         assert!(rs.unmatched_rules.contains(&"arsenik".to_string()));
@@ -500,10 +545,98 @@ mod tests {
             "bly",
             AnalysisValue::Measured(dec!(120)),
         )]);
-        let result = classify(&report, &[make_ruleset()]);
+        let result = classify(&report, &[make_ruleset()], Locale::En);
         let rs = &result[0];
         let bly = &rs.substance_results[0];
         assert!(!bly.reason.is_empty());
         assert!(!rs.overall_reason.is_empty());
     }
+
+    #[test]
+    fn test_reason_strings_follow_locale() {
+        let report = make_report(vec![row(
+            "Bly (Pb)",
+            "bly",
+            AnalysisValue::Measured(dec!(120)),
+        )]);
+        let rs = &classify(&report, &[make_ruleset()], Locale::Sv)[0];
+        assert_eq!(
+            rs.substance_results[0].reason,
+            "Bly (Pb): 120 mg/kg TS 120 > KM:50 men <= MKM:180 -> klassas som MKM"
+        );
+        assert_eq!(rs.overall_reason, "Avgörs av Bly (Pb) (MKM)");
+    }
+
+    #[test]
+    fn test_structured_explanation() {
+        let report = make_report(vec![
+            row("Bly (Pb)", "bly", AnalysisValue::Measured(dec!(120))),
+            row(
+                "Kvicksilver (Hg)",
+                "kvicksilver",
+                AnalysisValue::BelowDetection(dec!(0.30)),
+            ),
+        ]);
+        let rs = &classify(&report, &[make_ruleset()], Locale::En)[0];
+
+        let bly = rs.substance_results[0].explanation.as_ref().unwrap();
+        assert_eq!(bly.policy, DecisionPolicy::Measured);
+        let ops: Vec<_> = bly
+            .comparisons
+            .iter()
+            .map(|c| (c.category.as_str(), c.operator, c.threshold))
+            .collect();
+        assert_eq!(
+            ops,
+            vec![
+                ("KM", ComparisonOp::Gt, dec!(50)),
+                ("MKM", ComparisonOp::Le, dec!(180)),
+            ]
+        );
+        assert!(bly.escalation.is_none());
+
+        let hg = rs.substance_results[1].explanation.as_ref().unwrap();
+        assert_eq!(hg.policy, DecisionPolicy::BelowDetectionConservative);
+        let escalation = hg.escalation.as_ref().unwrap();
+        assert_eq!(
+            (escalation.from.as_str(), escalation.to.as_str()),
+            ("KM", "MKM")
+        );
+
+        let overall = rs.overall_explanation.as_ref().unwrap();
+        assert_eq!(overall.policy, DecisionPolicy::WorstSubstance);
+        assert_eq!(overall.determined_by, vec!["Bly (Pb)", "Kvicksilver (Hg)"]);
+        assert_eq!(rs.overall_reason, "Determined by 2 substances at MKM level");
+    }
+
+    #[test]
+    fn test_margin_to_neighbouring_thresholds() {
+        let report = make_report(vec![
+            row("Bly (Pb)", "bly", AnalysisValue::Measured(dec!(170))),
+            row("Arsenik (As)", "arsenik", AnalysisValue::Measured(dec!(5))),
+            row(
+                "Kvicksilver (Hg)",
+                "kvicksilver",
+                AnalysisValue::BelowDetection(dec!(0.1)),
+            ),
+        ]);
+        let rs = &classify(&report, &[make_ruleset()], Locale::En)[0];
+
+        let bly = rs.substance_results[0].margin.as_ref().unwrap();
+        let worse = bly.to_worse.as_ref().unwrap();
+        assert_eq!(worse.category, "> MKM");
+        assert_eq!(worse.absolute, dec!(10));
+        assert_eq!(worse.relative_pct, Some(dec!(5.88)));
+        let better = bly.to_better.as_ref().unwrap();
+        assert_eq!(
+            (better.category.as_str(), better.absolute),
+            ("KM", dec!(120))
+        );
+
+        let arsenik = rs.substance_results[1].margin.as_ref().unwrap();
+        assert_eq!(arsenik.to_worse.as_ref().unwrap().category, "MKM");
+        assert!(arsenik.to_better.is_none());
+
+        assert!(rs.substance_results[2].margin.is_none());
+    }
 }
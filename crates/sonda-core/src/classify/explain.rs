@@ -0,0 +1,200 @@
+//! Rendering of reason strings from structured [`Explanation`]s.
+//!
+//! The engines record what they compared and which policy they applied;
+//! the text in `reason` / `overall_reason` is produced here from that data
+//! so the two can never disagree.
+
+use crate::classify::outcome::{ComparisonOp, DecisionPolicy, Explanation};
+use crate::i18n::Locale;
+use crate::model::AnalysisValue;
+
+/// Render the reason string for a substance decision.
+pub fn substance_reason(
+    raw_name: &str,
+    value: &AnalysisValue,
+    unit: &str,
+    explanation: &Explanation,
+    locale: Locale,
+) -> String {
+    let comparisons = &explanation.comparisons;
+    let value = match value {
+        AnalysisValue::Measured(v) | AnalysisValue::BelowDetection(v) => *v,
+    };
+    let category_thresholds = |op: ComparisonOp| {
+        comparisons
+            .iter()
+            .filter(|c| c.operator == op)
+            .map(|c| format!("{}:{}", c.category, c.threshold))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    match explanation.policy {
+        DecisionPolicy::Measured => match comparisons.last() {
+            Some(last) if last.operator == ComparisonOp::Le && comparisons.len() == 1 => locale
+                .format(
+                    "threshold.first",
+                    &[
+                        ("substance", &raw_name),
+                        ("value", &value),
+                        ("unit", &unit),
+                        ("threshold", &last.threshold),
+                        ("category", &last.category),
+                    ],
+                ),
+            Some(last) if last.operator == ComparisonOp::Le => {
+                let exceeded: Vec<String> = comparisons
+                    .iter()
+                    .filter(|c| c.operator == ComparisonOp::Gt)
+                    .map(|c| format!("{} > {}:{}", c.value, c.category, c.threshold))
+                    .collect();
+                locale.format(
+                    "threshold.between",
+                    &[
+                        ("substance", &raw_name),
+                        ("value", &value),
+                        ("unit", &unit),
+                        ("exceeded", &exceeded.join(", ")),
+                        ("category", &last.category),
+                        ("threshold", &last.threshold),
+                    ],
+                )
+            }
+            _ => locale.format(
+                "threshold.exceeds_all",
+                &[
+                    ("substance", &raw_name),
+                    ("value", &value),
+                    ("unit", &unit),
+                    ("thresholds", &category_thresholds(ComparisonOp::Gt)),
+                ],
+            ),
+        },
+        DecisionPolicy::BelowDetectionConservative => match comparisons.last() {
+            Some(last) if last.operator == ComparisonOp::Lt => locale.format(
+                "threshold.below_detection",
+                &[
+                    ("substance", &raw_name),
+                    ("limit", &value),
+                    ("unit", &unit),
+                    ("category", &last.category),
+                    ("threshold", &last.threshold),
+                ],
+            ),
+            _ => locale.format(
+                "threshold.below_detection_uncertain",
+                &[
+                    ("substance", &raw_name),
+                    ("limit", &value),
+                    ("unit", &unit),
+                    ("thresholds", &category_thresholds(ComparisonOp::Ge)),
+                ],
+            ),
+        },
+        DecisionPolicy::HpSpeciation => match &explanation.speciation {
+            Some(sp) => locale.format(
+                "hp.substance",
+                &[
+                    ("substance", &raw_name),
+                    ("compound", &sp.compound),
+                    ("cas", &sp.cas),
+                    ("concentration", &format!("{:.4}", sp.concentration_pct)),
+                ],
+            ),
+            None => raw_name.to_string(),
+        },
+        DecisionPolicy::WorstSubstance | DecisionPolicy::AnyHpCriterion => {
+            overall_reason("", explanation, locale)
+        }
+    }
+}
+
+/// Render the reason string for an overall (per-ruleset) decision.
+pub fn overall_reason(category: &str, explanation: &Explanation, locale: Locale) -> String {
+    let determined_by = &explanation.determined_by;
+    match explanation.policy {
+        DecisionPolicy::AnyHpCriterion if determined_by.is_empty() => {
+            locale.text("hp.overall.not_hazardous").to_string()
+        }
+        DecisionPolicy::AnyHpCriterion => locale.format(
+            "hp.overall.hazardous",
+            &[("criteria", &determined_by.join(", "))],
+        ),
+        _ if determined_by.is_empty() => locale.text("overall.no_matches").to_string(),
+        _ if category.starts_with("> ") => locale.format(
+            "overall.exceeds",
+            &[("substances", &determined_by.join(", "))],
+        ),
+        _ if determined_by.len() == 1 => locale.format(
+            "overall.single",
+            &[("substance", &determined_by[0]), ("category", &category)],
+        ),
+        _ => locale.format(
+            "overall.multiple",
+            &[("count", &determined_by.len()), ("category", &category)],
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classify::outcome::{Comparison, Escalation, EscalationReason};
+    use rust_decimal_macros::dec;
+
+    fn cmp(
+        category: &str,
+        value: rust_decimal::Decimal,
+        op: ComparisonOp,
+        t: rust_decimal::Decimal,
+    ) -> Comparison {
+        Comparison {
+            category: category.into(),
+            value,
+            operator: op,
+            threshold: t,
+        }
+    }
+
+    #[test]
+    fn renders_between_from_comparisons() {
+        let mut exp = Explanation::new(DecisionPolicy::Measured);
+        exp.comparisons = vec![
+            cmp("KM", dec!(120), ComparisonOp::Gt, dec!(50)),
+            cmp("MKM", dec!(120), ComparisonOp::Le, dec!(180)),
+        ];
+        let reason = substance_reason(
+            "Bly (Pb)",
+            &AnalysisValue::Measured(dec!(120)),
+            "mg/kg TS",
+            &exp,
+            Locale::En,
+        );
+        assert_eq!(
+            reason,
+            "Bly (Pb): 120 mg/kg TS 120 > KM:50 but <= MKM:180 -> classified as MKM"
+        );
+    }
+
+    #[test]
+    fn explanation_serializes_operators_as_symbols() {
+        let mut exp = Explanation::new(DecisionPolicy::BelowDetectionConservative);
+        exp.comparisons = vec![cmp("KM", dec!(0.5), ComparisonOp::Ge, dec!(0.25))];
+        exp.escalation = Some(Escalation {
+            from: "KM".into(),
+            to: "MKM".into(),
+            reason: EscalationReason::DetectionLimitAtOrAboveThreshold,
+        });
+        let json = serde_json::to_value(&exp).unwrap();
+        assert_eq!(json["policy"], "below_detection_conservative");
+        assert_eq!(json["comparisons"][0]["operator"], ">=");
+        assert_eq!(json["comparisons"][0]["threshold"], "0.25");
+        assert_eq!(
+            json["escalation"]["reason"],
+            "detection_limit_at_or_above_threshold"
+        );
+        assert!(json.get("speciation").is_none());
+        let back: Explanation = serde_json::from_value(json).unwrap();
+        assert_eq!(back, exp);
+    }
+}
@@ -1,29 +1,38 @@
+use crate::classify::explain;
 use crate::classify::outcome::{
-    HpCriterionDetail, HpDetails, HpSubstanceContribution, RuleSetResult, SubstanceResult,
+    DecisionPolicy, Explanation, HpCriterionDetail, HpDetails, HpSubstanceContribution,
+    HpSumMargin, RuleSetResult, Speciation, SubstanceResult,
 };
 use crate::clp::schema::ClpSubstance;
 use crate::clp::speciation::{resolve_substances, ResolvedSubstance};
+use crate::i18n::Locale;
 use crate::model::AnalysisReport;
 use rust_decimal::Decimal;
 
 const ONE: Decimal = Decimal::ONE;
 
+/// Concentration in % w/w with four decimals, as shown in reason strings.
+fn pct(value: Decimal) -> String {
+    format!("{:.4}", value)
+}
+
 /// Classify a report using HP criteria (EU Regulation 1357/2014 and 2017/997).
 ///
 /// Returns a `RuleSetResult` with FA/Icke FA classification and HP details.
-pub fn classify_hp(report: &AnalysisReport) -> RuleSetResult {
+/// Criterion names and reason strings are written in `locale`.
+pub fn classify_hp(report: &AnalysisReport, locale: Locale) -> RuleSetResult {
     let (resolved, unresolved) = resolve_substances(report);
 
     let criteria_results = vec![
-        evaluate_hp7(&resolved),
-        evaluate_hp11(&resolved),
-        evaluate_hp10(&resolved),
-        evaluate_hp5(&resolved),
-        evaluate_hp6(&resolved),
-        evaluate_hp4(&resolved),
-        evaluate_hp8(&resolved),
-        evaluate_hp13(&resolved),
-        evaluate_hp14(&resolved),
+        evaluate_hp7(&resolved, locale),
+        evaluate_hp11(&resolved, locale),
+        evaluate_hp10(&resolved, locale),
+        evaluate_hp5(&resolved, locale),
+        evaluate_hp6(&resolved, locale),
+        evaluate_hp4(&resolved, locale),
+        evaluate_hp8(&resolved, locale),
+        evaluate_hp13(&resolved, locale),
+        evaluate_hp14(&resolved, locale),
     ];
 
     let is_hazardous = criteria_results.iter().any(|c| c.triggered);
@@ -40,11 +49,9 @@ pub fn classify_hp(report: &AnalysisReport) -> RuleSetResult {
         "Icke FA".to_string()
     };
 
-    let overall_reason = if is_hazardous {
-        format!("Farligt avfall: triggered by {}", triggered_ids.join(", "))
-    } else {
-        "Icke farligt avfall: no HP criteria triggered".to_string()
-    };
+    let mut overall_explanation = Explanation::new(DecisionPolicy::AnyHpCriterion);
+    overall_explanation.determined_by = triggered_ids.iter().map(|id| id.to_string()).collect();
+    let overall_reason = explain::overall_reason(&overall_category, &overall_explanation, locale);
 
     let determining_substances: Vec<String> = criteria_results
         .iter()
@@ -71,6 +78,13 @@ pub fn classify_hp(report: &AnalysisReport) -> RuleSetResult {
                         .any(|c| c.substance == r.row.normalized_name && c.triggers)
             });
 
+            let mut explanation = Explanation::new(DecisionPolicy::HpSpeciation);
+            explanation.speciation = Some(Speciation {
+                compound: r.compound_name.clone(),
+                cas: r.cas.clone(),
+                concentration_pct: r.concentration_pct,
+            });
+
             SubstanceResult {
                 substance: r.row.normalized_name.clone(),
                 raw_name: r.row.raw_name.clone(),
@@ -81,12 +95,17 @@ pub fn classify_hp(report: &AnalysisReport) -> RuleSetResult {
                 } else {
                     "Icke FA".to_string()
                 },
-                reason: format!(
-                    "{} -> {} ({}): {:.4}% w/w",
-                    r.row.raw_name, r.compound_name, r.cas, r.concentration_pct,
+                reason: explain::substance_reason(
+                    &r.row.raw_name,
+                    &r.row.value,
+                    "mg/kg TS",
+                    &explanation,
+                    locale,
                 ),
                 exceeded_threshold: None,
                 uncertain: false,
+                explanation: Some(explanation),
+                margin: None,
             }
         })
         .collect();
@@ -100,12 +119,14 @@ pub fn classify_hp(report: &AnalysisReport) -> RuleSetResult {
         ruleset_name: "Farligt avfall (HP-bedömning)".to_string(),
         overall_category,
         overall_reason,
+        overall_explanation: Some(overall_explanation),
         lowest_category: None,
         determining_substances,
         substance_results,
         unmatched_substances: unresolved,
         unmatched_rules: vec![],
         hp_details: Some(hp_details),
+        sensitivity: None,
     }
 }
 
@@ -114,7 +135,7 @@ pub fn classify_hp(report: &AnalysisReport) -> RuleSetResult {
 // Individual limits: H350 (1A/1B) ≥ 0.1%, H351 (2) ≥ 1.0%
 // ---------------------------------------------------------------------------
 
-fn evaluate_hp7(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
+fn evaluate_hp7(resolved: &[ResolvedSubstance<'_>], locale: Locale) -> HpCriterionDetail {
     let mut contributions = Vec::new();
     let mut triggered = false;
 
@@ -164,17 +185,18 @@ fn evaluate_hp7(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
     }
 
     let reason = if triggered {
-        "One or more substances exceed individual carcinogenic concentration limits".to_string()
+        locale.text("hp.HP7.triggered").to_string()
     } else {
-        "No substances exceed carcinogenic concentration limits".to_string()
+        locale.text("hp.HP7.not_triggered").to_string()
     };
 
     HpCriterionDetail {
         hp_id: "HP7".to_string(),
-        hp_name: "Carcinogenic".to_string(),
+        hp_name: locale.text("hp.HP7.name").to_string(),
         triggered,
         reason,
         contributions,
+        sum_margins: vec![],
     }
 }
 
@@ -183,7 +205,7 @@ fn evaluate_hp7(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
 // Individual limits: H340 (1A/1B) ≥ 0.1%, H341 (2) ≥ 1.0%
 // ---------------------------------------------------------------------------
 
-fn evaluate_hp11(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
+fn evaluate_hp11(resolved: &[ResolvedSubstance<'_>], locale: Locale) -> HpCriterionDetail {
     let mut contributions = Vec::new();
     let mut triggered = false;
 
@@ -226,17 +248,18 @@ fn evaluate_hp11(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
     }
 
     let reason = if triggered {
-        "One or more substances exceed individual mutagenic concentration limits".to_string()
+        locale.text("hp.HP11.triggered").to_string()
     } else {
-        "No substances exceed mutagenic concentration limits".to_string()
+        locale.text("hp.HP11.not_triggered").to_string()
     };
 
     HpCriterionDetail {
         hp_id: "HP11".to_string(),
-        hp_name: "Mutagenic".to_string(),
+        hp_name: locale.text("hp.HP11.name").to_string(),
         triggered,
         reason,
         contributions,
+        sum_margins: vec![],
     }
 }
 
@@ -245,7 +268,7 @@ fn evaluate_hp11(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
 // Individual limits: H360 (1A/1B) ≥ 0.3% (or SCL), H361 (2) ≥ 0.3%
 // ---------------------------------------------------------------------------
 
-fn evaluate_hp10(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
+fn evaluate_hp10(resolved: &[ResolvedSubstance<'_>], locale: Locale) -> HpCriterionDetail {
     let mut contributions = Vec::new();
     let mut triggered = false;
 
@@ -305,17 +328,18 @@ fn evaluate_hp10(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
     }
 
     let reason = if triggered {
-        "One or more substances exceed reproductive toxicity concentration limits".to_string()
+        locale.text("hp.HP10.triggered").to_string()
     } else {
-        "No substances exceed reproductive toxicity concentration limits".to_string()
+        locale.text("hp.HP10.not_triggered").to_string()
     };
 
     HpCriterionDetail {
         hp_id: "HP10".to_string(),
-        hp_name: "Toxic for reproduction".to_string(),
+        hp_name: locale.text("hp.HP10.name").to_string(),
         triggered,
         reason,
         contributions,
+        sum_margins: vec![],
     }
 }
 
@@ -325,7 +349,7 @@ fn evaluate_hp10(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
 // Summation: H372 sum ≥ 1.0%, H373 sum ≥ 10.0%
 // ---------------------------------------------------------------------------
 
-fn evaluate_hp5(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
+fn evaluate_hp5(resolved: &[ResolvedSubstance<'_>], locale: Locale) -> HpCriterionDetail {
     let mut contributions = Vec::new();
     let mut triggered = false;
 
@@ -430,24 +454,35 @@ fn evaluate_hp5(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
         }
     }
 
+    let sum_margins = vec![
+        sum_margin("H372", sum_h372, threshold_h372),
+        sum_margin("H373", sum_h373, threshold_h373),
+    ];
+
     let reason = if triggered {
-        format!(
-            "STOT triggered (H372 sum: {:.4}% >= {}%, H373 sum: {:.4}% >= {}%)",
-            sum_h372, threshold_h372, sum_h373, threshold_h373,
+        locale.format(
+            "hp.HP5.triggered",
+            &[
+                ("h372_sum", &pct(sum_h372)),
+                ("h372_limit", &threshold_h372),
+                ("h373_sum", &pct(sum_h373)),
+                ("h373_limit", &threshold_h373),
+            ],
         )
     } else {
-        format!(
-            "STOT not triggered (H372 sum: {:.4}%, H373 sum: {:.4}%)",
-            sum_h372, sum_h373,
+        locale.format(
+            "hp.HP5.not_triggered",
+            &[("h372_sum", &pct(sum_h372)), ("h373_sum", &pct(sum_h373))],
         )
     };
 
     HpCriterionDetail {
         hp_id: "HP5".to_string(),
-        hp_name: "STOT SE/RE".to_string(),
+        hp_name: locale.text("hp.HP5.name").to_string(),
         triggered,
         reason,
         contributions,
+        sum_margins,
     }
 }
 
@@ -458,7 +493,7 @@ fn evaluate_hp5(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
 // H330 sum ≥ 0.1%, H331 sum ≥ 5%, H332 sum ≥ 25%
 // ---------------------------------------------------------------------------
 
-fn evaluate_hp6(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
+fn evaluate_hp6(resolved: &[ResolvedSubstance<'_>], locale: Locale) -> HpCriterionDetail {
     let checks: &[(&str, &str)] = &[
         ("H300", "0.1"),
         ("H301", "5.0"),
@@ -472,6 +507,7 @@ fn evaluate_hp6(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
     ];
 
     let mut contributions = Vec::new();
+    let mut sum_margins = Vec::new();
     let mut triggered = false;
     let mut trigger_details = Vec::new();
 
@@ -484,10 +520,18 @@ fn evaluate_hp6(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
             .map(|r| r.concentration_pct)
             .sum();
 
+        sum_margins.push(sum_margin(h_code, sum, threshold));
         let code_triggered = sum >= threshold;
         if code_triggered {
             triggered = true;
-            trigger_details.push(format!("{} sum {:.4}% >= {}%", h_code, sum, threshold));
+            trigger_details.push(locale.format(
+                "hp.sum_at_or_above",
+                &[
+                    ("h_code", &h_code),
+                    ("sum", &pct(sum)),
+                    ("limit", &threshold),
+                ],
+            ));
         }
 
         for r in resolved {
@@ -508,17 +552,21 @@ fn evaluate_hp6(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
     }
 
     let reason = if triggered {
-        format!("Acute toxicity triggered: {}", trigger_details.join("; "))
+        locale.format(
+            "hp.HP6.triggered",
+            &[("details", &trigger_details.join("; "))],
+        )
     } else {
-        "No acute toxicity summation thresholds exceeded".to_string()
+        locale.text("hp.HP6.not_triggered").to_string()
     };
 
     HpCriterionDetail {
         hp_id: "HP6".to_string(),
-        hp_name: "Acute Toxicity".to_string(),
+        hp_name: locale.text("hp.HP6.name").to_string(),
         triggered,
         reason,
         contributions,
+        sum_margins,
     }
 }
 
@@ -527,10 +575,11 @@ fn evaluate_hp6(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
 // Summation: H315 sum ≥ 20%, H319 sum ≥ 20%
 // ---------------------------------------------------------------------------
 
-fn evaluate_hp4(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
+fn evaluate_hp4(resolved: &[ResolvedSubstance<'_>], locale: Locale) -> HpCriterionDetail {
     let checks: &[(&str, &str)] = &[("H315", "20.0"), ("H319", "20.0")];
 
     let mut contributions = Vec::new();
+    let mut sum_margins = Vec::new();
     let mut triggered = false;
 
     for &(h_code, threshold_str) in checks {
@@ -542,6 +591,7 @@ fn evaluate_hp4(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
             .map(|r| r.concentration_pct)
             .sum();
 
+        sum_margins.push(sum_margin(h_code, sum, threshold));
         let code_triggered = sum >= threshold;
         if code_triggered {
             triggered = true;
@@ -565,17 +615,18 @@ fn evaluate_hp4(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
     }
 
     let reason = if triggered {
-        "Irritant summation threshold exceeded".to_string()
+        locale.text("hp.HP4.triggered").to_string()
     } else {
-        "Irritant summation thresholds not exceeded".to_string()
+        locale.text("hp.HP4.not_triggered").to_string()
     };
 
     HpCriterionDetail {
         hp_id: "HP4".to_string(),
-        hp_name: "Irritant".to_string(),
+        hp_name: locale.text("hp.HP4.name").to_string(),
         triggered,
         reason,
         contributions,
+        sum_margins,
     }
 }
 
@@ -584,7 +635,7 @@ fn evaluate_hp4(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
 // Summation: H314 sum ≥ 5%
 // ---------------------------------------------------------------------------
 
-fn evaluate_hp8(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
+fn evaluate_hp8(resolved: &[ResolvedSubstance<'_>], locale: Locale) -> HpCriterionDetail {
     let threshold = dec_const("5.0");
 
     let sum: Decimal = resolved
@@ -609,20 +660,24 @@ fn evaluate_hp8(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
         .collect();
 
     let reason = if triggered {
-        format!("Corrosive: H314 sum {:.4}% >= {}%", sum, threshold)
+        locale.format(
+            "hp.HP8.triggered",
+            &[("sum", &pct(sum)), ("limit", &threshold)],
+        )
     } else {
-        format!(
-            "Corrosive not triggered: H314 sum {:.4}% < {}%",
-            sum, threshold
+        locale.format(
+            "hp.HP8.not_triggered",
+            &[("sum", &pct(sum)), ("limit", &threshold)],
         )
     };
 
     HpCriterionDetail {
         hp_id: "HP8".to_string(),
-        hp_name: "Corrosive".to_string(),
+        hp_name: locale.text("hp.HP8.name").to_string(),
         triggered,
         reason,
         contributions,
+        sum_margins: vec![sum_margin("H314", sum, threshold)],
     }
 }
 
@@ -631,7 +686,7 @@ fn evaluate_hp8(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
 // Individual limits: H317 ≥ 10%, H334 ≥ 10%
 // ---------------------------------------------------------------------------
 
-fn evaluate_hp13(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
+fn evaluate_hp13(resolved: &[ResolvedSubstance<'_>], locale: Locale) -> HpCriterionDetail {
     let threshold = dec_const("10.0");
     let mut contributions = Vec::new();
     let mut triggered = false;
@@ -659,17 +714,18 @@ fn evaluate_hp13(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
     }
 
     let reason = if triggered {
-        "Sensitising threshold exceeded".to_string()
+        locale.text("hp.HP13.triggered").to_string()
     } else {
-        "Sensitising thresholds not exceeded".to_string()
+        locale.text("hp.HP13.not_triggered").to_string()
     };
 
     HpCriterionDetail {
         hp_id: "HP13".to_string(),
-        hp_name: "Sensitising".to_string(),
+        hp_name: locale.text("hp.HP13.name").to_string(),
         triggered,
         reason,
         contributions,
+        sum_margins: vec![],
     }
 }
 
@@ -682,7 +738,7 @@ fn evaluate_hp13(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
 //   4. Σ(c_i) for H410+H411+H412+H413 ≥ 25% (with multipliers)    (simplified)
 // ---------------------------------------------------------------------------
 
-fn evaluate_hp14(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
+fn evaluate_hp14(resolved: &[ResolvedSubstance<'_>], locale: Locale) -> HpCriterionDetail {
     let threshold_check1 = dec_const("25.0");
     let threshold_check2 = dec_const("25.0");
     let _threshold_check3 = dec_const("2.5");
@@ -750,31 +806,38 @@ fn evaluate_hp14(resolved: &[ResolvedSubstance<'_>]) -> HpCriterionDetail {
     let reason = if triggered {
         let mut parts = Vec::new();
         if check1_triggered {
-            parts.push(format!(
-                "H400×M(ac) sum: {:.4}% >= {}%",
-                sum_check1, threshold_check1
+            parts.push(locale.format(
+                "hp.HP14.acute_sum",
+                &[("sum", &pct(sum_check1)), ("limit", &threshold_check1)],
             ));
         }
         if check2_triggered {
-            parts.push(format!(
-                "100×H410×M(ch) sum: {:.4}% >= {}%",
-                sum_check2, threshold_check2
+            parts.push(locale.format(
+                "hp.HP14.chronic_sum",
+                &[("sum", &pct(sum_check2)), ("limit", &threshold_check2)],
             ));
         }
-        format!("Ecotoxic triggered: {}", parts.join("; "))
+        locale.format("hp.HP14.triggered", &[("details", &parts.join("; "))])
     } else {
-        format!(
-            "Ecotoxic not triggered (H400×M sum: {:.4}%, 100×H410×M sum: {:.4}%)",
-            sum_check1, sum_check2
+        locale.format(
+            "hp.HP14.not_triggered",
+            &[
+                ("acute_sum", &pct(sum_check1)),
+                ("chronic_sum", &pct(sum_check2)),
+            ],
         )
     };
 
     HpCriterionDetail {
         hp_id: "HP14".to_string(),
-        hp_name: "Ecotoxic".to_string(),
+        hp_name: locale.text("hp.HP14.name").to_string(),
         triggered,
         reason,
         contributions,
+        sum_margins: vec![
+            sum_margin("H400", sum_check1, threshold_check1),
+            sum_margin("H410", sum_check2, threshold_check2),
+        ],
     }
 }
 
@@ -787,6 +850,18 @@ fn dec_const(s: &str) -> Decimal {
     s.parse::<Decimal>().expect("valid decimal constant")
 }
 
+/// Headroom from a summation to its limit.
+fn sum_margin(h_code: &str, sum: Decimal, limit: Decimal) -> HpSumMargin {
+    let absolute = limit - sum;
+    HpSumMargin {
+        h_code: h_code.to_string(),
+        sum_pct: sum,
+        limit_pct: limit,
+        absolute,
+        relative_pct: (!sum.is_zero()).then(|| (absolute / sum * Decimal::ONE_HUNDRED).round_dp(2)),
+    }
+}
+
 /// Get SCL for reproductive toxicity if present.
 fn get_scl_for_repr(clp: &ClpSubstance, category: &str) -> Option<Decimal> {
     let key = format!("Repr.{}", category);
@@ -805,6 +880,9 @@ mod tests {
             normalized_name: norm.into(),
             value: val,
             unit: Unit::MgPerKgTs,
+            uncertainty: None,
+            loq: None,
+            source_line: None,
         }
     }
 
@@ -823,7 +901,7 @@ mod tests {
             "arsenik",
             AnalysisValue::Measured(dec!(1200)),
         )]);
-        let result = classify_hp(&r);
+        let result = classify_hp(&r, Locale::En);
         assert_eq!(result.overall_category, "FA");
 
         let hp7 = result
@@ -845,7 +923,7 @@ mod tests {
             "arsenik",
             AnalysisValue::Measured(dec!(10)),
         )]);
-        let result = classify_hp(&r);
+        let result = classify_hp(&r, Locale::En);
 
         let hp7 = result
             .hp_details
@@ -867,7 +945,7 @@ mod tests {
             "bly",
             AnalysisValue::Measured(dec!(300)),
         )]);
-        let result = classify_hp(&r);
+        let result = classify_hp(&r, Locale::En);
 
         let hp10 = result
             .hp_details
@@ -888,7 +966,7 @@ mod tests {
             "bly",
             AnalysisValue::Measured(dec!(200)),
         )]);
-        let result = classify_hp(&r);
+        let result = classify_hp(&r, Locale::En);
 
         let hp10 = result
             .hp_details
@@ -911,7 +989,7 @@ mod tests {
             "koppar",
             AnalysisValue::Measured(dec!(500)),
         )]);
-        let result = classify_hp(&r);
+        let result = classify_hp(&r, Locale::En);
 
         let hp14 = result
             .hp_details
@@ -933,7 +1011,7 @@ mod tests {
             "koppar",
             AnalysisValue::Measured(dec!(5000)),
         )]);
-        let result = classify_hp(&r);
+        let result = classify_hp(&r, Locale::En);
 
         let hp14 = result
             .hp_details
@@ -946,6 +1024,38 @@ mod tests {
         assert!(hp14.triggered);
     }
 
+    #[test]
+    fn test_hp14_sum_margins() {
+        let hp14 = |copper| {
+            let r = report(vec![row(
+                "Koppar (Cu)",
+                "koppar",
+                AnalysisValue::Measured(copper),
+            )]);
+            let hp = classify_hp(&r, Locale::En).hp_details.unwrap();
+            hp.criteria_results
+                .into_iter()
+                .find(|c| c.hp_id == "HP14")
+                .unwrap()
+        };
+
+        // 500 mg/kg: H400 sum 5.65% of a 25% limit leaves 19.35%.
+        let low = hp14(dec!(500));
+        let acute = &low.sum_margins[0];
+        assert_eq!(acute.h_code, "H400");
+        assert_eq!(acute.limit_pct, dec!(25));
+        assert_eq!(acute.sum_pct, dec!(5.65));
+        assert_eq!(acute.absolute, dec!(19.35));
+        assert_eq!(acute.relative_pct, Some(dec!(342.48)));
+        assert_eq!(low.sum_margins[1].h_code, "H410");
+
+        // 5000 mg/kg is over the limit, so the margin turns negative.
+        let high = hp14(dec!(5000));
+        assert!(high.triggered);
+        assert!(high.sum_margins[0].absolute < Decimal::ZERO);
+        assert!(high.sum_margins[0].relative_pct.unwrap() < Decimal::ZERO);
+    }
+
     #[test]
     fn test_clean_soil_icke_fa() {
         // Low concentrations — nothing should trigger
@@ -960,7 +1070,7 @@ mod tests {
             row("Koppar (Cu)", "koppar", AnalysisValue::Measured(dec!(30))),
             row("Zink (Zn)", "zink", AnalysisValue::Measured(dec!(80))),
         ]);
-        let result = classify_hp(&r);
+        let result = classify_hp(&r, Locale::En);
         assert_eq!(result.overall_category, "Icke FA");
         assert!(result
             .hp_details
@@ -979,7 +1089,7 @@ mod tests {
             "arsenik",
             AnalysisValue::BelowDetection(dec!(2000)),
         )]);
-        let result = classify_hp(&r);
+        let result = classify_hp(&r, Locale::En);
         assert_eq!(result.overall_category, "Icke FA");
     }
 
@@ -991,7 +1101,7 @@ mod tests {
             "krom_total",
             AnalysisValue::Measured(dec!(1000)),
         )]);
-        let result = classify_hp(&r);
+        let result = classify_hp(&r, Locale::En);
 
         let hp11 = result
             .hp_details
@@ -1022,7 +1132,7 @@ mod tests {
                 AnalysisValue::Measured(dec!(3000)),
             ),
         ]);
-        let result = classify_hp(&r);
+        let result = classify_hp(&r, Locale::En);
 
         let hp5 = result
             .hp_details
@@ -1064,7 +1174,7 @@ mod tests {
                 AnalysisValue::Measured(dec!(2000)),
             ),
         ]);
-        let result = classify_hp(&r);
+        let result = classify_hp(&r, Locale::En);
 
         let hp5 = result
             .hp_details
@@ -1088,7 +1198,7 @@ mod tests {
             ),
             row("Koppar (Cu)", "koppar", AnalysisValue::Measured(dec!(5000))),
         ]);
-        let result = classify_hp(&r);
+        let result = classify_hp(&r, Locale::En);
         assert_eq!(result.overall_category, "FA");
         assert!(result.overall_reason.contains("HP7"));
         assert!(result.overall_reason.contains("HP14"));
@@ -1103,7 +1213,7 @@ mod tests {
             "naftalen",
             AnalysisValue::Measured(dec!(10000)),
         )]);
-        let result = classify_hp(&r);
+        let result = classify_hp(&r, Locale::En);
 
         let hp7 = result
             .hp_details
@@ -1125,7 +1235,7 @@ mod tests {
             "benso_a_pyren",
             AnalysisValue::Measured(dec!(1000)),
         )]);
-        let result = classify_hp(&r);
+        let result = classify_hp(&r, Locale::En);
 
         let hp11 = result
             .hp_details
@@ -1137,4 +1247,21 @@ mod tests {
             .unwrap();
         assert!(hp11.triggered);
     }
+
+    #[test]
+    fn test_swedish_criterion_names_and_reasons() {
+        let r = report(vec![row(
+            "Arsenik (As)",
+            "arsenik",
+            AnalysisValue::Measured(dec!(1200)),
+        )]);
+        let result = classify_hp(&r, Locale::Sv);
+        assert!(result
+            .overall_reason
+            .starts_with("Farligt avfall: uppfyller HP7"));
+
+        let hp7 = &result.hp_details.as_ref().unwrap().criteria_results[0];
+        assert_eq!(hp7.hp_name, "Cancerogen");
+        assert!(result.substance_results[0].reason.ends_with("vikt-%"));
+    }
 }
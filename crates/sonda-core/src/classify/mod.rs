@@ -1,6 +1,8 @@
 pub mod engine;
+pub mod explain;
 pub mod hp_engine;
 pub mod outcome;
+pub mod sensitivity;
 
 pub use engine::classify;
 pub use outcome::{ClassificationResult, RuleSetResult, SampleResult, SubstanceResult};
@@ -1,6 +1,121 @@
-use crate::model::AnalysisValue;
+use crate::adjust::ManualAdjustment;
+use crate::classify::sensitivity::Sensitivity;
+use crate::model::{AnalysisValue, Matrix};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Operator of a threshold comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ComparisonOp {
+    #[serde(rename = "<")]
+    Lt,
+    #[serde(rename = "<=")]
+    Le,
+    #[serde(rename = ">")]
+    Gt,
+    #[serde(rename = ">=")]
+    Ge,
+}
+
+impl fmt::Display for ComparisonOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            ComparisonOp::Lt => "<",
+            ComparisonOp::Le => "<=",
+            ComparisonOp::Gt => ">",
+            ComparisonOp::Ge => ">=",
+        };
+        f.write_str(symbol)
+    }
+}
+
+/// One comparison made against a category threshold, e.g. `120 > KM:50`.
+///
+/// Comparisons are recorded as they hold, in the order they were made.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Comparison {
+    /// Category whose threshold was compared against.
+    pub category: String,
+    /// Measured value or detection limit.
+    pub value: Decimal,
+    pub operator: ComparisonOp,
+    pub threshold: Decimal,
+}
+
+/// Rule used to reach a decision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionPolicy {
+    /// Measured value placed in the cleanest category whose threshold it does not exceed.
+    Measured,
+    /// Detection limit placed in the cleanest category whose threshold lies above it,
+    /// otherwise in the last category and flagged uncertain.
+    BelowDetectionConservative,
+    /// Concentration converted to the worst-case CLP compound for the HP criteria.
+    HpSpeciation,
+    /// Overall category is the worst substance category.
+    WorstSubstance,
+    /// Waste is hazardous if any HP criterion is triggered.
+    AnyHpCriterion,
+}
+
+/// Why a result was moved to a worse category than the value alone suggests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EscalationReason {
+    /// The detection limit is at or above the cleaner category's threshold.
+    DetectionLimitAtOrAboveThreshold,
+}
+
+/// Escalation from a cleaner category to the assigned one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Escalation {
+    pub from: String,
+    pub to: String,
+    pub reason: EscalationReason,
+}
+
+/// CLP compound a concentration was converted to by the HP engine.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Speciation {
+    pub compound: String,
+    pub cas: String,
+    /// Concentration in % w/w.
+    pub concentration_pct: Decimal,
+}
+
+/// Machine-readable explanation of a decision.
+///
+/// The human-readable reason strings are rendered from this data
+/// (see [`crate::classify::explain`]).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Explanation {
+    pub policy: DecisionPolicy,
+    /// Threshold comparisons, in evaluation order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comparisons: Vec<Comparison>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub escalation: Option<Escalation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speciation: Option<Speciation>,
+    /// Substances (threshold rulesets) or HP criteria (HP evaluation) that
+    /// determined an overall decision.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub determined_by: Vec<String>,
+}
+
+impl Explanation {
+    pub fn new(policy: DecisionPolicy) -> Self {
+        Explanation {
+            policy,
+            comparisons: Vec::new(),
+            escalation: None,
+            speciation: None,
+            determined_by: Vec::new(),
+        }
+    }
+}
 
 /// A substance's contribution to an HP criterion evaluation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +148,25 @@ pub struct HpCriterionDetail {
     pub reason: String,
     /// Substances that contributed to the evaluation.
     pub contributions: Vec<HpSubstanceContribution>,
+    /// Headroom of each summation to its limit; empty for criteria with
+    /// individual limits only.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sum_margins: Vec<HpSumMargin>,
+}
+
+/// Distance from an HP summation to its limit, in % w/w.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HpSumMargin {
+    /// H-code whose contributions are summed (e.g., "H314", "H400").
+    pub h_code: String,
+    /// Summed contribution, weighted by M-factors where the criterion uses them.
+    pub sum_pct: Decimal,
+    pub limit_pct: Decimal,
+    /// `limit_pct - sum_pct`; negative once the limit is reached or exceeded.
+    pub absolute: Decimal,
+    /// `absolute` as a percentage of the sum.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relative_pct: Option<Decimal>,
 }
 
 /// Full HP classification details.
@@ -44,6 +178,30 @@ pub struct HpDetails {
     pub criteria_results: Vec<HpCriterionDetail>,
 }
 
+/// Distance from a measured value to a neighbouring category threshold.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThresholdMargin {
+    /// Category the value moves to when it crosses `threshold`.
+    pub category: String,
+    pub threshold: Decimal,
+    /// Distance between value and threshold (never negative).
+    pub absolute: Decimal,
+    /// `absolute` as a percentage of the value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relative_pct: Option<Decimal>,
+}
+
+/// Headroom of a measured value to the thresholds around its category.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Margin {
+    /// Threshold the value must exceed to move to a worse category.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_worse: Option<ThresholdMargin>,
+    /// Threshold the value must fall to for a better category.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_better: Option<ThresholdMargin>,
+}
+
 /// Classification result for a single substance against a single ruleset.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubstanceResult {
@@ -63,6 +221,12 @@ pub struct SubstanceResult {
     pub exceeded_threshold: Option<Decimal>,
     /// True if classification is uncertain (detection limit >= threshold).
     pub uncertain: bool,
+    /// Structured form of `reason`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<Explanation>,
+    /// Headroom to the neighbouring thresholds (measured values only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub margin: Option<Margin>,
 }
 
 /// Classification result for one ruleset applied to a report.
@@ -74,6 +238,9 @@ pub struct RuleSetResult {
     pub overall_category: String,
     /// Human-readable explanation of the overall classification.
     pub overall_reason: String,
+    /// Structured form of `overall_reason`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overall_explanation: Option<Explanation>,
     /// The cleanest category in this ruleset (used for summary rendering).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lowest_category: Option<String>,
@@ -88,6 +255,9 @@ pub struct RuleSetResult {
     /// HP classification details (present only for HP-based evaluation).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hp_details: Option<HpDetails>,
+    /// Sensitivity of the overall category (present only when requested).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sensitivity: Option<Sensitivity>,
 }
 
 /// Warning generated during parsing/classification.
@@ -105,6 +275,18 @@ pub struct ParseWarning {
 pub struct SampleResult {
     /// Sample identifier (Provmärkning or Provnummer).
     pub sample_id: String,
+    /// Lab report ID (Analysrapport number) of the report the sample came from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lab_report_id: Option<String>,
+    /// Sample matrix from the report header.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matrix: Option<Matrix>,
+    /// Revision of the report, if it was reissued.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<u32>,
+    /// Lab report ID of the report it replaces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supersedes: Option<String>,
     /// Results per ruleset.
     pub ruleset_results: Vec<RuleSetResult>,
 }
@@ -115,5 +297,8 @@ pub struct ClassificationResult {
     pub samples: Vec<SampleResult>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub warnings: Vec<ParseWarning>,
+    /// Manual overrides and exclusions applied before classification.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub adjustments: Vec<ManualAdjustment>,
     pub trace: crate::trace::TraceBundle,
 }
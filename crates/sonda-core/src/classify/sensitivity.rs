@@ -0,0 +1,267 @@
+//! Sensitivity of the overall category to measurement variation.
+//!
+//! Each measured value is varied down and up (by a percentage or by its
+//! reported measurement uncertainty) and the ruleset is re-evaluated, one
+//! substance at a time and with all values moved together. Works for any
+//! engine: the caller supplies the re-classification as a closure, so
+//! threshold rulesets and the HP summations are handled the same way.
+
+use crate::classify::outcome::RuleSetResult;
+use crate::error::SondaError;
+use crate::model::{AnalysisReport, AnalysisRow, AnalysisValue};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// How far each measured value is moved in either direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Variation {
+    /// ± this percentage of the value.
+    Percent(Decimal),
+    /// ± the reported measurement uncertainty. Rows without one are not varied.
+    Uncertainty,
+}
+
+impl Variation {
+    /// Half-width of the variation band for a value, if it is varied at all.
+    fn delta(self, row: &AnalysisRow, value: Decimal) -> Option<Decimal> {
+        let delta = match self {
+            Variation::Percent(pct) => value * pct / Decimal::ONE_HUNDRED,
+            Variation::Uncertainty => row.uncertainty?,
+        };
+        (delta > Decimal::ZERO).then_some(delta)
+    }
+}
+
+impl fmt::Display for Variation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Variation::Percent(pct) => write!(f, "±{}%", pct),
+            Variation::Uncertainty => write!(f, "± uncertainty"),
+        }
+    }
+}
+
+impl FromStr for Variation {
+    type Err = SondaError;
+
+    /// Accepts a percentage ("10", "10%", "2.5%") or "uncertainty".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("uncertainty") || s.eq_ignore_ascii_case("u") {
+            return Ok(Variation::Uncertainty);
+        }
+        let pct = s
+            .trim_start_matches('±')
+            .trim_end_matches('%')
+            .trim()
+            .replace(',', ".");
+        match Decimal::from_str(&pct) {
+            Ok(pct) if pct > Decimal::ZERO && pct < Decimal::ONE_HUNDRED => {
+                Ok(Variation::Percent(pct))
+            }
+            _ => Err(SondaError::InvalidInput(format!(
+                "invalid sensitivity '{}'. Use a percentage between 0 and 100 (e.g. 10%) or 'uncertainty'",
+                s
+            ))),
+        }
+    }
+}
+
+/// Overall outcome when one substance's value is varied.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubstanceSensitivity {
+    pub substance: String,
+    pub raw_name: String,
+    pub value: Decimal,
+    /// Value at the low and high end of the variation band.
+    pub low: Decimal,
+    pub high: Decimal,
+    /// Overall category with this value at `low` and at `high`.
+    pub overall_low: String,
+    pub overall_high: String,
+}
+
+/// Sensitivity of one ruleset's overall category.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sensitivity {
+    pub variation: Variation,
+    /// Overall category with every varied value at its low end.
+    pub overall_low: String,
+    /// Overall category with every varied value at its high end.
+    pub overall_high: String,
+    /// Substances whose variation alone changes the overall category.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flips: Vec<SubstanceSensitivity>,
+}
+
+/// Vary the measured values of `report` and re-run `reclassify` to find
+/// which substances could change the overall category of `base`.
+///
+/// Below-detection values are not varied: the detection limit is not a
+/// measurement and already gets the conservative treatment.
+pub fn analyze<F>(
+    report: &AnalysisReport,
+    base: &RuleSetResult,
+    variation: Variation,
+    reclassify: F,
+) -> Sensitivity
+where
+    F: Fn(&AnalysisReport) -> RuleSetResult,
+{
+    let bands: Vec<Option<(Decimal, Decimal, Decimal)>> = report
+        .rows
+        .iter()
+        .map(|row| match row.value {
+            AnalysisValue::Measured(v) => variation
+                .delta(row, v)
+                .map(|d| (v, (v - d).max(Decimal::ZERO), v + d)),
+            AnalysisValue::BelowDetection(_) => None,
+        })
+        .collect();
+
+    let overall_with = |values: &dyn Fn(usize) -> Option<Decimal>| {
+        let mut varied = report.clone();
+        for (idx, row) in varied.rows.iter_mut().enumerate() {
+            if let Some(v) = values(idx) {
+                row.value = AnalysisValue::Measured(v);
+            }
+        }
+        reclassify(&varied).overall_category
+    };
+
+    let overall_low = overall_with(&|idx| bands[idx].map(|(_, low, _)| low));
+    let overall_high = overall_with(&|idx| bands[idx].map(|(_, _, high)| high));
+
+    let flips = report
+        .rows
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, row)| {
+            let (value, low, high) = bands[idx]?;
+            let overall_low = overall_with(&|i| (i == idx).then_some(low));
+            let overall_high = overall_with(&|i| (i == idx).then_some(high));
+            let flips =
+                overall_low != base.overall_category || overall_high != base.overall_category;
+            flips.then(|| SubstanceSensitivity {
+                substance: row.normalized_name.clone(),
+                raw_name: row.raw_name.clone(),
+                value,
+                low,
+                high,
+                overall_low,
+                overall_high,
+            })
+        })
+        .collect();
+
+    Sensitivity {
+        variation,
+        overall_low,
+        overall_high,
+        flips,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classify::{engine, hp_engine};
+    use crate::i18n::Locale;
+    use crate::model::{ReportHeader, Unit};
+    use crate::rules::builtin;
+    use rust_decimal_macros::dec;
+
+    fn row(name: &str, norm: &str, value: Decimal, uncertainty: Option<Decimal>) -> AnalysisRow {
+        AnalysisRow {
+            raw_name: name.into(),
+            normalized_name: norm.into(),
+            value: AnalysisValue::Measured(value),
+            unit: Unit::MgPerKgTs,
+            uncertainty,
+            loq: None,
+            source_line: None,
+        }
+    }
+
+    fn report(rows: Vec<AnalysisRow>) -> AnalysisReport {
+        AnalysisReport {
+            header: ReportHeader::default(),
+            rows,
+        }
+    }
+
+    #[test]
+    fn lead_near_mkm_flips_on_ten_percent() {
+        let nv = builtin::load_preset("nv").unwrap();
+        let report = report(vec![
+            row("Bly (Pb)", "bly", dec!(170), None),
+            row("Arsenik (As)", "arsenik", dec!(5), None),
+        ]);
+        let classify = |r: &AnalysisReport| {
+            engine::classify(r, std::slice::from_ref(&nv), Locale::En).remove(0)
+        };
+        let base = classify(&report);
+        assert_eq!(base.overall_category, "MKM");
+
+        let s = analyze(&report, &base, Variation::Percent(dec!(10)), classify);
+        assert_eq!(s.overall_high, "> MKM");
+        assert_eq!(s.flips.len(), 1);
+        assert_eq!(s.flips[0].substance, "bly");
+        assert_eq!(s.flips[0].high, dec!(187));
+        assert_eq!(s.flips[0].overall_low, "MKM");
+        assert_eq!(s.flips[0].overall_high, "> MKM");
+    }
+
+    #[test]
+    fn uncertainty_only_varies_rows_that_report_one() {
+        let nv = builtin::load_preset("nv").unwrap();
+        let report = report(vec![
+            row("Bly (Pb)", "bly", dec!(170), None),
+            row("Arsenik (As)", "arsenik", dec!(9), Some(dec!(2))),
+        ]);
+        let classify = |r: &AnalysisReport| {
+            engine::classify(r, std::slice::from_ref(&nv), Locale::En).remove(0)
+        };
+        let base = classify(&report);
+        let s = analyze(&report, &base, Variation::Uncertainty, classify);
+        assert!(s.flips.iter().all(|f| f.substance != "bly"));
+        assert_eq!(s.overall_high, base.overall_category);
+    }
+
+    #[test]
+    fn hp_summation_near_limit_is_reported() {
+        // Koppar 2000 mg/kg × 1.13 → 0.226%; H400 × M(ac)=100 → 22.6% < 25%.
+        // +20% gives 2400 mg/kg → 27.1% >= 25% and HP14 triggers.
+        let report = report(vec![row("Koppar (Cu)", "koppar", dec!(2000), None)]);
+        let classify = |r: &AnalysisReport| hp_engine::classify_hp(r, Locale::En);
+        let base = classify(&report);
+        assert_eq!(base.overall_category, "Icke FA");
+
+        let s = analyze(&report, &base, Variation::Percent(dec!(20)), classify);
+        assert_eq!(s.overall_low, "Icke FA");
+        assert_eq!(s.overall_high, "FA");
+        assert_eq!(s.flips.len(), 1);
+        assert_eq!(s.flips[0].substance, "koppar");
+    }
+
+    #[test]
+    fn parses_variation() {
+        assert_eq!(
+            "10%".parse::<Variation>().unwrap(),
+            Variation::Percent(dec!(10))
+        );
+        assert_eq!(
+            "±2,5".parse::<Variation>().unwrap(),
+            Variation::Percent(dec!(2.5))
+        );
+        assert_eq!(
+            "uncertainty".parse::<Variation>().unwrap(),
+            Variation::Uncertainty
+        );
+        assert!("0".parse::<Variation>().is_err());
+        assert!("lots".parse::<Variation>().is_err());
+    }
+}
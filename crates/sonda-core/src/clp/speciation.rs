@@ -108,6 +108,9 @@ mod tests {
             normalized_name: norm.into(),
             value: val,
             unit: Unit::MgPerKgTs,
+            uncertainty: None,
+            loq: None,
+            source_line: None,
         }
     }
 
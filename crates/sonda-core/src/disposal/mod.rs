@@ -0,0 +1,404 @@
+pub mod schema;
+
+use crate::classify::outcome::{ClassificationResult, SampleResult};
+use crate::error::SondaError;
+use rust_decimal::Decimal;
+use schema::{DisposalRoute, PriceList, SampleQuantities, SampleQuantity};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Estimated disposal route and cost for a single sample.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SampleCost {
+    pub sample_id: String,
+    /// Mass represented by the sample, if known.
+    pub tonnes: Option<Decimal>,
+    /// Category that selected the disposal route.
+    pub category: Option<String>,
+    /// Ruleset that produced the category.
+    pub ruleset_name: Option<String>,
+    pub waste_code: Option<String>,
+    pub facility: Option<String>,
+    pub price_per_tonne: Option<Decimal>,
+    /// Estimated cost (`tonnes × price_per_tonne`), if both are known.
+    pub cost: Option<Decimal>,
+}
+
+/// Aggregated tonnage and cost for one category/waste code/facility route.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryCost {
+    pub category: String,
+    pub waste_code: String,
+    pub facility: String,
+    pub sample_count: usize,
+    pub tonnes: Decimal,
+    pub cost: Decimal,
+}
+
+/// Full disposal cost estimate across all samples.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostEstimate {
+    pub price_list: String,
+    pub currency: String,
+    pub samples: Vec<SampleCost>,
+    pub categories: Vec<CategoryCost>,
+    pub total_tonnes: Decimal,
+    pub total_cost: Decimal,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Load a price list from a JSON file.
+pub fn load_price_list(path: &Path) -> Result<PriceList, SondaError> {
+    let content = std::fs::read_to_string(path)?;
+    let prices: PriceList = serde_json::from_str(&content)
+        .map_err(|e| SondaError::CostInput(format!("{}: {}", path.display(), e)))?;
+    validate_price_list(&prices)?;
+    Ok(prices)
+}
+
+/// Load per-sample quantities from a JSON file.
+pub fn load_quantities(path: &Path) -> Result<SampleQuantities, SondaError> {
+    let content = std::fs::read_to_string(path)?;
+    let quantities: SampleQuantities = serde_json::from_str(&content)
+        .map_err(|e| SondaError::CostInput(format!("{}: {}", path.display(), e)))?;
+    Ok(quantities)
+}
+
+/// Validate that a price list is well-formed.
+pub fn validate_price_list(prices: &PriceList) -> Result<(), SondaError> {
+    if prices.routes.is_empty() {
+        return Err(SondaError::CostInput(
+            "price list has no disposal routes".into(),
+        ));
+    }
+    for route in &prices.routes {
+        if route.category.is_empty() {
+            return Err(SondaError::CostInput(
+                "disposal route category must not be empty".into(),
+            ));
+        }
+        if route.price_per_tonne.is_sign_negative() {
+            return Err(SondaError::CostInput(format!(
+                "route '{}' ({}) has a negative price",
+                route.facility, route.category
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Estimate tonnage and disposal cost per sample, per category and in total.
+///
+/// Each sample is matched against the price list using the overall category
+/// of every ruleset result. Samples without a quantity or without a matching
+/// route are listed with a warning and excluded from the totals.
+pub fn estimate_costs(
+    result: &ClassificationResult,
+    quantities: &SampleQuantities,
+    prices: &PriceList,
+) -> Result<CostEstimate, SondaError> {
+    let mut samples = Vec::new();
+    let mut categories: Vec<CategoryCost> = Vec::new();
+    let mut warnings = Vec::new();
+    let mut total_tonnes = Decimal::ZERO;
+    let mut total_cost = Decimal::ZERO;
+
+    for sample in &result.samples {
+        let tonnes = match quantities.samples.get(&sample.sample_id) {
+            Some(q) => Some(resolve_tonnes(&sample.sample_id, q, quantities)?),
+            None => {
+                warnings.push(format!(
+                    "No quantity given for sample '{}'",
+                    sample.sample_id
+                ));
+                None
+            }
+        };
+
+        let selected = select_route(sample, prices);
+        if selected.is_none() {
+            let found: Vec<&str> = sample
+                .ruleset_results
+                .iter()
+                .map(|rs| rs.overall_category.as_str())
+                .collect();
+            warnings.push(format!(
+                "No disposal route for sample '{}' (categories: {})",
+                sample.sample_id,
+                found.join(", ")
+            ));
+        }
+
+        let cost = match (tonnes, selected) {
+            (Some(t), Some((route, _))) => Some(t * route.price_per_tonne),
+            _ => None,
+        };
+
+        if let (Some(t), Some((route, _)), Some(c)) = (tonnes, selected, cost) {
+            total_tonnes += t;
+            total_cost += c;
+            match categories.iter_mut().find(|cc| {
+                cc.category == route.category
+                    && cc.waste_code == route.waste_code
+                    && cc.facility == route.facility
+            }) {
+                Some(cc) => {
+                    cc.sample_count += 1;
+                    cc.tonnes += t;
+                    cc.cost += c;
+                }
+                None => categories.push(CategoryCost {
+                    category: route.category.clone(),
+                    waste_code: route.waste_code.clone(),
+                    facility: route.facility.clone(),
+                    sample_count: 1,
+                    tonnes: t,
+                    cost: c,
+                }),
+            }
+        }
+
+        samples.push(SampleCost {
+            sample_id: sample.sample_id.clone(),
+            tonnes,
+            category: selected.map(|(r, _)| r.category.clone()),
+            ruleset_name: selected.map(|(_, rs)| rs.to_string()),
+            waste_code: selected.map(|(r, _)| r.waste_code.clone()),
+            facility: selected.map(|(r, _)| r.facility.clone()),
+            price_per_tonne: selected.map(|(r, _)| r.price_per_tonne),
+            cost,
+        });
+    }
+
+    Ok(CostEstimate {
+        price_list: prices.name.clone(),
+        currency: prices.currency.clone(),
+        samples,
+        categories,
+        total_tonnes,
+        total_cost,
+        warnings,
+    })
+}
+
+/// Convert a sample quantity to tonnes.
+fn resolve_tonnes(
+    sample_id: &str,
+    quantity: &SampleQuantity,
+    quantities: &SampleQuantities,
+) -> Result<Decimal, SondaError> {
+    let tonnes = match (quantity.tonnes, quantity.volume_m3) {
+        (Some(t), _) => t,
+        (None, Some(v)) => {
+            let density = quantity
+                .density
+                .or(quantities.default_density)
+                .ok_or_else(|| {
+                    SondaError::CostInput(format!(
+                        "sample '{}' gives a volume but no density (set density or default_density)",
+                        sample_id
+                    ))
+                })?;
+            v * density
+        }
+        (None, None) => {
+            return Err(SondaError::CostInput(format!(
+                "sample '{}' has neither tonnes nor volume_m3",
+                sample_id
+            )));
+        }
+    };
+
+    if tonnes.is_sign_negative() {
+        return Err(SondaError::CostInput(format!(
+            "sample '{}' has a negative quantity",
+            sample_id
+        )));
+    }
+    Ok(tonnes)
+}
+
+/// Pick the most expensive route matching any of the sample's overall categories.
+fn select_route<'a>(
+    sample: &'a SampleResult,
+    prices: &'a PriceList,
+) -> Option<(&'a DisposalRoute, &'a str)> {
+    let mut best: Option<(&DisposalRoute, &str)> = None;
+    for rs in &sample.ruleset_results {
+        for route in &prices.routes {
+            if route.category != rs.overall_category {
+                continue;
+            }
+            if let Some(ref name) = route.ruleset {
+                if *name != rs.ruleset_name {
+                    continue;
+                }
+            }
+            match best {
+                Some((current, _)) if current.price_per_tonne >= route.price_per_tonne => {}
+                _ => best = Some((route, rs.ruleset_name.as_str())),
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classify::outcome::RuleSetResult;
+    use crate::trace::TraceBundle;
+    use rust_decimal_macros::dec;
+    use std::collections::BTreeMap;
+
+    fn rs(name: &str, category: &str) -> RuleSetResult {
+        RuleSetResult {
+            ruleset_name: name.into(),
+            overall_category: category.into(),
+            overall_reason: String::new(),
+            overall_explanation: None,
+            lowest_category: None,
+            determining_substances: vec![],
+            substance_results: vec![],
+            unmatched_substances: vec![],
+            unmatched_rules: vec![],
+            hp_details: None,
+            sensitivity: None,
+        }
+    }
+
+    fn result(samples: Vec<(&str, Vec<RuleSetResult>)>) -> ClassificationResult {
+        ClassificationResult {
+            samples: samples
+                .into_iter()
+                .map(|(id, ruleset_results)| SampleResult {
+                    sample_id: id.into(),
+                    lab_report_id: None,
+                    matrix: None,
+                    revision: None,
+                    supersedes: None,
+                    ruleset_results,
+                })
+                .collect(),
+            warnings: vec![],
+            adjustments: vec![],
+            trace: TraceBundle::default(),
+        }
+    }
+
+    fn route(category: &str, code: &str, price: Decimal) -> DisposalRoute {
+        DisposalRoute {
+            category: category.into(),
+            ruleset: None,
+            waste_code: code.into(),
+            facility: format!("Facility {category}"),
+            price_per_tonne: price,
+        }
+    }
+
+    fn prices() -> PriceList {
+        PriceList {
+            name: "Test".into(),
+            currency: "SEK".into(),
+            routes: vec![
+                route("KM", "17 05 04", dec!(100)),
+                route("MKM", "17 05 04", dec!(300)),
+                route("FA", "17 05 03*", dec!(1500)),
+            ],
+        }
+    }
+
+    fn tonnes(t: Decimal) -> SampleQuantity {
+        SampleQuantity {
+            tonnes: Some(t),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_cost_per_sample_and_total() {
+        let result = result(vec![
+            ("P1", vec![rs("NV", "KM"), rs("HP", "Icke FA")]),
+            ("P2", vec![rs("NV", "MKM"), rs("HP", "Icke FA")]),
+        ]);
+        let quantities = SampleQuantities {
+            default_density: None,
+            samples: BTreeMap::from([
+                ("P1".into(), tonnes(dec!(10))),
+                ("P2".into(), tonnes(dec!(20))),
+            ]),
+        };
+        let est = estimate_costs(&result, &quantities, &prices()).unwrap();
+        assert_eq!(est.samples[0].cost, Some(dec!(1000)));
+        assert_eq!(est.samples[1].cost, Some(dec!(6000)));
+        assert_eq!(est.total_tonnes, dec!(30));
+        assert_eq!(est.total_cost, dec!(7000));
+        assert_eq!(est.categories.len(), 2);
+        assert!(est.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_most_restrictive_route_wins() {
+        let result = result(vec![("P1", vec![rs("NV", "MKM"), rs("HP", "FA")])]);
+        let quantities = SampleQuantities {
+            default_density: None,
+            samples: BTreeMap::from([("P1".into(), tonnes(dec!(2)))]),
+        };
+        let est = estimate_costs(&result, &quantities, &prices()).unwrap();
+        assert_eq!(est.samples[0].category.as_deref(), Some("FA"));
+        assert_eq!(est.samples[0].waste_code.as_deref(), Some("17 05 03*"));
+        assert_eq!(est.total_cost, dec!(3000));
+    }
+
+    #[test]
+    fn test_volume_uses_default_density() {
+        let result = result(vec![("P1", vec![rs("NV", "KM")])]);
+        let quantities = SampleQuantities {
+            default_density: Some(dec!(1.8)),
+            samples: BTreeMap::from([(
+                "P1".into(),
+                SampleQuantity {
+                    volume_m3: Some(dec!(10)),
+                    ..Default::default()
+                },
+            )]),
+        };
+        let est = estimate_costs(&result, &quantities, &prices()).unwrap();
+        assert_eq!(est.samples[0].tonnes, Some(dec!(18.0)));
+    }
+
+    #[test]
+    fn test_volume_without_density_rejected() {
+        let result = result(vec![("P1", vec![rs("NV", "KM")])]);
+        let quantities = SampleQuantities {
+            default_density: None,
+            samples: BTreeMap::from([(
+                "P1".into(),
+                SampleQuantity {
+                    volume_m3: Some(dec!(10)),
+                    ..Default::default()
+                },
+            )]),
+        };
+        assert!(estimate_costs(&result, &quantities, &prices()).is_err());
+    }
+
+    #[test]
+    fn test_missing_quantity_and_route_warn() {
+        let result = result(vec![("P1", vec![rs("NV", "> MKM")])]);
+        let est = estimate_costs(&result, &SampleQuantities::default(), &prices()).unwrap();
+        assert_eq!(est.warnings.len(), 2);
+        assert_eq!(est.samples[0].cost, None);
+        assert_eq!(est.total_cost, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_route_restricted_to_ruleset() {
+        let mut p = prices();
+        p.routes[1].ruleset = Some("Other".into());
+        let result = result(vec![("P1", vec![rs("NV", "MKM")])]);
+        let est = estimate_costs(&result, &SampleQuantities::default(), &p).unwrap();
+        assert!(est.samples[0].category.is_none());
+    }
+}
@@ -0,0 +1,55 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A price list of receiving facilities, keyed by classification category.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceList {
+    pub name: String,
+    /// Currency used for all prices (e.g., "SEK").
+    pub currency: String,
+    /// Available disposal routes. A sample is sent to the most expensive
+    /// route whose category it matches, since the most restrictive
+    /// classification governs where the masses may be received.
+    pub routes: Vec<DisposalRoute>,
+}
+
+/// A single disposal route: where material of a given category is received.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisposalRoute {
+    /// Overall category this route accepts (e.g., "KM", "> MKM", "FA").
+    pub category: String,
+    /// If set, only match the category when it was produced by this ruleset.
+    #[serde(default)]
+    pub ruleset: Option<String>,
+    /// Waste code under the Swedish waste ordinance (e.g., "17 05 04").
+    pub waste_code: String,
+    /// Name of the receiving facility.
+    pub facility: String,
+    /// Reception fee per tonne (as string for exact decimal).
+    pub price_per_tonne: Decimal,
+}
+
+/// Excavated quantities per sample, given as mass or volume.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SampleQuantities {
+    /// Density in tonnes/m³ used when a sample only gives a volume.
+    #[serde(default)]
+    pub default_density: Option<Decimal>,
+    /// Sample ID -> quantity represented by that sample.
+    pub samples: BTreeMap<String, SampleQuantity>,
+}
+
+/// Quantity of material represented by one sample.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SampleQuantity {
+    /// Mass in tonnes. Takes precedence over volume.
+    #[serde(default)]
+    pub tonnes: Option<Decimal>,
+    /// Volume in m³, converted using `density` or the default density.
+    #[serde(default)]
+    pub volume_m3: Option<Decimal>,
+    /// Density in tonnes/m³ for this sample.
+    #[serde(default)]
+    pub density: Option<Decimal>,
+}
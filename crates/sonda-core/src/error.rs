@@ -8,9 +8,18 @@ pub enum SondaError {
     #[error("pdftotext not found. Install poppler: brew install poppler (macOS) or apt install poppler-utils (Linux)")]
     PdftotextNotFound,
 
+    #[error("pdftoppm not found. It is part of poppler: brew install poppler (macOS) or apt install poppler-utils (Linux)")]
+    PdftoppmNotFound,
+
+    #[error("tesseract not found. Install it with Swedish language data: brew install tesseract tesseract-lang (macOS) or apt install tesseract-ocr tesseract-ocr-swe (Linux)")]
+    TesseractNotFound,
+
     #[error("pdftotext failed with exit code {code}: {stderr}")]
     PdftotextFailed { code: i32, stderr: String },
 
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
+
     #[error("failed to parse report: {0}")]
     ParseError(String),
 
@@ -20,12 +29,21 @@ pub enum SondaError {
     #[error("invalid ruleset: {0}")]
     RulesetInvalid(String),
 
-    #[error("unsupported report format: {0}. Currently only Eurofins reports are supported.")]
+    #[error("unsupported report format: {0}")]
     UnsupportedReport(String),
 
     #[error("report matrix '{matrix}' does not match any of the provided rulesets")]
     MatrixMismatch { matrix: String },
 
+    #[error("invalid disposal cost input: {0}")]
+    CostInput(String),
+
+    #[error("invalid project manifest: {0}")]
+    ProjectInvalid(String),
+
+    #[error("invalid column mapping: {0}")]
+    MappingInvalid(String),
+
     #[error("no substances matched between report and ruleset")]
     NoMatches,
 
@@ -0,0 +1,86 @@
+//! Spreadsheet cell conversions shared by the workbook importers.
+
+use calamine::Data;
+use rust_decimal::Decimal;
+
+use crate::model::{AnalysisValue, Date};
+use crate::parsing::values::parse_value;
+
+/// Convert f64 to Decimal, preserving reasonable precision.
+///
+/// Uses string round-trip to avoid floating-point artifacts
+/// (e.g., 0.0035_f64 becoming 0.00349999...).
+pub(crate) fn f64_to_decimal(f: f64) -> Decimal {
+    // Format with enough precision to capture the original value,
+    // then parse as Decimal.
+    let s = format!("{f}");
+    s.parse::<Decimal>().unwrap_or_else(|_| {
+        // Fallback: use from_f64_retain
+        Decimal::try_from(f).unwrap_or_default()
+    })
+}
+
+/// A cell as trimmed text; dates as YYYY-MM-DD.
+pub(crate) fn cell_text(cell: &Data) -> String {
+    match cell {
+        Data::Empty => String::new(),
+        Data::String(s) => s.trim().to_string(),
+        Data::Float(f) => f64_to_decimal(*f).to_string(),
+        Data::Int(i) => i.to_string(),
+        Data::DateTime(dt) => excel_date(dt.as_f64()),
+        Data::DateTimeIso(s) => s.chars().take(10).collect(),
+        other => other.to_string(),
+    }
+}
+
+/// A result cell: numbers are measured values, text goes through
+/// [`parse_value`] so "<0,03" is below detection. `Ok(None)` for empty
+/// cells and placeholders like "-".
+pub(crate) fn cell_value(cell: &Data) -> Result<Option<AnalysisValue>, String> {
+    match cell {
+        Data::Float(f) => Ok(Some(AnalysisValue::Measured(f64_to_decimal(*f)))),
+        Data::Int(i) => Ok(Some(AnalysisValue::Measured(Decimal::from(*i)))),
+        Data::Empty => Ok(None),
+        Data::String(s) => parse_value(s).map_err(|e| e.to_string()),
+        other => Err(format!("unexpected cell value '{other}'")),
+    }
+}
+
+/// An Excel date serial as YYYY-MM-DD (days since 1899-12-30).
+pub(crate) fn excel_date(serial: f64) -> String {
+    Date::from_unix_days(serial.floor() as i64 - 25569)
+        .map_or_else(|| serial.to_string(), |date| date.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn f64_to_decimal_preserves_precision() {
+        assert_eq!(f64_to_decimal(0.0035), dec!(0.0035));
+        assert_eq!(f64_to_decimal(68.0), dec!(68));
+        assert_eq!(f64_to_decimal(1.23), dec!(1.23));
+    }
+
+    #[test]
+    fn test_excel_date() {
+        assert_eq!(excel_date(45426.0), "2024-05-14");
+        assert_eq!(excel_date(36526.5), "2000-01-01");
+    }
+
+    #[test]
+    fn test_text_cells_below_detection() {
+        assert_eq!(
+            cell_value(&Data::String("<0,03".into())),
+            Ok(Some(AnalysisValue::BelowDetection(dec!(0.03))))
+        );
+        assert_eq!(
+            cell_value(&Data::Float(12.5)),
+            Ok(Some(AnalysisValue::Measured(dec!(12.5))))
+        );
+        assert_eq!(cell_value(&Data::String("-".into())), Ok(None));
+        assert!(cell_value(&Data::String("se bilaga".into())).is_err());
+    }
+}
@@ -0,0 +1,519 @@
+//! Eurofins electronic data deliverables (CSV or XLSX exports).
+//!
+//! One row per result, with columns for sample, parameter, result, unit,
+//! LOQ and measurement uncertainty. Columns are found by their header name
+//! (Swedish or English), so their order and any extra columns don't matter.
+//! Rows are grouped into one report per (analysis report, sample).
+
+use std::io::Cursor;
+
+use calamine::{Reader, Xlsx};
+use rust_decimal::Decimal;
+
+use crate::error::SondaError;
+use crate::extraction::cells::cell_text;
+use crate::model::{
+    AnalysisRow, AnalysisValue, Coordinates, Date, DepthInterval, Matrix, ReportHeader, Unit,
+};
+use crate::parsing::normalize::normalize_substance;
+use crate::parsing::values::{parse_uncertainty, parse_value};
+use crate::parsing::{push_sample, ParsedReports, SkippedLine};
+
+/// The header row must appear within this many rows of the top.
+const HEADER_SEARCH_ROWS: usize = 20;
+
+/// Result cells meaning "below the LOQ" without a number of their own.
+const BELOW_LOQ: &[&str] = &["<loq", "< loq", "ej påv.", "ej påvisad", "n.d."];
+
+#[derive(Clone, Copy)]
+enum Column {
+    ReportId,
+    SampleNumber,
+    SampleLabel,
+    Date,
+    Matrix,
+    Project,
+    Parameter,
+    Prefix,
+    Result,
+    Unit,
+    Loq,
+    Uncertainty,
+    Received,
+    Depth,
+    Sampler,
+    Location,
+    OrderNumber,
+    North,
+    East,
+}
+
+/// Header names per column, lowercase.
+const ALIASES: &[(Column, &[&str])] = &[
+    (
+        Column::ReportId,
+        &[
+            "analysrapport",
+            "rapportnummer",
+            "report number",
+            "report no",
+        ],
+    ),
+    (
+        Column::SampleNumber,
+        &[
+            "provnummer",
+            "provnr",
+            "labnummer",
+            "lab sample id",
+            "sample number",
+        ],
+    ),
+    (
+        Column::SampleLabel,
+        &[
+            "provmärkning",
+            "provbeteckning",
+            "sample id",
+            "sample name",
+            "client sample id",
+        ],
+    ),
+    (
+        Column::Date,
+        &[
+            "provtagningsdatum",
+            "provtagningsdag",
+            "sampling date",
+            "sampled",
+        ],
+    ),
+    (Column::Matrix, &["matris", "provtyp", "matrix"]),
+    (Column::Project, &["projekt", "project"]),
+    (Column::Parameter, &["parameter", "analyt", "analyte"]),
+    (Column::Prefix, &["prefix", "operator"]),
+    (Column::Result, &["resultat", "result", "värde", "value"]),
+    (Column::Unit, &["enhet", "unit"]),
+    (
+        Column::Loq,
+        &[
+            "rapporteringsgräns",
+            "rapp.gräns",
+            "loq",
+            "mrl",
+            "reporting limit",
+        ],
+    ),
+    (
+        Column::Uncertainty,
+        &["mätosäkerhet", "osäkerhet", "mu", "uncertainty"],
+    ),
+    (
+        Column::Received,
+        &[
+            "ankomstdatum",
+            "mottagningsdatum",
+            "received",
+            "date received",
+        ],
+    ),
+    (Column::Depth, &["provtagningsdjup", "djup", "depth"]),
+    (Column::Sampler, &["provtagare", "sampler"]),
+    (
+        Column::Location,
+        &["provplats", "provpunkt", "sampling point", "location"],
+    ),
+    (
+        Column::OrderNumber,
+        &[
+            "ordernummer",
+            "ordernr",
+            "beställningsnummer",
+            "order number",
+        ],
+    ),
+    (
+        Column::North,
+        &["n-koordinat", "koordinat n", "x-koordinat", "northing"],
+    ),
+    (
+        Column::East,
+        &["e-koordinat", "koordinat e", "y-koordinat", "easting"],
+    ),
+];
+
+/// Positions of the recognised columns in the header row.
+#[derive(Default)]
+struct Columns {
+    indices: [Option<usize>; ALIASES.len()],
+}
+
+impl Columns {
+    fn from_header(row: &[String]) -> Columns {
+        let mut columns = Columns::default();
+        for (i, cell) in row.iter().enumerate() {
+            let name = cell.trim().to_lowercase();
+            for (column, aliases) in ALIASES {
+                let slot = &mut columns.indices[*column as usize];
+                if slot.is_none() && aliases.iter().any(|a| header_matches(&name, a)) {
+                    *slot = Some(i);
+                    break;
+                }
+            }
+        }
+        columns
+    }
+
+    /// Parameter, result and some sample identifier are required.
+    fn is_complete(&self) -> bool {
+        self.index(Column::Parameter).is_some()
+            && self.index(Column::Result).is_some()
+            && (self.index(Column::SampleNumber).is_some()
+                || self.index(Column::SampleLabel).is_some())
+    }
+
+    fn index(&self, column: Column) -> Option<usize> {
+        self.indices[column as usize]
+    }
+
+    fn get<'a>(&self, row: &'a [String], column: Column) -> &'a str {
+        self.index(column)
+            .and_then(|i| row.get(i))
+            .map(|s| s.trim())
+            .unwrap_or("")
+    }
+}
+
+/// "Mätosäkerhet (±)" and "LOQ (mg/kg)" match their bare names.
+fn header_matches(name: &str, alias: &str) -> bool {
+    name == alias
+        || name
+            .strip_prefix(alias)
+            .is_some_and(|rest| rest.starts_with([' ', '(']))
+}
+
+/// Parse a Eurofins CSV or XLSX export into structured reports.
+///
+/// XLSX is recognised by its zip signature; anything else is read as CSV.
+pub fn parse_eurofins_export(bytes: &[u8]) -> Result<ParsedReports, SondaError> {
+    let table = if bytes.starts_with(b"PK\x03\x04") {
+        read_xlsx(bytes)?
+    } else {
+        read_csv(bytes)?
+    };
+    parse_table(&table)
+}
+
+/// Whether an xlsx workbook has an export header row on its first sheet.
+pub fn is_eurofins_export_xlsx(bytes: &[u8]) -> bool {
+    read_xlsx(bytes)
+        .map(|table| find_header(&table).is_some())
+        .unwrap_or(false)
+}
+
+fn find_header(table: &[Vec<String>]) -> Option<(usize, Columns)> {
+    table
+        .iter()
+        .take(HEADER_SEARCH_ROWS)
+        .enumerate()
+        .map(|(i, row)| (i, Columns::from_header(row)))
+        .find(|(_, columns)| columns.is_complete())
+}
+
+/// Results of one sample while rows are being collected.
+struct Sample {
+    key: (String, String),
+    header: ReportHeader,
+    rows: Vec<AnalysisRow>,
+    skipped: Vec<SkippedLine>,
+}
+
+fn parse_table(table: &[Vec<String>]) -> Result<ParsedReports, SondaError> {
+    let (header_row, columns) = find_header(table).ok_or_else(|| {
+        SondaError::ParseError(
+            "no Eurofins export header row (needs sample, parameter and result columns)".into(),
+        )
+    })?;
+
+    let mut samples: Vec<Sample> = Vec::new();
+    for row in &table[header_row + 1..] {
+        let parameter = columns.get(row, Column::Parameter);
+        if parameter.is_empty() {
+            continue;
+        }
+        let number = columns.get(row, Column::SampleNumber);
+        let label = columns.get(row, Column::SampleLabel);
+        let key = (
+            columns.get(row, Column::ReportId).to_string(),
+            if number.is_empty() { label } else { number }.to_string(),
+        );
+        let sample = match samples.iter().position(|s| s.key == key) {
+            Some(i) => &mut samples[i],
+            None => {
+                samples.push(Sample {
+                    key,
+                    header: ReportHeader {
+                        lab: Some("Eurofins".to_string()),
+                        ..Default::default()
+                    },
+                    rows: Vec::new(),
+                    skipped: Vec::new(),
+                });
+                samples.last_mut().expect("just pushed")
+            }
+        };
+        fill_header(&mut sample.header, &columns, row);
+
+        let line = row.join("; ");
+        match parse_row(&columns, row) {
+            Ok(Some(mut analysis)) => {
+                analysis.source_line = Some(line);
+                sample.rows.push(analysis);
+            }
+            Ok(None) => {}
+            Err(reason) => sample.skipped.push(SkippedLine {
+                line_text: line,
+                reason,
+                report_index: None,
+            }),
+        }
+    }
+
+    let mut parsed = ParsedReports {
+        reports: Vec::new(),
+        warnings: Vec::new(),
+        skipped_lines: Vec::new(),
+    };
+    for (index, sample) in samples.into_iter().enumerate() {
+        push_sample(
+            &mut parsed,
+            index,
+            sample.header,
+            sample.rows,
+            sample.skipped,
+            "no analysis values found for sample",
+        );
+    }
+
+    if parsed.reports.is_empty() {
+        return Err(SondaError::ParseError(
+            "no analysis values found in Eurofins export".into(),
+        ));
+    }
+    Ok(parsed)
+}
+
+/// Fill header fields still missing from one row. The customer's sample
+/// label is preferred over the lab's sample number, as in the PDF reports.
+fn fill_header(header: &mut ReportHeader, columns: &Columns, row: &[String]) {
+    let non_empty = |column| Some(columns.get(row, column)).filter(|s| !s.is_empty());
+    if header.sample_id.is_none() {
+        header.sample_id = non_empty(Column::SampleLabel)
+            .or_else(|| non_empty(Column::SampleNumber))
+            .map(str::to_string);
+    }
+    if header.lab_report_id.is_none() {
+        header.lab_report_id = non_empty(Column::ReportId).map(str::to_string);
+    }
+    if header.date.is_none() {
+        header.date = non_empty(Column::Date).and_then(Date::from_str_loose);
+    }
+    if header.received.is_none() {
+        header.received = non_empty(Column::Received).and_then(Date::from_str_loose);
+    }
+    if header.depth.is_none() {
+        header.depth = non_empty(Column::Depth).and_then(DepthInterval::from_str_loose);
+    }
+    if header.sampler.is_none() {
+        header.sampler = non_empty(Column::Sampler).map(str::to_string);
+    }
+    if header.location.is_none() {
+        header.location = non_empty(Column::Location).map(str::to_string);
+    }
+    if header.order_number.is_none() {
+        header.order_number = non_empty(Column::OrderNumber).map(str::to_string);
+    }
+    if header.coordinates.is_none() {
+        let coordinate = |column| non_empty(column)?.replace(',', ".").parse::<Decimal>().ok();
+        if let (Some(north), Some(east)) = (coordinate(Column::North), coordinate(Column::East)) {
+            header.coordinates = Some(Coordinates {
+                north,
+                east,
+                crs: None,
+            });
+        }
+    }
+    if header.project.is_none() {
+        header.project = non_empty(Column::Project).map(str::to_string);
+    }
+    if header.matrix.is_none() {
+        header.matrix = non_empty(Column::Matrix).and_then(Matrix::from_str_loose);
+    }
+}
+
+/// One result row. `Ok(None)` for rows without a result (not analysed).
+fn parse_row(columns: &Columns, row: &[String]) -> Result<Option<AnalysisRow>, String> {
+    let name = columns.get(row, Column::Parameter);
+    let result = columns.get(row, Column::Result);
+    let loq = match parse_value(columns.get(row, Column::Loq)) {
+        Ok(value) => value.map(|v| v.numeric()),
+        Err(e) => return Err(format!("invalid LOQ: {e}")),
+    };
+
+    let below_loq = columns.get(row, Column::Prefix).starts_with('<')
+        || BELOW_LOQ.contains(&result.to_lowercase().as_str());
+    let value = if below_loq {
+        match parse_value(result.trim_start_matches('<')) {
+            Ok(Some(value)) => AnalysisValue::BelowDetection(value.numeric()),
+            _ => match loq {
+                Some(loq) => AnalysisValue::BelowDetection(loq),
+                None => return Err("below LOQ but no LOQ given".into()),
+            },
+        }
+    } else {
+        match parse_value(result) {
+            Ok(Some(value)) => value,
+            Ok(None) => return Ok(None),
+            Err(e) => return Err(e.to_string()),
+        }
+    };
+
+    let uncertainty = match value {
+        AnalysisValue::Measured(v) => {
+            parse_export_uncertainty(columns.get(row, Column::Uncertainty), v)
+        }
+        _ => None,
+    };
+    let unit = columns.get(row, Column::Unit);
+
+    Ok(Some(AnalysisRow {
+        raw_name: name.to_string(),
+        normalized_name: normalize_substance(name),
+        value,
+        unit: if unit.is_empty() {
+            Unit::default()
+        } else {
+            Unit::from_str_loose(unit)
+        },
+        uncertainty,
+        loq,
+        source_line: None,
+    }))
+}
+
+/// Uncertainty cells are written "±20%", "20 %" or as an absolute "13".
+fn parse_export_uncertainty(cell: &str, value: Decimal) -> Option<Decimal> {
+    let cell = cell.trim().trim_start_matches('±').trim();
+    if cell.is_empty() {
+        return None;
+    }
+    parse_uncertainty(&format!("±{cell}"), value)
+}
+
+/// Read a CSV export. Eurofins writes `;`-separated Latin-1 files from
+/// Swedish Excel, so the delimiter is sniffed from the first line and
+/// invalid UTF-8 is decoded as Latin-1.
+fn read_csv(bytes: &[u8]) -> Result<Vec<Vec<String>>, SondaError> {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes.iter().map(|&b| b as char).collect(),
+    };
+    let first_line = text.lines().next().unwrap_or("");
+    let delimiter = [b';', b'\t', b',']
+        .into_iter()
+        .max_by_key(|&d| first_line.matches(d as char).count())
+        .unwrap_or(b';');
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    reader
+        .records()
+        .map(|record| {
+            record
+                .map(|r| r.iter().map(str::to_string).collect())
+                .map_err(|e| SondaError::ParseError(format!("failed to read csv: {e}")))
+        })
+        .collect()
+}
+
+/// Read the first sheet of an xlsx export as text cells.
+fn read_xlsx(bytes: &[u8]) -> Result<Vec<Vec<String>>, SondaError> {
+    let mut workbook: Xlsx<_> = calamine::open_workbook_from_rs(Cursor::new(bytes))
+        .map_err(|e| SondaError::ParseError(format!("failed to open xlsx: {e}")))?;
+    let sheet = workbook
+        .worksheet_range_at(0)
+        .ok_or_else(|| SondaError::ParseError("xlsx has no sheets".into()))?
+        .map_err(|e| SondaError::ParseError(format!("failed to read xlsx sheet: {e}")))?;
+    Ok(sheet
+        .rows()
+        .map(|row| row.iter().map(cell_text).collect())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const EXPORT: &str = "\
+Analysrapport;Provnummer;Provmärkning;Provtagningsdatum;Matris;Parameter;Resultat;Enhet;Rapporteringsgräns;Mätosäkerhet
+AR-24-SL-012345;177-2024-0001;P1 0-0,5;2024-05-14;Jord;Bly (Pb);120;mg/kg TS;1,0;±25%
+AR-24-SL-012345;177-2024-0001;P1 0-0,5;2024-05-14;Jord;Kadmium (Cd);<0,20;mg/kg TS;0,20;
+AR-24-SL-012345;177-2024-0002;P2 0,5-1;2024-05-14;Jord;Bly (Pb);18;mg/kg TS;1,0;4,5
+AR-24-SL-012345;177-2024-0002;P2 0,5-1;2024-05-14;Jord;Kadmium (Cd);<LOQ;mg/kg TS;0,20;
+AR-24-SL-012345;177-2024-0002;P2 0,5-1;2024-05-14;Jord;Koppar (Cu);se bilaga;mg/kg TS;;
+";
+
+    #[test]
+    fn test_csv_groups_rows_by_sample() {
+        let parsed = parse_eurofins_export(EXPORT.as_bytes()).unwrap();
+        assert_eq!(parsed.reports.len(), 2);
+
+        let p1 = &parsed.reports[0];
+        assert_eq!(p1.header.sample_id.as_deref(), Some("P1 0-0,5"));
+        assert_eq!(p1.header.lab_report_id.as_deref(), Some("AR-24-SL-012345"));
+        assert_eq!(p1.header.date, Date::new(2024, 5, 14));
+        assert_eq!(p1.header.matrix, Some(Matrix::Jord));
+        assert_eq!(p1.header.lab.as_deref(), Some("Eurofins"));
+        assert_eq!(p1.rows[0].normalized_name, "bly");
+        assert_eq!(p1.rows[0].value, AnalysisValue::Measured(dec!(120)));
+        assert_eq!(p1.rows[0].uncertainty, Some(dec!(30)));
+        assert_eq!(p1.rows[1].value, AnalysisValue::BelowDetection(dec!(0.20)));
+
+        let p2 = &parsed.reports[1];
+        assert_eq!(p2.rows[0].uncertainty, Some(dec!(4.5)));
+        assert_eq!(p2.rows[1].value, AnalysisValue::BelowDetection(dec!(0.20)));
+
+        assert_eq!(parsed.skipped_lines.len(), 1);
+        assert_eq!(parsed.skipped_lines[0].report_index, Some(1));
+    }
+
+    #[test]
+    fn test_comma_separated_latin1_with_prefix_column() {
+        // "G\xe5rd" is "Gård" in Latin-1.
+        let bytes = b"Sample ID,Parameter,Prefix,Result,Unit,LOQ (mg/kg)\n\
+G\xe5rd 1,Bly,,35,mg/kg TS,1\n\
+G\xe5rd 1,Kvicksilver,<,0.05,mg/kg TS,0.05\n";
+        let parsed = parse_eurofins_export(bytes).unwrap();
+        let report = &parsed.reports[0];
+        assert_eq!(report.header.sample_id.as_deref(), Some("Gård 1"));
+        assert_eq!(report.rows[0].value, AnalysisValue::Measured(dec!(35)));
+        // A measured value keeps the LOQ it was reported with.
+        assert_eq!(report.rows[0].loq, Some(dec!(1)));
+        assert_eq!(
+            report.rows[1].value,
+            AnalysisValue::BelowDetection(dec!(0.05))
+        );
+        assert_eq!(report.rows[1].loq, Some(dec!(0.05)));
+    }
+
+    #[test]
+    fn test_missing_columns_rejected() {
+        let err = parse_eurofins_export(b"Provnummer;Resultat\n1;2\n").unwrap_err();
+        assert!(err.to_string().contains("header row"));
+    }
+}
@@ -0,0 +1,687 @@
+//! Spreadsheet import driven by a column mapping.
+//!
+//! A mapping file says where a consultancy's compilation template keeps its
+//! data: the sheet, cells with header fields, the substance name column or
+//! row, and the value cells. Samples either run across columns (substances
+//! down the rows, one value column per sample) or down rows (one sample per
+//! row, substances across a header row).
+//!
+//! ```json
+//! {
+//!   "sheet": "Sammanställning",
+//!   "marker": { "cell": "A1", "contains": "AVFALLSKLASSNING@SWECO" },
+//!   "header": { "project": "B1", "date": "G3" },
+//!   "lab": "Sweco",
+//!   "matrix": "jord",
+//!   "samples": { "layout": "columns", "name_column": "A", "first_row": 17, "sample_id_row": 3 }
+//! }
+//! ```
+//!
+//! Rows are numbered from 1 and columns are letters, as in Excel.
+
+use std::io::Cursor;
+use std::path::Path;
+
+use calamine::{Data, Range, Reader, Xlsx};
+use serde::{Deserialize, Serialize};
+
+use crate::error::SondaError;
+use crate::extraction::cells::{cell_text, cell_value};
+use crate::model::{AnalysisRow, Date, DepthInterval, Matrix, ReportHeader, Unit};
+use crate::parsing::normalize::normalize_substance;
+use crate::parsing::{push_sample, ParsedReports, SkippedLine};
+
+/// Where a spreadsheet template keeps its samples and header fields.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ColumnMapping {
+    /// Sheet to read. Default: the first sheet.
+    #[serde(default)]
+    pub sheet: Option<String>,
+    /// A cell that must contain the given text, to reject other templates.
+    #[serde(default)]
+    pub marker: Option<Marker>,
+    /// Cells holding header fields shared by every sample.
+    #[serde(default)]
+    pub header: HeaderCells,
+    /// Laboratory or consultancy recorded on every sample.
+    #[serde(default)]
+    pub lab: Option<String>,
+    /// Matrix of samples whose matrix is not in the sheet.
+    #[serde(default)]
+    pub matrix: Option<Matrix>,
+    /// Unit of every value. Default: mg/kg TS.
+    #[serde(default)]
+    pub unit: Option<Unit>,
+    pub samples: SampleLayout,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Marker {
+    pub cell: String,
+    pub contains: String,
+}
+
+/// Cell references ("B2") of header fields shared by every sample.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HeaderCells {
+    #[serde(default)]
+    pub lab_report_id: Option<String>,
+    #[serde(default)]
+    pub sample_id: Option<String>,
+    #[serde(default)]
+    pub date: Option<String>,
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub matrix: Option<String>,
+    #[serde(default)]
+    pub received: Option<String>,
+    #[serde(default)]
+    pub depth: Option<String>,
+    #[serde(default)]
+    pub sampler: Option<String>,
+    #[serde(default)]
+    pub location: Option<String>,
+    #[serde(default)]
+    pub order_number: Option<String>,
+}
+
+/// How samples and substances are laid out.
+///
+/// Without a `last_row`, data ends at the first row whose name (or sample
+/// id) cell is empty. `value_columns` entries are letters or ranges
+/// ("B:K"); when empty, every column right of the name (or sample id)
+/// column with a sample id (or substance name) is read.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "layout", rename_all = "snake_case", deny_unknown_fields)]
+pub enum SampleLayout {
+    /// Substances down the rows, one sample per value column.
+    Columns {
+        name_column: String,
+        first_row: u32,
+        #[serde(default)]
+        last_row: Option<u32>,
+        #[serde(default)]
+        value_columns: Vec<String>,
+        /// Row holding each column's sample id.
+        #[serde(default)]
+        sample_id_row: Option<u32>,
+        #[serde(default)]
+        date_row: Option<u32>,
+        #[serde(default)]
+        matrix_row: Option<u32>,
+        #[serde(default)]
+        depth_row: Option<u32>,
+        #[serde(default)]
+        location_row: Option<u32>,
+        /// Column holding each substance's unit.
+        #[serde(default)]
+        unit_column: Option<String>,
+    },
+    /// One sample per row, substances across a header row.
+    Rows {
+        /// Row holding the substance names.
+        header_row: u32,
+        first_row: u32,
+        #[serde(default)]
+        last_row: Option<u32>,
+        sample_id_column: String,
+        #[serde(default)]
+        value_columns: Vec<String>,
+        #[serde(default)]
+        date_column: Option<String>,
+        #[serde(default)]
+        matrix_column: Option<String>,
+        #[serde(default)]
+        depth_column: Option<String>,
+        #[serde(default)]
+        location_column: Option<String>,
+    },
+}
+
+/// Load and validate a mapping file.
+pub fn load_mapping(path: &Path) -> Result<ColumnMapping, SondaError> {
+    let content = std::fs::read_to_string(path)?;
+    parse_mapping_str(&content).map_err(|e| match e {
+        SondaError::MappingInvalid(reason) => {
+            SondaError::MappingInvalid(format!("{}: {reason}", path.display()))
+        }
+        other => other,
+    })
+}
+
+/// Parse and validate a mapping from a JSON string.
+pub fn parse_mapping_str(json: &str) -> Result<ColumnMapping, SondaError> {
+    let mapping: ColumnMapping =
+        serde_json::from_str(json).map_err(|e| SondaError::MappingInvalid(e.to_string()))?;
+    validate_mapping(&mapping)?;
+    Ok(mapping)
+}
+
+/// Check that every cell reference, column and row number is well-formed.
+pub fn validate_mapping(mapping: &ColumnMapping) -> Result<(), SondaError> {
+    let header = &mapping.header;
+    for cell in [
+        &header.lab_report_id,
+        &header.sample_id,
+        &header.date,
+        &header.project,
+        &header.matrix,
+        &header.received,
+        &header.depth,
+        &header.sampler,
+        &header.location,
+        &header.order_number,
+    ]
+    .into_iter()
+    .flatten()
+    .chain(mapping.marker.as_ref().map(|m| &m.cell))
+    {
+        cell_ref(cell)?;
+    }
+
+    let (rows, columns, value_columns, first_row, last_row) = match &mapping.samples {
+        SampleLayout::Columns {
+            name_column,
+            first_row,
+            last_row,
+            value_columns,
+            sample_id_row,
+            date_row,
+            matrix_row,
+            depth_row,
+            location_row,
+            unit_column,
+        } => {
+            if value_columns.is_empty() && sample_id_row.is_none() {
+                return Err(SondaError::MappingInvalid(
+                    "the columns layout needs value_columns or sample_id_row".into(),
+                ));
+            }
+            let rows = [
+                Some(*first_row),
+                *sample_id_row,
+                *date_row,
+                *matrix_row,
+                *depth_row,
+                *location_row,
+            ];
+            let columns = [Some(name_column), unit_column.as_ref(), None, None, None];
+            (rows, columns, value_columns, first_row, last_row)
+        }
+        SampleLayout::Rows {
+            header_row,
+            first_row,
+            last_row,
+            sample_id_column,
+            value_columns,
+            date_column,
+            matrix_column,
+            depth_column,
+            location_column,
+        } => {
+            let rows = [Some(*header_row), Some(*first_row), None, None, None, None];
+            let columns = [
+                Some(sample_id_column),
+                date_column.as_ref(),
+                matrix_column.as_ref(),
+                depth_column.as_ref(),
+                location_column.as_ref(),
+            ];
+            (rows, columns, value_columns, first_row, last_row)
+        }
+    };
+    if rows.iter().flatten().any(|&row| row == 0) {
+        return Err(SondaError::MappingInvalid("row numbers start at 1".into()));
+    }
+    for column in columns.into_iter().flatten() {
+        column_index(column)?;
+    }
+    for spec in value_columns {
+        column_range(spec)?;
+    }
+    if last_row.is_some_and(|last| last < *first_row) {
+        return Err(SondaError::MappingInvalid(
+            "last_row is before first_row".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Read an xlsx workbook with a column mapping.
+pub fn parse_mapped_xlsx(
+    bytes: &[u8],
+    mapping: &ColumnMapping,
+) -> Result<ParsedReports, SondaError> {
+    let mut workbook: Xlsx<_> = calamine::open_workbook_from_rs(Cursor::new(bytes))
+        .map_err(|e| SondaError::ParseError(format!("failed to open xlsx: {e}")))?;
+    let sheet = match &mapping.sheet {
+        Some(name) => workbook
+            .worksheet_range(name)
+            .map_err(|e| SondaError::ParseError(format!("sheet '{name}' not found: {e}")))?,
+        None => workbook
+            .worksheet_range_at(0)
+            .ok_or_else(|| SondaError::ParseError("xlsx has no sheets".into()))?
+            .map_err(|e| SondaError::ParseError(format!("failed to read xlsx sheet: {e}")))?,
+    };
+    parse_range(&sheet, mapping)
+}
+
+/// One sample's header and the cells its values are read from.
+struct SampleCells {
+    header: ReportHeader,
+    /// (substance name, unit, value cell) per substance.
+    cells: Vec<(String, Unit, (u32, u32))>,
+}
+
+/// Read a sheet already in memory with a column mapping.
+pub fn parse_range(
+    sheet: &Range<Data>,
+    mapping: &ColumnMapping,
+) -> Result<ParsedReports, SondaError> {
+    validate_mapping(mapping)?;
+    let text = |(row, col): (u32, u32)| {
+        sheet
+            .get_value((row, col))
+            .map(cell_text)
+            .unwrap_or_default()
+    };
+    let non_empty = |pos| Some(text(pos)).filter(|s| !s.is_empty());
+    let header_cell =
+        |cell: &Option<String>| cell.as_deref().and_then(|c| non_empty(cell_ref(c).ok()?));
+
+    if let Some(marker) = &mapping.marker {
+        if !text(cell_ref(&marker.cell)?).contains(&marker.contains) {
+            return Err(SondaError::ParseError(format!(
+                "cell {} does not contain '{}'",
+                marker.cell, marker.contains
+            )));
+        }
+    }
+
+    let shared = ReportHeader {
+        lab_report_id: header_cell(&mapping.header.lab_report_id),
+        sample_id: header_cell(&mapping.header.sample_id),
+        date: header_cell(&mapping.header.date).and_then(|d| Date::from_str_loose(&d)),
+        project: header_cell(&mapping.header.project),
+        matrix: header_cell(&mapping.header.matrix)
+            .and_then(|m| Matrix::from_str_loose(&m))
+            .or(mapping.matrix),
+        lab: mapping.lab.clone(),
+        received: header_cell(&mapping.header.received).and_then(|d| Date::from_str_loose(&d)),
+        depth: header_cell(&mapping.header.depth).and_then(|d| DepthInterval::from_str_loose(&d)),
+        sampler: header_cell(&mapping.header.sampler),
+        location: header_cell(&mapping.header.location),
+        order_number: header_cell(&mapping.header.order_number),
+        ..Default::default()
+    };
+    let unit = mapping.unit.unwrap_or_default();
+    let width = sheet.end().map_or(0, |(_, col)| col + 1);
+    let height = sheet.end().map_or(0, |(row, _)| row + 1);
+
+    let samples: Vec<SampleCells> = match &mapping.samples {
+        SampleLayout::Columns {
+            name_column,
+            first_row,
+            last_row,
+            value_columns,
+            sample_id_row,
+            date_row,
+            matrix_row,
+            depth_row,
+            location_row,
+            unit_column,
+        } => {
+            let name_col = column_index(name_column)?;
+            let unit_col = unit_column.as_deref().map(column_index).transpose()?;
+            let row_cell = |row: &Option<u32>, col| row.and_then(|r| non_empty((r - 1, col)));
+            let columns = match value_columns.is_empty() {
+                false => expand_columns(value_columns)?,
+                true => (name_col + 1..width)
+                    .filter(|&col| row_cell(sample_id_row, col).is_some())
+                    .collect(),
+            };
+            let substances: Vec<(String, Unit, u32)> =
+                data_rows(*first_row, *last_row, height, |row| {
+                    non_empty((row, name_col))
+                })
+                .map(|(row, name)| {
+                    let unit = unit_col
+                        .and_then(|col| non_empty((row, col)))
+                        .map_or(unit, |u| Unit::from_str_loose(&u));
+                    (name, unit, row)
+                })
+                .collect();
+            columns
+                .into_iter()
+                .map(|col| SampleCells {
+                    header: ReportHeader {
+                        sample_id: row_cell(sample_id_row, col)
+                            .or_else(|| shared.sample_id.clone())
+                            .or_else(|| Some(format!("column {}", column_name(col)))),
+                        date: row_cell(date_row, col)
+                            .and_then(|d| Date::from_str_loose(&d))
+                            .or(shared.date),
+                        matrix: row_cell(matrix_row, col)
+                            .and_then(|m| Matrix::from_str_loose(&m))
+                            .or(shared.matrix),
+                        depth: row_cell(depth_row, col)
+                            .and_then(|d| DepthInterval::from_str_loose(&d))
+                            .or_else(|| shared.depth.clone()),
+                        location: row_cell(location_row, col).or_else(|| shared.location.clone()),
+                        ..shared.clone()
+                    },
+                    cells: substances
+                        .iter()
+                        .map(|(name, unit, row)| (name.clone(), *unit, (*row, col)))
+                        .collect(),
+                })
+                .collect()
+        }
+        SampleLayout::Rows {
+            header_row,
+            first_row,
+            last_row,
+            sample_id_column,
+            value_columns,
+            date_column,
+            matrix_column,
+            depth_column,
+            location_column,
+        } => {
+            let id_col = column_index(sample_id_column)?;
+            let date_col = date_column.as_deref().map(column_index).transpose()?;
+            let matrix_col = matrix_column.as_deref().map(column_index).transpose()?;
+            let depth_col = depth_column.as_deref().map(column_index).transpose()?;
+            let location_col = location_column.as_deref().map(column_index).transpose()?;
+            let metadata_cols = [date_col, matrix_col, depth_col, location_col];
+            let name_row = header_row - 1;
+            let columns: Vec<u32> = match value_columns.is_empty() {
+                false => expand_columns(value_columns)?,
+                true => (id_col + 1..width)
+                    .filter(|&col| !metadata_cols.contains(&Some(col)))
+                    .collect(),
+            };
+            let substances: Vec<(String, u32)> = columns
+                .into_iter()
+                .filter_map(|col| non_empty((name_row, col)).map(|name| (name, col)))
+                .collect();
+            data_rows(*first_row, *last_row, height, |row| {
+                non_empty((row, id_col))
+            })
+            .map(|(row, sample_id)| SampleCells {
+                header: ReportHeader {
+                    sample_id: Some(sample_id),
+                    date: date_col
+                        .and_then(|col| non_empty((row, col)))
+                        .and_then(|d| Date::from_str_loose(&d))
+                        .or(shared.date),
+                    matrix: matrix_col
+                        .and_then(|col| non_empty((row, col)))
+                        .and_then(|m| Matrix::from_str_loose(&m))
+                        .or(shared.matrix),
+                    depth: depth_col
+                        .and_then(|col| non_empty((row, col)))
+                        .and_then(|d| DepthInterval::from_str_loose(&d))
+                        .or_else(|| shared.depth.clone()),
+                    location: location_col
+                        .and_then(|col| non_empty((row, col)))
+                        .or_else(|| shared.location.clone()),
+                    ..shared.clone()
+                },
+                cells: substances
+                    .iter()
+                    .map(|(name, col)| (name.clone(), unit, (row, *col)))
+                    .collect(),
+            })
+            .collect()
+        }
+    };
+
+    let mut parsed = ParsedReports {
+        reports: Vec::new(),
+        warnings: Vec::new(),
+        skipped_lines: Vec::new(),
+    };
+    for (index, sample) in samples.into_iter().enumerate() {
+        let sample_id = sample.header.sample_id.clone().unwrap_or_default();
+        let mut rows = Vec::new();
+        let mut skipped = Vec::new();
+        for (name, unit, pos) in sample.cells {
+            let Some(cell) = sheet.get_value(pos) else {
+                continue;
+            };
+            match cell_value(cell) {
+                Ok(Some(value)) => rows.push(AnalysisRow {
+                    normalized_name: normalize_substance(&name),
+                    raw_name: name,
+                    value,
+                    unit,
+                    uncertainty: None,
+                    loq: None,
+                    source_line: None,
+                }),
+                Ok(None) => {}
+                Err(reason) => skipped.push(SkippedLine {
+                    line_text: format!("{sample_id}: {name}: {}", cell_text(cell)),
+                    reason,
+                    report_index: None,
+                }),
+            }
+        }
+
+        push_sample(
+            &mut parsed,
+            index,
+            sample.header,
+            rows,
+            skipped,
+            "no substance values for sample",
+        );
+    }
+
+    if parsed.reports.is_empty() {
+        return Err(SondaError::ParseError(
+            "no substance data found with the column mapping".into(),
+        ));
+    }
+    Ok(parsed)
+}
+
+/// Data rows from `first_row` (1-based) with their key cell, up to
+/// `last_row` or, without one, up to the first empty key cell.
+fn data_rows<'a>(
+    first_row: u32,
+    last_row: Option<u32>,
+    height: u32,
+    key: impl Fn(u32) -> Option<String> + 'a,
+) -> impl Iterator<Item = (u32, String)> + 'a {
+    let end = last_row.unwrap_or(height).min(height);
+    let rows = (first_row - 1..end).map(move |row| (row, key(row)));
+    let bounded = last_row.is_some();
+    rows.take_while(move |(_, k)| bounded || k.is_some())
+        .filter_map(|(row, k)| Some((row, k?)))
+}
+
+/// Zero-based (row, column) of a cell reference like "G3".
+fn cell_ref(cell: &str) -> Result<(u32, u32), SondaError> {
+    let cell = cell.trim();
+    let split = cell
+        .find(|c: char| c.is_ascii_digit())
+        .ok_or_else(|| invalid_cell(cell))?;
+    let (letters, digits) = cell.split_at(split);
+    let row: u32 = digits.parse().map_err(|_| invalid_cell(cell))?;
+    if row == 0 || letters.is_empty() {
+        return Err(invalid_cell(cell));
+    }
+    Ok((row - 1, column_index(letters)?))
+}
+
+fn invalid_cell(cell: &str) -> SondaError {
+    SondaError::MappingInvalid(format!("invalid cell reference '{cell}'"))
+}
+
+/// Zero-based index of a column letter ("A" = 0, "AA" = 26).
+fn column_index(column: &str) -> Result<u32, SondaError> {
+    let column = column.trim();
+    if column.is_empty() || !column.chars().all(|c| c.is_ascii_alphabetic()) || column.len() > 3 {
+        return Err(SondaError::MappingInvalid(format!(
+            "invalid column '{column}'"
+        )));
+    }
+    Ok(column
+        .to_ascii_uppercase()
+        .bytes()
+        .fold(0, |acc, b| acc * 26 + u32::from(b - b'A' + 1))
+        - 1)
+}
+
+fn column_name(mut index: u32) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'A' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8(name).expect("ASCII letters")
+}
+
+/// Zero-based columns of a "B" or "B:K" entry.
+fn column_range(spec: &str) -> Result<std::ops::RangeInclusive<u32>, SondaError> {
+    let (from, to) = spec.split_once(':').unwrap_or((spec, spec));
+    let (from, to) = (column_index(from)?, column_index(to)?);
+    if from > to {
+        return Err(SondaError::MappingInvalid(format!(
+            "column range '{spec}' runs backwards"
+        )));
+    }
+    Ok(from..=to)
+}
+
+fn expand_columns(specs: &[String]) -> Result<Vec<u32>, SondaError> {
+    let mut columns = Vec::new();
+    for spec in specs {
+        columns.extend(column_range(spec)?);
+    }
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::AnalysisValue;
+    use rust_decimal_macros::dec;
+
+    fn sheet(rows: &[&[&str]]) -> Range<Data> {
+        let width = rows.iter().map(|r| r.len()).max().unwrap_or(1) as u32;
+        let mut range = Range::new((0, 0), (rows.len() as u32 - 1, width - 1));
+        for (r, row) in rows.iter().enumerate() {
+            for (c, cell) in row.iter().enumerate() {
+                let value = match cell.parse::<f64>() {
+                    Ok(f) => Data::Float(f),
+                    Err(_) if cell.is_empty() => Data::Empty,
+                    Err(_) => Data::String(cell.to_string()),
+                };
+                range.set_value((r as u32, c as u32), value);
+            }
+        }
+        range
+    }
+
+    #[test]
+    fn test_wide_sheet_one_sample_per_column() {
+        let mapping = parse_mapping_str(
+            r#"{
+                "header": { "project": "B1" },
+                "matrix": "jord",
+                "samples": { "layout": "columns", "name_column": "A", "first_row": 4, "sample_id_row": 2, "date_row": 3 }
+            }"#,
+        )
+        .unwrap();
+        let range = sheet(&[
+            &["Projekt", "Kv. Eken"],
+            &["Prov", "S1", "S2", "S3"],
+            &["Datum", "2024-05-02", "2024-05-02", "2024-05-03"],
+            &["Bly", "35", "<5,0", "210"],
+            &["Kadmium", "0.3", "-", "se bilaga"],
+            &[],
+            &["Kommentar", "x"],
+        ]);
+        let parsed = parse_range(&range, &mapping).unwrap();
+        assert_eq!(parsed.reports.len(), 3);
+
+        let s2 = &parsed.reports[1];
+        assert_eq!(s2.header.sample_id.as_deref(), Some("S2"));
+        assert_eq!(s2.header.project.as_deref(), Some("Kv. Eken"));
+        assert_eq!(s2.header.matrix, Some(Matrix::Jord));
+        assert_eq!(s2.rows.len(), 1);
+        assert_eq!(s2.rows[0].value, AnalysisValue::BelowDetection(dec!(5.0)));
+
+        let s3 = &parsed.reports[2];
+        assert_eq!(s3.header.date, Date::new(2024, 5, 3));
+        assert_eq!(parsed.skipped_lines.len(), 1);
+        assert_eq!(parsed.skipped_lines[0].report_index, Some(2));
+    }
+
+    #[test]
+    fn test_samples_down_rows() {
+        let mapping = parse_mapping_str(
+            r#"{
+                "samples": { "layout": "rows", "header_row": 1, "first_row": 2, "sample_id_column": "A", "matrix_column": "B" }
+            }"#,
+        )
+        .unwrap();
+        let range = sheet(&[
+            &["Prov", "Matris", "Bly (Pb)", "Zink (Zn)"],
+            &["P1", "Jord", "120", "<10"],
+            &["P2", "Asfalt", "14", "95"],
+        ]);
+        let parsed = parse_range(&range, &mapping).unwrap();
+        assert_eq!(parsed.reports.len(), 2);
+        assert_eq!(parsed.reports[1].header.matrix, Some(Matrix::Asfalt));
+        assert_eq!(parsed.reports[0].rows[0].normalized_name, "bly");
+        assert_eq!(
+            parsed.reports[0].rows[1].value,
+            AnalysisValue::BelowDetection(dec!(10))
+        );
+    }
+
+    #[test]
+    fn test_marker_and_invalid_mappings() {
+        let mapping = parse_mapping_str(
+            r#"{
+                "marker": { "cell": "A1", "contains": "MALL" },
+                "samples": { "layout": "columns", "name_column": "A", "first_row": 2, "value_columns": ["B"] }
+            }"#,
+        )
+        .unwrap();
+        let err = parse_range(&sheet(&[&["Annan"], &["Bly", "1"]]), &mapping).unwrap_err();
+        assert!(err.to_string().contains("does not contain 'MALL'"));
+
+        for json in [
+            r#"{ "samples": { "layout": "columns", "name_column": "A", "first_row": 2 } }"#,
+            r#"{ "samples": { "layout": "columns", "name_column": "1", "first_row": 2, "sample_id_row": 1 } }"#,
+            r#"{ "header": { "date": "G" }, "samples": { "layout": "rows", "header_row": 1, "first_row": 2, "sample_id_column": "A" } }"#,
+            r#"{ "samples": { "layout": "diagonal" } }"#,
+        ] {
+            assert!(matches!(
+                parse_mapping_str(json),
+                Err(SondaError::MappingInvalid(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_column_letters() {
+        assert_eq!(cell_ref("G3").unwrap(), (2, 6));
+        assert_eq!(column_index("AA").unwrap(), 26);
+        assert_eq!(column_name(26), "AA");
+        assert_eq!(column_range("B:D").unwrap(), 1..=3);
+    }
+}
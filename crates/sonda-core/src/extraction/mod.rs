@@ -1,3 +1,9 @@
+mod cells;
+pub mod eurofins_export;
+pub mod mapping;
+#[cfg(feature = "ocr")]
+pub mod ocr;
+#[cfg(feature = "pdftotext")]
 pub mod pdftotext;
 pub mod sweco_xlsx;
 pub mod table;
@@ -18,8 +24,13 @@ pub struct LineSpan {
     pub line_index: usize,
     pub text: String,
     pub bbox: BBox,
+    /// Recognition confidence (0–100) of OCR'd lines; `None` for a text layer.
+    pub confidence: Option<f32>,
 }
 
+/// OCR'd lines below this confidence (0–100) become trace warnings.
+pub const LOW_CONFIDENCE: f32 = 60.0;
+
 /// Content extracted from a single page of a PDF.
 #[derive(Debug, Clone)]
 pub struct PageContent {
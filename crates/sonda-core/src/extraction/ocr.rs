@@ -0,0 +1,322 @@
+use crate::error::SondaError;
+use crate::extraction::{BBox, LineSpan, PageContent, PdfExtractor};
+use std::process::Command;
+
+/// OCR backend for scanned PDFs without a text layer.
+///
+/// Rasterises each page with `pdftoppm` (poppler-utils) and recognises it
+/// with `tesseract`. Words are joined into lines with wide gaps kept as runs
+/// of spaces, like `pdftotext -layout`, so the table parsers can split
+/// columns. Line boxes are in PDF points, as from pdftotext.
+pub struct OcrExtractor {
+    language: String,
+    dpi: u32,
+}
+
+impl OcrExtractor {
+    /// Swedish language data at 300 dpi.
+    pub fn new() -> Self {
+        OcrExtractor {
+            language: "swe".to_string(),
+            dpi: 300,
+        }
+    }
+
+    /// Use other tesseract language data, e.g. "swe+eng".
+    pub fn with_language(mut self, language: &str) -> Self {
+        self.language = language.to_string();
+        self
+    }
+
+    /// Check if tesseract is available on the system.
+    pub fn is_available() -> bool {
+        Command::new("tesseract")
+            .arg("--version")
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+}
+
+impl Default for OcrExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PdfExtractor for OcrExtractor {
+    fn extract_pages(&self, pdf_bytes: &[u8]) -> Result<Vec<PageContent>, SondaError> {
+        let dir = tempfile::tempdir().map_err(|e| SondaError::Extraction(e.to_string()))?;
+        let pdf_path = dir.path().join("input.pdf");
+        std::fs::write(&pdf_path, pdf_bytes).map_err(|e| SondaError::Extraction(e.to_string()))?;
+
+        // Rasterise every page to page-1.png, page-2.png, ...
+        let output = Command::new("pdftoppm")
+            .arg("-r")
+            .arg(self.dpi.to_string())
+            .arg("-png")
+            .arg(&pdf_path)
+            .arg(dir.path().join("page"))
+            .output()
+            .map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    SondaError::PdftoppmNotFound
+                } else {
+                    SondaError::Extraction(format!("pdftoppm failed: {}", e))
+                }
+            })?;
+        if !output.status.success() {
+            return Err(SondaError::Extraction(format!(
+                "pdftoppm failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        // pdftoppm zero-pads page numbers to the same width, so names sort.
+        let mut images: Vec<_> = std::fs::read_dir(dir.path())
+            .map_err(|e| SondaError::Extraction(e.to_string()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == "png"))
+            .collect();
+        images.sort();
+
+        let scale = 72.0 / self.dpi as f32;
+        images
+            .iter()
+            .enumerate()
+            .map(|(i, image)| {
+                let output = Command::new("tesseract")
+                    .arg(image)
+                    .arg("stdout")
+                    .arg("-l")
+                    .arg(&self.language)
+                    // One uniform block, so table rows stay on one line.
+                    .arg("--psm")
+                    .arg("6")
+                    .arg("tsv")
+                    .output()
+                    .map_err(|e| {
+                        if e.kind() == std::io::ErrorKind::NotFound {
+                            SondaError::TesseractNotFound
+                        } else {
+                            SondaError::Extraction(format!("tesseract failed: {}", e))
+                        }
+                    })?;
+                if !output.status.success() {
+                    return Err(SondaError::Extraction(format!(
+                        "tesseract failed: {}",
+                        String::from_utf8_lossy(&output.stderr)
+                    )));
+                }
+                Ok(parse_tsv(
+                    &String::from_utf8_lossy(&output.stdout),
+                    i + 1,
+                    scale,
+                ))
+            })
+            .collect()
+    }
+
+    fn backend_name(&self) -> &str {
+        "tesseract"
+    }
+}
+
+/// Reads the text layer, and OCRs the document when the text layer is empty.
+pub struct AutoExtractor {
+    text: Box<dyn PdfExtractor>,
+    ocr: Box<dyn PdfExtractor>,
+}
+
+impl AutoExtractor {
+    pub fn new(text: Box<dyn PdfExtractor>, ocr: Box<dyn PdfExtractor>) -> Self {
+        AutoExtractor { text, ocr }
+    }
+}
+
+/// pdftotext, falling back to [`OcrExtractor`].
+#[cfg(feature = "pdftotext")]
+impl Default for AutoExtractor {
+    fn default() -> Self {
+        Self::new(
+            Box::new(crate::extraction::pdftotext::PdftotextExtractor::new()),
+            Box::new(OcrExtractor::new()),
+        )
+    }
+}
+
+impl PdfExtractor for AutoExtractor {
+    fn extract_pages(&self, pdf_bytes: &[u8]) -> Result<Vec<PageContent>, SondaError> {
+        let pages = self.text.extract_pages(pdf_bytes)?;
+        let has_text = pages
+            .iter()
+            .any(|p| p.lines.iter().any(|l| !l.trim().is_empty()));
+        if has_text {
+            Ok(pages)
+        } else {
+            self.ocr.extract_pages(pdf_bytes)
+        }
+    }
+
+    fn backend_name(&self) -> &str {
+        "auto"
+    }
+}
+
+/// One recognised word from tesseract's TSV output.
+struct Word {
+    left: f32,
+    top: f32,
+    right: f32,
+    bottom: f32,
+    confidence: f32,
+    text: String,
+}
+
+/// Build a page from tesseract TSV output. Pixel coordinates are multiplied
+/// by `scale` to get PDF points.
+fn parse_tsv(tsv: &str, page_number: usize, scale: f32) -> PageContent {
+    // Words grouped by (block, paragraph, line), in reading order.
+    let mut lines: Vec<((u32, u32, u32), Vec<Word>)> = Vec::new();
+    for row in tsv.lines().skip(1) {
+        let fields: Vec<&str> = row.split('\t').collect();
+        // level page block par line word left top width height conf text
+        if fields.len() < 12 || fields[0] != "5" {
+            continue;
+        }
+        let text = fields[11].trim();
+        if text.is_empty() {
+            continue;
+        }
+        let num = |i: usize| fields[i].parse::<f32>().unwrap_or(0.0);
+        let key = (num(2) as u32, num(3) as u32, num(4) as u32);
+        let word = Word {
+            left: num(6),
+            top: num(7),
+            right: num(6) + num(8),
+            bottom: num(7) + num(9),
+            confidence: num(10),
+            text: text.to_string(),
+        };
+        match lines.last_mut() {
+            Some((last, words)) if *last == key => words.push(word),
+            _ => lines.push((key, vec![word])),
+        }
+    }
+
+    let line_spans: Vec<LineSpan> = lines
+        .iter()
+        .enumerate()
+        .map(|(line_index, (_, words))| {
+            let confidences: Vec<f32> = words
+                .iter()
+                .map(|w| w.confidence)
+                .filter(|c| *c >= 0.0)
+                .collect();
+            LineSpan {
+                page_number,
+                line_index,
+                text: layout_line(words),
+                bbox: BBox {
+                    x_min: words.iter().map(|w| w.left).fold(f32::MAX, f32::min) * scale,
+                    y_min: words.iter().map(|w| w.top).fold(f32::MAX, f32::min) * scale,
+                    x_max: words.iter().map(|w| w.right).fold(0.0, f32::max) * scale,
+                    y_max: words.iter().map(|w| w.bottom).fold(0.0, f32::max) * scale,
+                },
+                confidence: (!confidences.is_empty())
+                    .then(|| confidences.iter().sum::<f32>() / confidences.len() as f32),
+            }
+        })
+        .collect();
+
+    PageContent {
+        page_number,
+        lines: line_spans.iter().map(|s| s.text.clone()).collect(),
+        line_spans,
+    }
+}
+
+/// Join words with one space, or with a run of spaces as wide as the gap
+/// (at least two) where the gap is wider than a character and a half.
+fn layout_line(words: &[Word]) -> String {
+    let chars: usize = words.iter().map(|w| w.text.chars().count()).sum();
+    let width: f32 = words.iter().map(|w| w.right - w.left).sum();
+    let char_width = (width / chars.max(1) as f32).max(1.0);
+
+    let mut line = String::new();
+    for (i, word) in words.iter().enumerate() {
+        if i > 0 {
+            let gap = word.left - words[i - 1].right;
+            let spaces = if gap > 1.5 * char_width {
+                ((gap / char_width).round() as usize).max(2)
+            } else {
+                1
+            };
+            line.push_str(&" ".repeat(spaces));
+        }
+        line.push_str(&word.text);
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TSV: &str = "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext
+1\t1\t0\t0\t0\t0\t0\t0\t2480\t3508\t-1\t
+4\t1\t1\t1\t1\t0\t200\t300\t1400\t40\t-1\t
+5\t1\t1\t1\t1\t1\t200\t300\t100\t40\t96.0\tBly
+5\t1\t1\t1\t1\t2\t310\t300\t80\t40\t90.0\t(Pb)
+5\t1\t1\t1\t1\t3\t900\t300\t60\t40\t88.0\t120
+5\t1\t1\t1\t1\t4\t1300\t300\t300\t40\t86.0\tmg/kg TS
+5\t1\t1\t1\t2\t1\t200\t360\t200\t40\t31.0\tKv1cks1lver
+";
+
+    #[test]
+    fn test_tsv_lines_keep_column_gaps() {
+        let page = parse_tsv(TSV, 1, 72.0 / 300.0);
+        assert_eq!(page.lines.len(), 2);
+        let segments: Vec<&str> = page.lines[0]
+            .split("  ")
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect();
+        assert_eq!(segments, ["Bly (Pb)", "120", "mg/kg TS"]);
+
+        let span = &page.line_spans[0];
+        assert_eq!(span.confidence, Some(90.0));
+        assert_eq!(span.bbox.x_min, 48.0);
+        assert_eq!(span.bbox.y_max, 81.6);
+        assert_eq!(page.line_spans[1].confidence, Some(31.0));
+    }
+
+    struct Fixed(Vec<&'static str>);
+
+    impl PdfExtractor for Fixed {
+        fn extract_pages(&self, _pdf_bytes: &[u8]) -> Result<Vec<PageContent>, SondaError> {
+            Ok(vec![PageContent {
+                page_number: 1,
+                lines: self.0.iter().map(|l| l.to_string()).collect(),
+                line_spans: vec![],
+            }])
+        }
+
+        fn backend_name(&self) -> &str {
+            "fixed"
+        }
+    }
+
+    #[test]
+    fn test_auto_falls_back_to_ocr_without_text_layer() {
+        let scanned = AutoExtractor::new(
+            Box::new(Fixed(vec!["", "  "])),
+            Box::new(Fixed(vec!["ocr"])),
+        );
+        assert_eq!(scanned.extract_pages(b"").unwrap()[0].lines, ["ocr"]);
+
+        let digital =
+            AutoExtractor::new(Box::new(Fixed(vec!["text"])), Box::new(Fixed(vec!["ocr"])));
+        assert_eq!(digital.extract_pages(b"").unwrap()[0].lines, ["text"]);
+    }
+}
@@ -79,6 +79,7 @@ impl PdfExtractor for PdftotextExtractor {
                         line_index,
                         text: b.text.clone(),
                         bbox: b.bbox.clone(),
+                        confidence: None,
                     })
                     .collect();
                 PageContent {
@@ -267,6 +268,7 @@ mod tests {
                 line_index,
                 text: b.text.clone(),
                 bbox: b.bbox.clone(),
+                confidence: None,
             })
             .collect();
         assert_eq!(spans.len(), 1);
@@ -1,12 +1,36 @@
 use std::io::Cursor;
 
-use calamine::{Reader, Xlsx};
-use rust_decimal::Decimal;
+use calamine::{Data, Range, Reader, Xlsx};
 
 use crate::error::SondaError;
-use crate::model::{AnalysisReport, AnalysisRow, AnalysisValue, Matrix, ReportHeader, Unit};
+use crate::extraction::cells::{cell_text, cell_value};
+use crate::model::{AnalysisRow, Date, Matrix, ReportHeader, Unit};
+use crate::parsing::header::read_sampling_fields;
 use crate::parsing::normalize::normalize_substance;
-use crate::parsing::ParsedReports;
+use crate::parsing::{push_sample, ParsedReports, SkippedLine};
+
+/// Row of the substance table header (row 16), with the sample name above
+/// each value column.
+const TABLE_HEADER_ROW: u32 = 15;
+
+/// First substance row (row 17).
+const FIRST_DATA_ROW: u32 = 16;
+
+/// Table header cells of columns that hold no sample values.
+const NON_SAMPLE_HEADERS: &[&str] = &[
+    "enhet",
+    "unit",
+    "km",
+    "mkm",
+    "fa",
+    "ifa",
+    "riktvärde",
+    "gränsvärde",
+    "kommentar",
+];
+
+/// Labels of the cell (or the cell to its right) giving the matrix.
+const MATRIX_LABELS: &[&str] = &["matris", "provtyp", "material"];
 
 /// Parse a Sweco "AVFALLSKLASSNING@SWECO" xlsx file into structured reports.
 ///
@@ -21,9 +45,22 @@ pub fn parse_sweco_xlsx(bytes: &[u8]) -> Result<ParsedReports, SondaError> {
         .worksheet_range("Sammanställning")
         .map_err(|e| SondaError::ParseError(format!("sheet 'Sammanställning' not found: {e}")))?;
 
+    parse_sheet(&sheet)
+}
+
+/// Parse the "Sammanställning" sheet. Substance names run down column A
+/// from row 17; every column to the right with a sample name in row 16 or
+/// values below it is one sample.
+fn parse_sheet(sheet: &Range<Data>) -> Result<ParsedReports, SondaError> {
+    let text = |row: u32, col: u32| {
+        sheet
+            .get_value((row, col))
+            .map(cell_text)
+            .filter(|s| !s.is_empty())
+    };
+
     // Verify format marker in row 1 (0-indexed row 0)
-    let marker = sheet.get_value((0, 0)).and_then(cell_as_string);
-    match marker {
+    match text(0, 0) {
         Some(ref s) if s.contains("AVFALLSKLASSNING@SWECO") => {}
         _ => {
             return Err(SondaError::ParseError(
@@ -33,117 +70,228 @@ pub fn parse_sweco_xlsx(bytes: &[u8]) -> Result<ParsedReports, SondaError> {
     }
 
     // Row 3 (0-indexed row 2): col A = sample name, col G = date
-    let sample_id = sheet.get_value((2, 0)).and_then(cell_as_string);
-    let date = sheet.get_value((2, 6)).and_then(cell_as_string);
-
-    // Parse substance rows starting at row 17 (0-indexed row 16) until first empty row
-    let mut rows = Vec::new();
-    let mut skipped_lines = Vec::new();
-    let mut row_idx: u32 = 16;
-
-    loop {
-        let name_cell = sheet.get_value((row_idx, 0));
-        let raw_name = match name_cell.and_then(cell_as_string) {
-            Some(n) if !n.is_empty() => n,
-            _ => break, // Empty row = end of data
-        };
-
-        let value_cell = sheet.get_value((row_idx, 1));
-        match cell_as_f64(value_cell) {
-            Some(f) => {
-                let decimal = f64_to_decimal(f);
-                let normalized = normalize_substance(&raw_name);
-                rows.push(AnalysisRow {
-                    raw_name,
-                    normalized_name: normalized,
-                    value: AnalysisValue::Measured(decimal),
-                    unit: Unit::MgPerKgTs,
-                });
-            }
-            None => {
-                let cell_text = value_cell.map(|c| format!("{c}")).unwrap_or_default();
-                if !cell_text.is_empty() {
-                    skipped_lines.push(crate::parsing::SkippedLine {
-                        line_text: format!("{raw_name}: {cell_text}"),
-                        reason: "non-numeric value in xlsx".into(),
-                    });
-                }
-            }
-        }
-
-        row_idx += 1;
+    let sheet_sample_id = text(2, 0);
+    let mut shared = ReportHeader {
+        lab: Some("Sweco".into()),
+        matrix: Some(find_matrix(sheet).unwrap_or(Matrix::Jord)),
+        date: text(2, 6).and_then(|d| Date::from_str_loose(&d)),
+        ..Default::default()
+    };
+    // Labelled fields above the table ("Provtagare", "Djup", ...), read
+    // like a report header line with the row's cells as columns.
+    let width = sheet.end().map_or(0, |(_, col)| col + 1);
+    for row in 0..TABLE_HEADER_ROW {
+        let line: Vec<String> = (0..width).filter_map(|col| text(row, col)).collect();
+        read_sampling_fields(&line.join("   "), &mut shared);
     }
 
-    if rows.is_empty() {
+    // Substance rows run until the first empty name cell.
+    let substances: Vec<(u32, String)> = (FIRST_DATA_ROW..)
+        .map_while(|row| text(row, 0).map(|name| (row, name)))
+        .collect();
+    if substances.is_empty() {
         return Err(SondaError::ParseError(
             "no substance data found in xlsx".into(),
         ));
     }
 
-    let header = ReportHeader {
-        lab: Some("Sweco".into()),
-        sample_id,
-        matrix: Some(Matrix::Jord),
-        date,
-        ..Default::default()
-    };
+    // Sample columns run from B until the first column with neither a
+    // sample name nor any value.
+    let columns: Vec<(u32, Option<String>)> = (1..)
+        .map(|col| (col, text(TABLE_HEADER_ROW, col)))
+        .take_while(|(col, name)| {
+            name.is_some() || substances.iter().any(|(row, _)| text(*row, *col).is_some())
+        })
+        .filter(|(_, name)| {
+            !name
+                .as_deref()
+                .is_some_and(|n| NON_SAMPLE_HEADERS.contains(&n.to_lowercase().as_str()))
+        })
+        .collect();
 
-    Ok(ParsedReports {
-        reports: vec![AnalysisReport { header, rows }],
-        warnings: vec![],
-        skipped_lines,
-    })
-}
+    let mut parsed = ParsedReports {
+        reports: Vec::new(),
+        warnings: Vec::new(),
+        skipped_lines: Vec::new(),
+    };
+    let single = columns.len() == 1;
+    for (index, (col, column_name)) in columns.into_iter().enumerate() {
+        // One sample keeps the sheet's sample name; several are named by
+        // their column headers.
+        let sample_id = if single {
+            sheet_sample_id.clone().or(column_name)
+        } else {
+            column_name.or_else(|| {
+                Some(format!(
+                    "{} {}",
+                    sheet_sample_id.as_deref().unwrap_or("Prov"),
+                    index + 1
+                ))
+            })
+        };
 
-fn cell_as_string(cell: &calamine::Data) -> Option<String> {
-    match cell {
-        calamine::Data::String(s) => {
-            let trimmed = s.trim();
-            if trimmed.is_empty() {
-                None
-            } else {
-                Some(trimmed.to_string())
+        let mut rows = Vec::new();
+        let mut skipped = Vec::new();
+        for (row, raw_name) in &substances {
+            let Some(cell) = sheet.get_value((*row, col)) else {
+                continue;
+            };
+            match cell_value(cell) {
+                Ok(Some(value)) => rows.push(AnalysisRow {
+                    raw_name: raw_name.clone(),
+                    normalized_name: normalize_substance(raw_name),
+                    value,
+                    unit: Unit::MgPerKgTs,
+                    uncertainty: None,
+                    loq: None,
+                    source_line: None,
+                }),
+                Ok(None) => {}
+                Err(_) => skipped.push(SkippedLine {
+                    line_text: format!("{raw_name}: {}", cell_text(cell)),
+                    reason: "non-numeric value in xlsx".into(),
+                    report_index: None,
+                }),
             }
         }
-        calamine::Data::Float(f) => Some(f.to_string()),
-        calamine::Data::Int(i) => Some(i.to_string()),
-        calamine::Data::DateTime(dt) => Some(dt.to_string()),
-        calamine::Data::Empty => None,
-        _ => Some(format!("{cell}")),
+
+        let header = ReportHeader {
+            sample_id,
+            ..shared.clone()
+        };
+        push_sample(
+            &mut parsed,
+            index,
+            header,
+            rows,
+            skipped,
+            "no substance values in sample column",
+        );
     }
-}
 
-fn cell_as_f64(cell: Option<&calamine::Data>) -> Option<f64> {
-    match cell? {
-        calamine::Data::Float(f) => Some(*f),
-        calamine::Data::Int(i) => Some(*i as f64),
-        _ => None,
+    if parsed.reports.is_empty() {
+        return Err(SondaError::ParseError(
+            "no substance data found in xlsx".into(),
+        ));
     }
+    Ok(parsed)
 }
 
-/// Convert f64 to Decimal, preserving reasonable precision.
-///
-/// Uses string round-trip to avoid floating-point artifacts
-/// (e.g., 0.0035_f64 becoming 0.00349999...).
-fn f64_to_decimal(f: f64) -> Decimal {
-    // Format with enough precision to capture the original value,
-    // then parse as Decimal.
-    let s = format!("{f}");
-    s.parse::<Decimal>().unwrap_or_else(|_| {
-        // Fallback: use from_f64_retain
-        Decimal::try_from(f).unwrap_or_default()
+/// The matrix from a "Matris: Asfalt" cell above the table, or from the
+/// cell to the right of a "Matris" label.
+fn find_matrix(sheet: &Range<Data>) -> Option<Matrix> {
+    let width = sheet.end().map_or(0, |(_, col)| col + 1);
+    (0..TABLE_HEADER_ROW).find_map(|row| {
+        (0..width).find_map(|col| {
+            let label = cell_text(sheet.get_value((row, col))?).to_lowercase();
+            if !MATRIX_LABELS.iter().any(|l| label.starts_with(l)) {
+                return None;
+            }
+            match label.split_once(':') {
+                Some((_, value)) if !value.trim().is_empty() => Matrix::from_str_loose(value),
+                _ => (col + 1..width)
+                    .map(|c| sheet.get_value((row, c)).map(cell_text).unwrap_or_default())
+                    .find(|s| !s.is_empty())
+                    .and_then(|s| Matrix::from_str_loose(&s)),
+            }
+        })
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::AnalysisValue;
     use rust_decimal_macros::dec;
 
+    /// A "Sammanställning" sheet with the given cells, as (row, col, text).
+    /// Numeric text becomes a number cell.
+    fn sheet(cells: &[(u32, u32, &str)]) -> Range<Data> {
+        let mut range = Range::new((0, 0), (30, 10));
+        range.set_value((0, 0), Data::String("AVFALLSKLASSNING@SWECO".into()));
+        for &(row, col, text) in cells {
+            let value = match text.parse::<f64>() {
+                Ok(f) => Data::Float(f),
+                Err(_) => Data::String(text.into()),
+            };
+            range.set_value((row, col), value);
+        }
+        range
+    }
+
+    #[test]
+    fn test_single_sample_keeps_sheet_name() {
+        let parsed = parse_sheet(&sheet(&[
+            (2, 0, "S1 0-0,5"),
+            (2, 6, "2024-04-11"),
+            (4, 0, "Provtagare"),
+            (4, 1, "Anna Berg"),
+            (5, 0, "Djup"),
+            (5, 1, "0-0,5 m"),
+            (16, 0, "Bly"),
+            (16, 1, "68"),
+            (17, 0, "Kadmium"),
+            (17, 1, "<0,03"),
+        ]))
+        .unwrap();
+        assert_eq!(parsed.reports.len(), 1);
+        let report = &parsed.reports[0];
+        assert_eq!(report.header.sample_id.as_deref(), Some("S1 0-0,5"));
+        assert_eq!(report.header.date, Date::new(2024, 4, 11));
+        assert_eq!(report.header.matrix, Some(Matrix::Jord));
+        assert_eq!(report.header.sampler.as_deref(), Some("Anna Berg"));
+        assert_eq!(report.header.depth.as_ref().unwrap().to.to_string(), "0.5");
+        assert_eq!(
+            report.rows[1].value,
+            AnalysisValue::BelowDetection(dec!(0.03))
+        );
+        assert!(parsed.skipped_lines.is_empty());
+    }
+
+    #[test]
+    fn test_every_sample_column_is_a_report() {
+        let parsed = parse_sheet(&sheet(&[
+            (4, 0, "Matris"),
+            (4, 1, "Asfalt"),
+            (15, 0, "Ämne"),
+            (15, 1, "A1"),
+            (15, 2, "A2"),
+            (15, 3, "A3"),
+            (15, 4, "Enhet"),
+            (16, 0, "PAH-16"),
+            (16, 1, "12"),
+            (16, 2, "340"),
+            (16, 3, "ej analyserad"),
+            (16, 4, "mg/kg TS"),
+            (17, 0, "Bens(a)pyren"),
+            (17, 1, "<0,5"),
+            (17, 2, "25"),
+            (17, 4, "mg/kg TS"),
+        ]))
+        .unwrap();
+        let ids: Vec<_> = parsed
+            .reports
+            .iter()
+            .map(|r| r.header.sample_id.as_deref().unwrap())
+            .collect();
+        assert_eq!(ids, ["A1", "A2"]);
+        assert_eq!(parsed.reports[1].header.matrix, Some(Matrix::Asfalt));
+        assert_eq!(
+            parsed.reports[1].rows[1].value,
+            AnalysisValue::Measured(dec!(25))
+        );
+
+        // A3 only has an unreadable cell.
+        assert_eq!(parsed.warnings.len(), 1);
+        assert_eq!(parsed.warnings[0].sample_id.as_deref(), Some("A3"));
+        assert_eq!(parsed.skipped_lines.len(), 1);
+        assert_eq!(parsed.skipped_lines[0].report_index, None);
+    }
+
     #[test]
-    fn f64_to_decimal_preserves_precision() {
-        assert_eq!(f64_to_decimal(0.0035), dec!(0.0035));
-        assert_eq!(f64_to_decimal(68.0), dec!(68));
-        assert_eq!(f64_to_decimal(1.23), dec!(1.23));
+    fn test_missing_marker_rejected() {
+        let mut range = sheet(&[(16, 0, "Bly"), (16, 1, "68")]);
+        range.set_value((0, 0), Data::String("Något annat".into()));
+        assert!(parse_sheet(&range).is_err());
     }
 }
@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::error::SondaError;
+
+const EN_JSON: &str = include_str!("../../../locales/en.json");
+const SV_JSON: &str = include_str!("../../../locales/sv.json");
+
+/// Language of reason strings, trace messages and CLI output.
+///
+/// Category names (e.g., "KM", "FA") and ruleset names are identifiers and
+/// are never translated.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Sv,
+}
+
+/// Available locales.
+pub const LOCALES: &[Locale] = &[Locale::En, Locale::Sv];
+
+type Catalogue = HashMap<String, String>;
+
+impl Locale {
+    /// Look up a message. Falls back to English, then to the key itself.
+    pub fn text(self, key: &str) -> &str {
+        catalogue(self)
+            .get(key)
+            .or_else(|| catalogue(Locale::En).get(key))
+            .map_or(key, String::as_str)
+    }
+
+    /// Look up a message and substitute `{name}` placeholders.
+    pub fn format(self, key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        let mut msg = self.text(key).to_string();
+        for (name, value) in args {
+            msg = msg.replace(&format!("{{{name}}}"), &value.to_string());
+        }
+        msg
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Locale::En => write!(f, "en"),
+            Locale::Sv => write!(f, "sv"),
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = SondaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "en" | "english" => Ok(Locale::En),
+            "sv" | "svenska" | "swedish" => Ok(Locale::Sv),
+            _ => Err(SondaError::InvalidInput(format!(
+                "unknown language '{}'. Available: en, sv",
+                s
+            ))),
+        }
+    }
+}
+
+fn catalogue(locale: Locale) -> &'static Catalogue {
+    static EN: OnceLock<Catalogue> = OnceLock::new();
+    static SV: OnceLock<Catalogue> = OnceLock::new();
+    let (cell, json) = match locale {
+        Locale::En => (&EN, EN_JSON),
+        Locale::Sv => (&SV, SV_JSON),
+    };
+    cell.get_or_init(|| serde_json::from_str(json).expect("embedded message catalogue"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeSet, HashSet};
+
+    fn placeholders(msg: &str) -> BTreeSet<&str> {
+        msg.split('{')
+            .skip(1)
+            .filter_map(|s| s.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn catalogues_have_same_keys_and_placeholders() {
+        let en = catalogue(Locale::En);
+        let sv = catalogue(Locale::Sv);
+        let en_keys: BTreeSet<_> = en.keys().collect();
+        let sv_keys: BTreeSet<_> = sv.keys().collect();
+        assert_eq!(en_keys, sv_keys);
+
+        for (key, msg) in en {
+            assert_eq!(placeholders(msg), placeholders(&sv[key]), "key '{key}'");
+        }
+    }
+
+    #[test]
+    fn format_substitutes_placeholders() {
+        let msg = Locale::Sv.format(
+            "overall.single",
+            &[("substance", &"Bly (Pb)"), ("category", &"MKM")],
+        );
+        assert!(msg.contains("Bly (Pb)") && msg.contains("MKM"));
+        assert!(!msg.contains('{'));
+        assert_eq!("sv".parse::<Locale>().unwrap(), Locale::Sv);
+        assert!("de".parse::<Locale>().is_err());
+    }
+
+    /// String literals in the Rust sources under `dir`.
+    fn literals(dir: &std::path::Path, out: &mut Vec<String>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                literals(&path, out);
+            } else if path.extension().is_some_and(|e| e == "rs") {
+                let src = std::fs::read_to_string(&path).unwrap();
+                out.extend(src.split('"').skip(1).step_by(2).map(String::from));
+            }
+        }
+    }
+
+    #[test]
+    fn every_key_in_use_exists() {
+        let en = catalogue(Locale::En);
+        let sv = catalogue(Locale::Sv);
+        let prefixes: HashSet<&str> = en.keys().filter_map(|k| k.split('.').next()).collect();
+        let is_key = |s: &str| {
+            s.split_once('.').is_some_and(|(prefix, rest)| {
+                prefixes.contains(prefix)
+                    && rest.split('.').all(|p| {
+                        !p.is_empty() && p.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                    })
+            })
+        };
+
+        let crates = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let mut keys = Vec::new();
+        for krate in ["sonda-core", "sonda-cli", "sonda-py"] {
+            literals(&crates.join(krate).join("src"), &mut keys);
+        }
+        keys.retain(|k| is_key(k));
+        assert!(keys.len() > 100, "found only {} keys", keys.len());
+
+        // Keys built at runtime.
+        use crate::trace::TraceStepType::*;
+        for step in [
+            ParseValue,
+            NormalizeSubstance,
+            ThresholdCompare,
+            OverallDecision,
+            HpCriterion,
+            ManualAdjustment,
+        ] {
+            let name = serde_json::to_value(step).unwrap();
+            keys.push(format!("trace.step.{}", name.as_str().unwrap()));
+        }
+        for id in [
+            "HP4", "HP5", "HP6", "HP7", "HP8", "HP10", "HP11", "HP13", "HP14",
+        ] {
+            keys.push(format!("hp.{id}.name"));
+        }
+
+        for key in &keys {
+            assert!(en.contains_key(key), "'{key}' missing from en.json");
+            assert!(sv.contains_key(key), "'{key}' missing from sv.json");
+        }
+    }
+
+    #[test]
+    fn missing_key_falls_back_to_key() {
+        assert_eq!(Locale::Sv.text("test.no_such_key"), "test.no_such_key");
+        let key = format!("trace.step.{}", "no_such_step");
+        assert_eq!(Locale::En.text(&key), "trace.step.no_such_step");
+    }
+}
@@ -0,0 +1,143 @@
+use crate::error::SondaError;
+use crate::extraction::eurofins_export::is_eurofins_export_xlsx;
+use crate::extraction::PdfExtractor;
+use crate::model::{AnalysisReport, Date};
+use crate::parsing::{ParseWarning, ParseWarningKind, ParsedReports};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Supported input file formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputFormat {
+    /// Lab report PDF.
+    Pdf,
+    /// Sweco "AVFALLSKLASSNING@SWECO" workbook.
+    SwecoXlsx,
+    /// Eurofins CSV or XLSX export, one row per result.
+    EurofinsExport,
+    /// Pre-parsed reports (top-level array of `AnalysisReport`).
+    ReportsJson,
+}
+
+impl InputFormat {
+    /// Detect the input format from the file extension, falling back to
+    /// content sniffing for extension-less JSON files. Workbooks are Sweco
+    /// files unless their first sheet has a Eurofins export header row.
+    pub fn detect(path: &Path, bytes: &[u8]) -> InputFormat {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase());
+        match ext.as_deref() {
+            Some("xlsx") if is_eurofins_export_xlsx(bytes) => InputFormat::EurofinsExport,
+            Some("xlsx") => InputFormat::SwecoXlsx,
+            Some("csv") => InputFormat::EurofinsExport,
+            Some("json") => InputFormat::ReportsJson,
+            _ if looks_like_json(bytes) => InputFormat::ReportsJson,
+            _ => InputFormat::Pdf,
+        }
+    }
+}
+
+/// Parse input bytes of a known format into structured reports.
+pub fn parse_input(
+    format: InputFormat,
+    bytes: &[u8],
+    extractor: &dyn PdfExtractor,
+) -> Result<ParsedReports, SondaError> {
+    match format {
+        InputFormat::Pdf => crate::parse_pdf(bytes, extractor),
+        InputFormat::SwecoXlsx => crate::parse_sweco_xlsx(bytes),
+        InputFormat::EurofinsExport => crate::parse_eurofins_export(bytes),
+        InputFormat::ReportsJson => parse_reports_json(bytes),
+    }
+}
+
+/// Read pre-parsed reports (a top-level array of `AnalysisReport`). A header
+/// date that isn't a date reads as missing, with a warning quoting it.
+pub fn parse_reports_json(bytes: &[u8]) -> Result<ParsedReports, SondaError> {
+    let reports: Vec<AnalysisReport> = serde_json::from_slice(bytes)?;
+    let raw: Vec<serde_json::Value> = serde_json::from_slice(bytes)?;
+    let warnings = raw
+        .iter()
+        .zip(&reports)
+        .enumerate()
+        .flat_map(|(i, (raw, report))| {
+            ["date", "received"].into_iter().filter_map(move |field| {
+                let text = raw["header"][field].as_str()?;
+                Date::from_str_loose(text).is_none().then(|| ParseWarning {
+                    section_index: i + 1,
+                    sample_id: report.header.sample_id.clone(),
+                    reason: text.to_string(),
+                    kind: ParseWarningKind::UnreadableDate,
+                })
+            })
+        })
+        .collect();
+    Ok(ParsedReports {
+        reports,
+        warnings,
+        skipped_lines: vec![],
+    })
+}
+
+fn looks_like_json(bytes: &[u8]) -> bool {
+    bytes
+        .iter()
+        .copied()
+        .find(|b| !b.is_ascii_whitespace())
+        .map(|b| b == b'[' || b == b'{')
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_by_extension() {
+        assert_eq!(
+            InputFormat::detect(Path::new("a.XLSX"), b""),
+            InputFormat::SwecoXlsx
+        );
+        assert_eq!(
+            InputFormat::detect(Path::new("export.csv"), b""),
+            InputFormat::EurofinsExport
+        );
+        assert_eq!(
+            InputFormat::detect(Path::new("a.json"), b""),
+            InputFormat::ReportsJson
+        );
+        assert_eq!(
+            InputFormat::detect(Path::new("a.pdf"), b"%PDF-1.7"),
+            InputFormat::Pdf
+        );
+    }
+
+    #[test]
+    fn test_json_warns_about_unreadable_dates() {
+        let json = br#"[
+            {"header": {"sample_id": "P1", "date": "maj 2024", "received": "2024-05-06"}, "rows": []},
+            {"header": {"sample_id": "P2", "date": null}, "rows": []}
+        ]"#;
+        let parsed = parse_reports_json(json).unwrap();
+        assert_eq!(parsed.reports[0].header.date, None);
+        assert_eq!(parsed.reports[0].header.received, Date::new(2024, 5, 6));
+        assert_eq!(parsed.warnings.len(), 1);
+        let warning = &parsed.warnings[0];
+        assert_eq!(warning.sample_id.as_deref(), Some("P1"));
+        assert_eq!(warning.kind, ParseWarningKind::UnreadableDate);
+        assert!(warning
+            .message(crate::i18n::Locale::En)
+            .contains("'maj 2024'"));
+    }
+
+    #[test]
+    fn test_detect_extensionless_json() {
+        assert_eq!(
+            InputFormat::detect(Path::new("parsed"), b"  [ {} ]"),
+            InputFormat::ReportsJson
+        );
+    }
+}
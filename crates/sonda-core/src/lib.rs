@@ -1,15 +1,27 @@
+pub mod adjust;
 pub mod classify;
 pub mod clp;
+pub mod disposal;
 pub mod error;
 pub mod extraction;
+pub mod i18n;
+pub mod input;
 pub mod model;
 pub mod parsing;
+pub mod project;
+pub mod request;
+pub mod revision;
 pub mod rules;
 pub mod trace;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
-use classify::outcome::{ClassificationResult, SampleResult};
+use adjust::{AdjustmentAction, Adjustments, ManualAdjustment};
+use classify::outcome::{ClassificationResult, ParseWarning, SampleResult};
+use classify::sensitivity::Variation;
 use error::SondaError;
 use extraction::PdfExtractor;
+use i18n::Locale;
 use model::{AnalysisReport, Matrix};
 use parsing::ParsedReports;
 use rules::schema::RuleSetDef;
@@ -19,6 +31,16 @@ use rules::schema::RuleSetDef;
 pub struct ClassifyOptions {
     /// Run HP-based hazardous waste (FA) classification.
     pub include_hp: bool,
+    /// Language of reason strings, HP criterion names and trace messages.
+    pub locale: Locale,
+    /// Vary measured values and report which could change the overall category.
+    pub sensitivity: Option<Variation>,
+    /// Manual value overrides and exclusions applied before classification.
+    pub adjustments: Adjustments,
+    /// The adjustments also cover other inputs of a batch, so a sample they
+    /// name may be missing from this one. The caller then checks for unknown
+    /// samples across the batch instead.
+    pub adjustments_span_inputs: bool,
 }
 
 /// Parse a Sweco "AVFALLSKLASSNING@SWECO" xlsx file into structured reports.
@@ -29,6 +51,21 @@ pub fn parse_sweco_xlsx(xlsx_bytes: &[u8]) -> Result<ParsedReports, SondaError>
     extraction::sweco_xlsx::parse_sweco_xlsx(xlsx_bytes)
 }
 
+/// Parse a Eurofins CSV or XLSX export (one row per result) into structured
+/// reports, one per sample.
+pub fn parse_eurofins_export(bytes: &[u8]) -> Result<ParsedReports, SondaError> {
+    extraction::eurofins_export::parse_eurofins_export(bytes)
+}
+
+/// Parse an xlsx workbook laid out as described by a column mapping, one
+/// report per sample.
+pub fn parse_mapped_xlsx(
+    xlsx_bytes: &[u8],
+    mapping: &extraction::mapping::ColumnMapping,
+) -> Result<ParsedReports, SondaError> {
+    extraction::mapping::parse_mapped_xlsx(xlsx_bytes, mapping)
+}
+
 /// Parse a PDF report into structured analysis reports without classifying.
 ///
 /// Returns the intermediate `ParsedReports` which can be serialized to JSON,
@@ -55,18 +92,27 @@ pub fn classify_reports(
             "no analysis reports to classify".into(),
         ));
     }
+    options.adjustments.validate()?;
 
     let mut samples = Vec::new();
-    let trace = trace::TraceBundle::default();
+    let mut trace = trace::TraceBundle::default();
+    let mut warnings = Vec::new();
+    let mut adjustments = Vec::new();
 
     for report in reports {
-        let sample_result = classify_sample(report, rulesets, options)?;
+        let report = adjust_report(report, options, &mut warnings, &mut adjustments)?;
+        let sample_result = classify_sample(&report, rulesets, options)?;
+        // No PDF pages here, so entries carry no evidence spans.
+        trace_sample(&mut trace, &report, &sample_result, &[], options.locale);
         samples.push(sample_result);
     }
+    trace_adjustments(&mut trace, &adjustments, options.locale);
+    warn_unknown_samples(reports, options, &mut warnings, &mut trace);
 
     Ok(ClassificationResult {
         samples,
-        warnings: vec![],
+        warnings,
+        adjustments,
         trace,
     })
 }
@@ -81,106 +127,241 @@ pub fn classify_pdf(
     rulesets: &[RuleSetDef],
     options: &ClassifyOptions,
 ) -> Result<ClassificationResult, SondaError> {
+    options.adjustments.validate()?;
+
     // Extract text from PDF
     let pages = extractor.extract_pages(pdf_bytes)?;
 
-    // Parse into one or more reports (one per sample)
+    // Detect the lab and parse into one or more reports (one per sample)
     let parsed = parsing::parse_reports(&pages)?;
     let reports = parsed.reports;
     let mut trace = trace::TraceBundle::default();
 
-    // Check for supported lab (check the first report)
-    if reports
-        .first()
-        .and_then(|r| r.header.lab.as_ref())
-        .is_none()
-    {
-        return Err(SondaError::UnsupportedReport(
-            "could not detect laboratory. Look for 'Eurofins' in the report header".into(),
-        ));
-    }
-
     // Classify each sample
     let mut samples = Vec::new();
+    let mut adjustment_warnings = Vec::new();
+    let mut adjustments = Vec::new();
     for report in &reports {
-        let sample_result = classify_sample(report, rulesets, options)?;
-
-        for (entry_idx, row) in report.rows.iter().enumerate() {
-            trace.entries.push(trace::build_entry_trace(
-                &sample_result.sample_id,
-                entry_idx,
-                row,
-                &row.unit.to_string(),
-                &pages,
-            ));
-        }
-
-        for (rs_idx, rs) in sample_result.ruleset_results.iter().enumerate() {
-            trace.decisions.extend(trace::build_ruleset_decisions(
-                &sample_result.sample_id,
-                rs_idx,
-                rs,
-            ));
-        }
-
+        let report = adjust_report(report, options, &mut adjustment_warnings, &mut adjustments)?;
+        let sample_result = classify_sample(&report, rulesets, options)?;
+        trace_sample(&mut trace, &report, &sample_result, &pages, options.locale);
         samples.push(sample_result);
     }
+    trace_adjustments(&mut trace, &adjustments, options.locale);
+    warn_unknown_samples(&reports, options, &mut adjustment_warnings, &mut trace);
 
     // Surface skipped lines as trace warnings (Info/Auto — diagnostic, not critical)
     for skip in parsed.skipped_lines {
         trace.warnings.push(trace::TraceWarning {
             sample_id: None,
-            message: format!("Skipped line ({}): '{}'", skip.reason, skip.line_text),
+            message: options.locale.format(
+                "warning.skipped_line",
+                &[("reason", &skip.reason), ("line", &skip.line_text)],
+            ),
             severity: trace::TraceSeverity::Info,
             visibility: trace::TraceVisibility::Auto,
         });
     }
 
-    let warnings = parsed
+    let mut warnings: Vec<ParseWarning> = parsed
         .warnings
         .into_iter()
         .map(|w| {
-            let message = if let Some(ref id) = w.sample_id {
-                format!(
-                    "Skipped sample '{}' (section {}): {}",
-                    id, w.section_index, w.reason
-                )
-            } else {
-                format!("Skipped section {}: {}", w.section_index, w.reason)
+            let message = w.message(options.locale);
+            // Skipped sections always show; doubtful OCR lines are for review.
+            let visibility = match w.kind {
+                parsing::ParseWarningKind::SkippedSection
+                | parsing::ParseWarningKind::UnreadableDate => trace::TraceVisibility::Always,
+                parsing::ParseWarningKind::LowOcrConfidence { .. } => trace::TraceVisibility::Auto,
             };
             trace.warnings.push(trace::TraceWarning {
                 sample_id: w.sample_id.clone(),
                 message: message.clone(),
                 severity: trace::TraceSeverity::Important,
-                visibility: trace::TraceVisibility::Always,
+                visibility,
             });
-            classify::outcome::ParseWarning {
+            ParseWarning {
                 sample_id: w.sample_id,
                 message,
             }
         })
         .collect();
+    warnings.extend(adjustment_warnings);
 
     Ok(ClassificationResult {
         samples,
         warnings,
+        adjustments,
         trace,
     })
 }
 
-/// Classify a single sample report against applicable rulesets.
-fn classify_sample(
+/// Apply manual overrides and exclusions to a copy of `report`.
+///
+/// Applied changes are appended to `applied`; adjustments that matched no
+/// substance in this sample become warnings.
+fn adjust_report(
     report: &AnalysisReport,
-    rulesets: &[RuleSetDef],
     options: &ClassifyOptions,
-) -> Result<SampleResult, SondaError> {
-    // Build sample ID from header
-    let sample_id = report
+    warnings: &mut Vec<ParseWarning>,
+    applied: &mut Vec<ManualAdjustment>,
+) -> Result<AnalysisReport, SondaError> {
+    let mut report = report.clone();
+    if options.adjustments.is_empty() {
+        return Ok(report);
+    }
+    let sample_id = sample_id(&report);
+    let (changes, unmatched) = adjust::apply(&mut report, &sample_id, &options.adjustments);
+    applied.extend(changes);
+    warnings.extend(unmatched.into_iter().map(|u| ParseWarning {
+        message: options.locale.format(
+            "warning.adjustment_unmatched",
+            &[("substance", &u.substance), ("sample", &u.sample_id)],
+        ),
+        sample_id: Some(u.sample_id),
+    }));
+    Ok(report)
+}
+
+/// Warn about adjustments aimed at a sample none of `reports` names, so a
+/// mistyped sample ID doesn't silently drop an override from the audit trail.
+fn warn_unknown_samples(
+    reports: &[AnalysisReport],
+    options: &ClassifyOptions,
+    warnings: &mut Vec<ParseWarning>,
+    trace: &mut trace::TraceBundle,
+) {
+    if options.adjustments_span_inputs {
+        return;
+    }
+    let ids: Vec<&str> = reports
+        .iter()
+        .flat_map(|r| {
+            [
+                r.header.sample_id.as_deref(),
+                r.header.lab_report_id.as_deref(),
+            ]
+        })
+        .flatten()
+        .collect();
+    for u in options.adjustments.unknown_samples(&ids) {
+        let message = options.locale.format(
+            "warning.adjustment_unknown_sample",
+            &[("substance", &u.substance), ("sample", &u.sample_id)],
+        );
+        trace.warnings.push(trace::TraceWarning {
+            sample_id: None,
+            message: message.clone(),
+            severity: trace::TraceSeverity::Important,
+            visibility: trace::TraceVisibility::Always,
+        });
+        warnings.push(ParseWarning {
+            sample_id: Some(u.sample_id),
+            message,
+        });
+    }
+}
+
+/// Record manual adjustments: overrides as a step on the parsed entry,
+/// exclusions (which have no entry) as warnings.
+fn trace_adjustments(
+    trace: &mut trace::TraceBundle,
+    adjustments: &[ManualAdjustment],
+    locale: Locale,
+) {
+    let unknown = locale.text("trace.unknown_author").to_string();
+    for adj in adjustments {
+        let author = adj.author.as_ref().unwrap_or(&unknown);
+        match adj.action {
+            AdjustmentAction::Override => {
+                let value = adj
+                    .value
+                    .as_ref()
+                    .map(|v| v.to_string())
+                    .unwrap_or_default();
+                let step = trace::TraceStep {
+                    step_type: trace::TraceStepType::ManualAdjustment,
+                    message: locale.format(
+                        "trace.manual_override",
+                        &[
+                            ("original", &adj.original),
+                            ("value", &value),
+                            ("author", author),
+                            ("justification", &adj.justification),
+                        ],
+                    ),
+                    explanation: None,
+                };
+                trace
+                    .entries
+                    .iter_mut()
+                    .filter(|e| e.sample_id == adj.sample_id && e.normalized_name == adj.substance)
+                    .for_each(|e| e.steps.push(step.clone()));
+            }
+            AdjustmentAction::Exclude => trace.warnings.push(trace::TraceWarning {
+                sample_id: Some(adj.sample_id.clone()),
+                message: locale.format(
+                    "trace.manual_exclusion",
+                    &[
+                        ("substance", &adj.raw_name),
+                        ("original", &adj.original),
+                        ("author", author),
+                        ("justification", &adj.justification),
+                    ],
+                ),
+                severity: trace::TraceSeverity::Important,
+                visibility: trace::TraceVisibility::Always,
+            }),
+        }
+    }
+}
+
+/// Record parse entries and classification decisions for one sample.
+fn trace_sample(
+    trace: &mut trace::TraceBundle,
+    report: &AnalysisReport,
+    sample_result: &SampleResult,
+    pages: &[extraction::PageContent],
+    locale: Locale,
+) {
+    for (entry_idx, row) in report.rows.iter().enumerate() {
+        trace.entries.push(trace::build_entry_trace(
+            &sample_result.sample_id,
+            entry_idx,
+            row,
+            &row.unit.to_string(),
+            pages,
+            locale,
+        ));
+    }
+
+    for (rs_idx, rs) in sample_result.ruleset_results.iter().enumerate() {
+        trace.decisions.extend(trace::build_ruleset_decisions(
+            &sample_result.sample_id,
+            rs_idx,
+            rs,
+            locale,
+        ));
+    }
+}
+
+/// Sample ID from the header: Provmärkning, else the lab report ID.
+fn sample_id(report: &AnalysisReport) -> String {
+    report
         .header
         .sample_id
         .clone()
         .or_else(|| report.header.lab_report_id.clone())
-        .unwrap_or_else(|| "unknown".into());
+        .unwrap_or_else(|| "unknown".into())
+}
+
+/// Classify a single sample report against applicable rulesets.
+fn classify_sample(
+    report: &AnalysisReport,
+    rulesets: &[RuleSetDef],
+    options: &ClassifyOptions,
+) -> Result<SampleResult, SondaError> {
+    let sample_id = sample_id(report);
 
     let mut ruleset_results = Vec::new();
 
@@ -214,13 +395,34 @@ fn classify_sample(
         }
 
         let applicable_owned: Vec<RuleSetDef> = applicable.into_iter().cloned().collect();
-        let threshold_results = classify::classify(report, &applicable_owned);
+        let mut threshold_results = classify::classify(report, &applicable_owned, options.locale);
+        if let Some(variation) = options.sensitivity {
+            for (result, ruleset) in threshold_results.iter_mut().zip(&applicable_owned) {
+                result.sensitivity = Some(classify::sensitivity::analyze(
+                    report,
+                    result,
+                    variation,
+                    |varied| {
+                        classify::classify(varied, std::slice::from_ref(ruleset), options.locale)
+                            .remove(0)
+                    },
+                ));
+            }
+        }
         ruleset_results.extend(threshold_results);
     }
 
     // Run HP-based classification if requested
     if options.include_hp {
-        let hp_result = classify::hp_engine::classify_hp(report);
+        let mut hp_result = classify::hp_engine::classify_hp(report, options.locale);
+        if let Some(variation) = options.sensitivity {
+            hp_result.sensitivity = Some(classify::sensitivity::analyze(
+                report,
+                &hp_result,
+                variation,
+                |varied| classify::hp_engine::classify_hp(varied, options.locale),
+            ));
+        }
         ruleset_results.push(hp_result);
     }
 
@@ -236,6 +438,10 @@ fn classify_sample(
 
     Ok(SampleResult {
         sample_id,
+        lab_report_id: report.header.lab_report_id.clone(),
+        matrix: report.header.matrix,
+        revision: report.header.revision,
+        supersedes: report.header.supersedes.clone(),
         ruleset_results,
     })
 }
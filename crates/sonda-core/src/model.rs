@@ -102,6 +102,290 @@ pub struct AnalysisRow {
     pub normalized_name: String,
     pub value: AnalysisValue,
     pub unit: Unit,
+    /// Expanded measurement uncertainty (±, same unit as the value), if reported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uncertainty: Option<Decimal>,
+    /// Limit of quantification (same unit as the value), if reported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loq: Option<Decimal>,
+    /// Report line the row was parsed from, if it came from extracted text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_line: Option<String>,
+}
+
+/// A sampling point position in a projected coordinate system.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Coordinates {
+    pub north: Decimal,
+    pub east: Decimal,
+    /// Coordinate reference system (e.g., "SWEREF 99 TM").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crs: Option<String>,
+}
+
+impl Coordinates {
+    /// Read "N 6580123 E 674321", "N: 6580123, E: 674321" or the Swedish
+    /// "X 6580123 Y 674321" (X is northing), with an optional "SWEREF 99 TM"
+    /// or "RT 90" reference system anywhere in the text.
+    pub fn from_str_loose(s: &str) -> Option<Coordinates> {
+        let lower = s.to_lowercase();
+        let axis = |labels: &[&str]| {
+            labels.iter().find_map(|label| {
+                // A comma ends a value ("N 6580123, E 674321") unless it is
+                // a decimal comma.
+                let tokens: Vec<&str> = lower
+                    .split(|c: char| c.is_whitespace() || c == ':' || c == '=')
+                    .map(|t| t.trim_end_matches(','))
+                    .filter(|t| !t.is_empty())
+                    .collect();
+                let pos = tokens.iter().position(|t| t == label)?;
+                tokens
+                    .get(pos + 1)?
+                    .replace(',', ".")
+                    .parse::<Decimal>()
+                    .ok()
+            })
+        };
+        let north = axis(&["n", "x", "northing"])?;
+        let east = axis(&["e", "y", "easting", "ö"])?;
+        let compact = lower.replace(' ', "");
+        let crs = if compact.contains("sweref99tm") {
+            Some("SWEREF 99 TM".to_string())
+        } else if compact.contains("rt90") {
+            Some("RT 90".to_string())
+        } else {
+            None
+        };
+        Some(Coordinates { north, east, crs })
+    }
+}
+
+impl fmt::Display for Coordinates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "N {} E {}", self.north, self.east)?;
+        match &self.crs {
+            Some(crs) => write!(f, " ({crs})"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A calendar date, written as YYYY-MM-DD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// A date, if the day exists in that month.
+    pub fn new(year: u16, month: u8, day: u8) -> Option<Date> {
+        let leap =
+            (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400);
+        let days = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return None,
+        };
+        (1..=days)
+            .contains(&day)
+            .then_some(Date { year, month, day })
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// The date `days` after 1970-01-01, using Howard Hinnant's
+    /// `civil_from_days` algorithm. `None` outside the years a `Date` holds.
+    pub fn from_unix_days(days: i64) -> Option<Date> {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + i64::from(month <= 2);
+        Date::new(u16::try_from(year).ok()?, month as u8, day as u8)
+    }
+
+    /// The first date in the text: "2024-05-14", "2024/05/14", "20240514",
+    /// "14.05.2024" or "14/5 2024". A trailing time is ignored.
+    pub fn from_str_loose(s: &str) -> Option<Date> {
+        Self::all_in(s).into_iter().next()
+    }
+
+    /// Every date in the text, in order.
+    fn all_in(s: &str) -> Vec<Date> {
+        // Runs of digits with the single separator that follows each.
+        let mut parts: Vec<(u32, usize, Option<char>)> = Vec::new();
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            if !c.is_ascii_digit() {
+                continue;
+            }
+            let mut digits = c.to_string();
+            while let Some(d) = chars.peek().filter(|d| d.is_ascii_digit()) {
+                digits.push(*d);
+                chars.next();
+            }
+            let sep = chars
+                .peek()
+                .copied()
+                .filter(|c| matches!(c, '-' | '/' | '.' | ' '));
+            parts.push((digits.parse().unwrap_or(u32::MAX), digits.len(), sep));
+        }
+
+        let mut dates = Vec::new();
+        let mut i = 0;
+        while i < parts.len() {
+            let (value, len, sep) = parts[i];
+            // YYYYMMDD
+            if len == 8 {
+                if let Some(date) = Self::from_parts(value / 10_000, value / 100 % 100, value % 100)
+                {
+                    dates.push(date);
+                    i += 1;
+                    continue;
+                }
+            }
+            if let (Some(&(b, b_len, b_sep)), Some(&(c, c_len, _))) =
+                (parts.get(i + 1), parts.get(i + 2))
+            {
+                let joined = sep.is_some() && b_sep.is_some();
+                let date = if !joined || b_len > 2 {
+                    None
+                } else if len == 4 && c_len <= 2 {
+                    Self::from_parts(value, b, c)
+                } else if len <= 2 && c_len == 4 {
+                    Self::from_parts(c, b, value)
+                } else {
+                    None
+                };
+                if let Some(date) = date {
+                    dates.push(date);
+                    i += 3;
+                    continue;
+                }
+            }
+            i += 1;
+        }
+        dates
+    }
+
+    fn from_parts(year: u32, month: u32, day: u32) -> Option<Date> {
+        if !(1900..=2999).contains(&year) {
+            return None;
+        }
+        Date::new(
+            year as u16,
+            u8::try_from(month).ok()?,
+            u8::try_from(day).ok()?,
+        )
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Serialize for Date {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Date::from_str_loose(&s)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid date '{s}'")))
+    }
+}
+
+/// Read an optional header date. Text that isn't a date, like "maj 2024" in
+/// a hand-edited or older reports file, reads as no date rather than
+/// rejecting the whole file; `input::parse_reports_json` warns about it.
+fn lenient_date<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Date>, D::Error> {
+    let text = Option::<String>::deserialize(deserializer)?;
+    Ok(text.as_deref().and_then(Date::from_str_loose))
+}
+
+/// First and last day of a period, e.g. when a sample was analysed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateRange {
+    pub start: Date,
+    pub end: Date,
+}
+
+impl DateRange {
+    /// "2024-05-06 - 2024-05-14"; a single date is a one-day period.
+    pub fn from_str_loose(s: &str) -> Option<DateRange> {
+        let dates = Date::all_in(s);
+        let start = *dates.first()?;
+        let end = dates.get(1).copied().unwrap_or(start);
+        Some(DateRange { start, end })
+    }
+}
+
+impl fmt::Display for DateRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start)
+        } else {
+            write!(f, "{} – {}", self.start, self.end)
+        }
+    }
+}
+
+/// Sampling depth below ground surface, in metres.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepthInterval {
+    pub from: Decimal,
+    pub to: Decimal,
+}
+
+impl DepthInterval {
+    /// Read "0-0,5 m", "0,5–1,0", "0.5 - 1 m" or "50-100 cm".
+    pub fn from_str_loose(s: &str) -> Option<DepthInterval> {
+        let lower = s.trim().to_lowercase();
+        let scale = if lower.ends_with("cm") {
+            Decimal::new(1, 2)
+        } else {
+            Decimal::ONE
+        };
+        let number = lower.trim_end_matches(|c: char| c.is_alphabetic() || c.is_whitespace());
+        let (from, to) = number.split_once(['-', '–'])?;
+        let parse = |v: &str| v.trim().replace(',', ".").parse::<Decimal>().ok();
+        let (from, to) = (parse(from)? * scale, parse(to)? * scale);
+        (from <= to).then(|| DepthInterval {
+            from: from.normalize(),
+            to: to.normalize(),
+        })
+    }
+}
+
+impl fmt::Display for DepthInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}–{} m", self.from, self.to)
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -109,10 +393,40 @@ pub struct ReportHeader {
     pub lab_report_id: Option<String>,
     pub sample_id: Option<String>,
     pub matrix: Option<Matrix>,
-    pub date: Option<String>,
+    /// Sampling date.
+    #[serde(default, deserialize_with = "lenient_date")]
+    pub date: Option<Date>,
     pub project: Option<String>,
     /// Detected laboratory (e.g., "Eurofins").
     pub lab: Option<String>,
+    /// Date the sample arrived at the lab.
+    #[serde(
+        default,
+        deserialize_with = "lenient_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub received: Option<Date>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analysis_period: Option<DateRange>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<DepthInterval>,
+    /// Who took the sample.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampler: Option<String>,
+    /// Sampling point name (e.g., "Provgrop 3").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coordinates: Option<Coordinates>,
+    /// The client's order or purchase number.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order_number: Option<String>,
+    /// Revision of a reissued report ("Revision 2").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<u32>,
+    /// Lab report ID of the report this one replaces ("Ersätter rapport ...").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supersedes: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -120,3 +434,91 @@ pub struct AnalysisReport {
     pub header: ReportHeader,
     pub rows: Vec<AnalysisRow>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_date_formats() {
+        let date = Date::new(2024, 5, 14);
+        for text in [
+            "2024-05-14",
+            "2024/05/14",
+            "20240514",
+            "14.05.2024",
+            "14/5 2024",
+            "2024-05-14 10:30",
+        ] {
+            assert_eq!(Date::from_str_loose(text), date, "{text}");
+        }
+        assert_eq!(Date::from_str_loose("2024-02-30"), None);
+        assert_eq!(Date::from_str_loose("Provnummer 177-0042"), None);
+        assert_eq!(date.unwrap().to_string(), "2024-05-14");
+    }
+
+    #[test]
+    fn test_date_from_unix_days() {
+        assert_eq!(Date::from_unix_days(0), Date::new(1970, 1, 1));
+        assert_eq!(Date::from_unix_days(19_782), Date::new(2024, 2, 29));
+        assert_eq!(Date::from_unix_days(20_744), Date::new(2026, 10, 18));
+        assert_eq!(Date::from_unix_days(-800_000), None);
+    }
+
+    #[test]
+    fn test_date_serde_round_trip() {
+        let date = Date::new(2024, 6, 3).unwrap();
+        let json = serde_json::to_string(&date).unwrap();
+        assert_eq!(json, "\"2024-06-03\"");
+        assert_eq!(serde_json::from_str::<Date>(&json).unwrap(), date);
+        assert!(serde_json::from_str::<Date>("\"i juni\"").is_err());
+    }
+
+    #[test]
+    fn test_header_reads_old_free_text_dates() {
+        // Saved before dates were typed: free text, or null.
+        let old = r#"{"lab_report_id": "AR-1", "sample_id": "P1", "matrix": null,
+            "date": "maj 2024", "project": null, "lab": "Eurofins"}"#;
+        let header: ReportHeader = serde_json::from_str(old).unwrap();
+        assert_eq!(header.date, None);
+        assert_eq!(header.sample_id.as_deref(), Some("P1"));
+
+        let header = ReportHeader {
+            date: Date::new(2024, 5, 2),
+            received: Date::new(2024, 5, 6),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&header).unwrap();
+        let back: ReportHeader = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.date, header.date);
+        assert_eq!(back.received, header.received);
+
+        let header: ReportHeader = serde_json::from_str(r#"{"date": null}"#).unwrap();
+        assert_eq!(header.date, None);
+    }
+
+    #[test]
+    fn test_date_range_and_depth() {
+        let period = DateRange::from_str_loose("2024-05-06 - 2024-05-14").unwrap();
+        assert_eq!(Some(period.start), Date::new(2024, 5, 6));
+        assert_eq!(Some(period.end), Date::new(2024, 5, 14));
+
+        let depth = DepthInterval::from_str_loose("0,5-1,0 m").unwrap();
+        assert_eq!((depth.from, depth.to), (dec!(0.5), dec!(1)));
+        let depth = DepthInterval::from_str_loose("50–100 cm").unwrap();
+        assert_eq!((depth.from, depth.to), (dec!(0.5), dec!(1)));
+        assert_eq!(DepthInterval::from_str_loose("1-0,5 m"), None);
+    }
+
+    #[test]
+    fn test_coordinates() {
+        let c =
+            Coordinates::from_str_loose("Koordinater (SWEREF 99 TM): N 6580123 E 674321").unwrap();
+        assert_eq!((c.north, c.east), (dec!(6580123), dec!(674321)));
+        assert_eq!(c.crs.as_deref(), Some("SWEREF 99 TM"));
+        let c = Coordinates::from_str_loose("X: 6580123,5  Y: 674321").unwrap();
+        assert_eq!(c.north, dec!(6580123.5));
+        assert_eq!(c.crs, None);
+    }
+}
@@ -0,0 +1,227 @@
+//! ALS Scandinavia.
+//!
+//! A report header (report number, project) followed by one block per
+//! sample starting at "Er beteckning" ("Provbeteckning" on older reports).
+//! Results follow a "Parameter  Resultat  Osäkerhet (±)  Enhet" table
+//! header, with the uncertainty as a bare number in its own column.
+
+use super::header::{
+    extract_after_label, is_supersedes_line, read_revision_fields, read_sampling_fields,
+};
+use super::values::parse_value;
+use super::{
+    collect_sections, split_by_whitespace_gaps, try_parse_row, Detection, ParsedReports,
+    ReportParser, SkippedLine,
+};
+use crate::error::SondaError;
+use crate::model::{AnalysisReport, AnalysisRow, AnalysisValue, Matrix, ReportHeader};
+
+pub struct Als;
+
+/// Labels that start a sample block.
+const SAMPLE_LABELS: &[&str] = &["er beteckning", "provbeteckning"];
+
+impl ReportParser for Als {
+    fn name(&self) -> &'static str {
+        "als"
+    }
+
+    fn detect(&self, lines: &[&str]) -> Detection {
+        if lines
+            .iter()
+            .any(|l| l.to_lowercase().contains("als scandinavia"))
+        {
+            Detection::Match(100)
+        } else if lines.iter().any(|l| is_sample_start(l))
+            && lines.iter().any(|l| l.to_lowercase().contains("labnummer"))
+        {
+            // Letterhead lost in extraction, but the sample blocks are ALS's.
+            Detection::Match(50)
+        } else {
+            Detection::declined("no 'ALS Scandinavia' or 'Er beteckning' sample blocks")
+        }
+    }
+
+    fn parse(&self, lines: &[&str]) -> Result<ParsedReports, SondaError> {
+        let start = lines
+            .iter()
+            .position(|l| is_sample_start(l))
+            .ok_or_else(|| SondaError::ParseError("no 'Er beteckning' sample blocks".into()))?;
+        let shared = parse_report_header(&lines[..start]);
+        let blocks = split_into_blocks(&lines[start..]);
+
+        collect_sections(
+            &blocks,
+            |block| parse_block(block, &shared).map(|report| vec![report]),
+            |block| parse_sample_header(block, &shared).sample_id,
+        )
+    }
+}
+
+fn is_sample_start(line: &str) -> bool {
+    let lower = line.trim().to_lowercase();
+    SAMPLE_LABELS.iter().any(|label| lower.starts_with(label))
+}
+
+/// Split lines into sample blocks, each starting at a sample label.
+fn split_into_blocks<'a>(lines: &[&'a str]) -> Vec<Vec<&'a str>> {
+    let mut blocks: Vec<Vec<&str>> = Vec::new();
+    for &line in lines {
+        if is_sample_start(line) || blocks.is_empty() {
+            blocks.push(Vec::new());
+        }
+        if let Some(block) = blocks.last_mut() {
+            block.push(line);
+        }
+    }
+    blocks
+}
+
+/// Fields shared by every sample: report number, revision, project and lab.
+fn parse_report_header(lines: &[&str]) -> ReportHeader {
+    let mut header = ReportHeader {
+        lab: Some("ALS".to_string()),
+        ..Default::default()
+    };
+    for line in lines {
+        let line = line.trim();
+        if header.lab_report_id.is_none()
+            && line.to_lowercase().starts_with("rapport")
+            && !is_supersedes_line(line)
+        {
+            header.lab_report_id = extract_after_label(line, "rapport");
+        }
+        read_revision_fields(line, &mut header);
+        if header.project.is_none() {
+            header.project = extract_after_label(line, "projekt");
+        }
+        if header.matrix.is_none() {
+            header.matrix = parse_matrix(line);
+        }
+    }
+    header
+}
+
+/// Header of one sample block, falling back to the report header.
+fn parse_sample_header(block: &[&str], shared: &ReportHeader) -> ReportHeader {
+    let mut header = shared.clone();
+    let mut sample_matrix = None;
+    let mut lab_number = None;
+    for line in block.iter().take_while(|l| !is_table_header(l)) {
+        let line = line.trim();
+        if header.sample_id.is_none() {
+            if let Some(id) = SAMPLE_LABELS
+                .iter()
+                .find_map(|label| extract_after_label(line, label))
+            {
+                header.sample_id = Some(id);
+            }
+        }
+        if lab_number.is_none() {
+            lab_number = extract_after_label(line, "labnummer");
+        }
+        read_sampling_fields(line, &mut header);
+        if sample_matrix.is_none() {
+            sample_matrix = parse_matrix(line);
+        }
+    }
+    header.sample_id = header.sample_id.or(lab_number);
+    header.matrix = sample_matrix.or(shared.matrix);
+    header
+}
+
+fn parse_matrix(line: &str) -> Option<Matrix> {
+    extract_after_label(line, "provtyp")
+        .or_else(|| extract_after_label(line, "matris"))
+        .and_then(|val| Matrix::from_str_loose(&val))
+}
+
+/// The "Parameter  Resultat  ..." line above each results table.
+fn is_table_header(line: &str) -> bool {
+    let lower = line.trim().to_lowercase();
+    lower.starts_with("parameter") && lower.contains("resultat")
+}
+
+fn parse_block(
+    block: &[&str],
+    shared: &ReportHeader,
+) -> Result<(AnalysisReport, Vec<SkippedLine>), SondaError> {
+    let header = parse_sample_header(block, shared);
+
+    let table_start = block
+        .iter()
+        .position(|l| is_table_header(l))
+        .ok_or_else(|| SondaError::ParseError("no results table in sample block".into()))?;
+
+    let mut rows = Vec::new();
+    let mut skipped = Vec::new();
+    for line in &block[table_start + 1..] {
+        let line = line.trim();
+        if line.len() < 3 || is_table_header(line) {
+            continue;
+        }
+        match try_parse_row(line) {
+            Ok(Some(row)) => rows.push(with_uncertainty_column(row, line)),
+            Ok(None) => {}
+            Err(skip) => skipped.push(skip),
+        }
+    }
+
+    if rows.is_empty() {
+        return Err(SondaError::ParseError(
+            "no analysis values found in section".into(),
+        ));
+    }
+
+    Ok((AnalysisReport { header, rows }, skipped))
+}
+
+/// Read the "Osäkerhet (±)" column: a bare number right after the result.
+/// Empty for values below the detection limit.
+fn with_uncertainty_column(mut row: AnalysisRow, line: &str) -> AnalysisRow {
+    if row.uncertainty.is_none() && !row.value.is_below_detection() {
+        let segments = split_by_whitespace_gaps(line);
+        if let Some(Ok(Some(AnalysisValue::Measured(u)))) = segments.get(2).map(|s| parse_value(s))
+        {
+            row.uncertainty = Some(u);
+        }
+    }
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_sample_header_falls_back_to_lab_number() {
+        let shared = parse_report_header(&["Rapport      T2412345", "Projekt      Kv. Björken"]);
+        assert_eq!(shared.lab_report_id.as_deref(), Some("T2412345"));
+
+        let header = parse_sample_header(
+            &[
+                "Provbeteckning",
+                "Labnummer           O11456789",
+                "Provtyp             Jord",
+            ],
+            &shared,
+        );
+        assert_eq!(header.sample_id.as_deref(), Some("O11456789"));
+        assert_eq!(header.matrix, Some(Matrix::Jord));
+        assert_eq!(header.project.as_deref(), Some("Kv. Björken"));
+    }
+
+    #[test]
+    fn test_uncertainty_column() {
+        let line = "As                  12.1      3.6      mg/kg TS    2    H    ANNI";
+        let row = with_uncertainty_column(try_parse_row(line).unwrap().unwrap(), line);
+        assert_eq!(row.normalized_name, "arsenik");
+        assert_eq!(row.uncertainty, Some(dec!(3.6)));
+
+        let line = "Cd                  <0.10              mg/kg TS    2    H    ANNI";
+        let row = with_uncertainty_column(try_parse_row(line).unwrap().unwrap(), line);
+        assert_eq!(row.value, AnalysisValue::BelowDetection(dec!(0.10)));
+        assert_eq!(row.uncertainty, None);
+    }
+}
@@ -0,0 +1,305 @@
+//! Tables with one value column per sample.
+//!
+//! Summary reports may list several samples side by side under one header:
+//!
+//! ```text
+//! Provmärkning        PG1 0-0,5     PG2 0-0,5     PG3 0,5-1     Enhet
+//! Bly (Pb)            12            <5            48            mg/kg TS
+//! ```
+//!
+//! Each line is split by column position into one line per sample, e.g.
+//! "Bly (Pb)   12   mg/kg TS", which the usual row and header parsers read.
+
+use super::is_header_word;
+use super::values::parse_value;
+
+/// Headings of columns that never hold a sample, besides the usual table
+/// headers: limits, method references, uncertainty and units. A heading
+/// matches with spaces removed and may go on with e.g. "(mg/kg)".
+const NOT_SAMPLE_HEADINGS: &[&str] = &[
+    "loq",
+    "lod",
+    "rapp.gräns",
+    "rapporteringsgräns",
+    "detektionsgräns",
+    "kvantifieringsgräns",
+    "metod",
+    "metodref",
+    "metodreferens",
+    "method",
+    "standard",
+    "mätosäkerhet",
+    "osäkerhet",
+    "±",
+    "mg/kgts",
+    "mg/kg",
+    "µg/kgts",
+    "mg/l",
+    "µg/l",
+    "%ts",
+    "%",
+];
+
+/// Whether a column heading names a sample rather than e.g. "Enhet" or "LOQ".
+fn names_sample(heading: &str) -> bool {
+    let key: String = heading
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    let key = key.trim_end_matches([':', '.']);
+    !is_header_word(key)
+        && !NOT_SAMPLE_HEADINGS.iter().any(|h| {
+            key.strip_prefix(h)
+                .is_some_and(|rest| !rest.starts_with(char::is_alphanumeric))
+        })
+}
+
+/// Text between gaps of 2+ spaces, with its start and end char column.
+struct Cell<'a> {
+    start: usize,
+    end: usize,
+    text: &'a str,
+}
+
+impl Cell<'_> {
+    /// Twice the centre column, to stay in whole numbers.
+    fn centre2(&self) -> usize {
+        self.start + self.end
+    }
+}
+
+/// Split a line like `split_by_whitespace_gaps`, keeping char positions so
+/// cells can be matched to the column heading above them.
+fn cells(line: &str) -> Vec<Cell<'_>> {
+    let mut cells = Vec::new();
+    // (byte, char) index where the current cell starts
+    let mut start: Option<(usize, usize)> = None;
+    let mut last_end = (0, 0);
+    let mut space_count = 0;
+
+    for (column, (i, c)) in line.char_indices().enumerate() {
+        if c.is_whitespace() {
+            space_count += 1;
+            if space_count == 2 {
+                if let Some((byte, char)) = start.take() {
+                    cells.push(Cell {
+                        start: char,
+                        end: last_end.1,
+                        text: &line[byte..last_end.0],
+                    });
+                }
+            }
+        } else {
+            if start.is_none() {
+                start = Some((i, column));
+            }
+            space_count = 0;
+            last_end = (i + c.len_utf8(), column + 1);
+        }
+    }
+
+    if let Some((byte, char)) = start {
+        cells.push(Cell {
+            start: char,
+            end: last_end.1,
+            text: &line[byte..last_end.0],
+        });
+    }
+
+    cells
+}
+
+/// Column layout read from the line naming the samples.
+#[derive(Debug)]
+pub struct SampleColumns {
+    /// Index of the line naming the samples.
+    pub label_line: usize,
+    /// Doubled centre of each heading, and its sample index if it names a
+    /// sample rather than e.g. "Enhet".
+    headings: Vec<(usize, Option<usize>)>,
+    samples: usize,
+    /// Cells centred beyond this (doubled) column belong to every sample,
+    /// like the unit and method.
+    table_end2: usize,
+}
+
+impl SampleColumns {
+    /// Number of sample columns.
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// Split a line into one line per sample column: the line's first cell,
+    /// the sample's cells and the cells shared by all samples. `None` where
+    /// the sample's cell is blank.
+    pub fn split_line(&self, line: &str) -> Vec<Option<String>> {
+        let cells = cells(line);
+        let mut values: Vec<Vec<&str>> = vec![Vec::new(); self.samples];
+        let Some((name, rest)) = cells.split_first() else {
+            return vec![None; self.samples];
+        };
+        let mut shared = Vec::new();
+        for cell in rest {
+            match self.sample_of(cell) {
+                Some(sample) => values[sample].push(cell.text),
+                None => shared.push(cell.text),
+            }
+        }
+        values
+            .into_iter()
+            .map(|cells| {
+                (!cells.is_empty()).then(|| {
+                    std::iter::once(name.text)
+                        .chain(cells)
+                        .chain(shared.iter().copied())
+                        .collect::<Vec<_>>()
+                        .join("   ")
+                })
+            })
+            .collect()
+    }
+
+    /// The sample column under the heading nearest to the cell.
+    fn sample_of(&self, cell: &Cell) -> Option<usize> {
+        let centre2 = cell.centre2();
+        if centre2 > self.table_end2 {
+            return None;
+        }
+        self.headings
+            .iter()
+            .min_by_key(|(heading, _)| heading.abs_diff(centre2))
+            .and_then(|(_, sample)| *sample)
+    }
+
+    /// How many samples have a value on the line.
+    fn values_on(&self, line: &str) -> usize {
+        let cells = cells(line);
+        let mut found = vec![false; self.samples];
+        for cell in cells.iter().skip(1) {
+            if let (Some(sample), Ok(Some(_))) = (self.sample_of(cell), parse_value(cell.text)) {
+                found[sample] = true;
+            }
+        }
+        found.iter().filter(|f| **f).count()
+    }
+}
+
+/// Find a column-per-sample table: a line starting with one of `labels`
+/// (e.g. "Provmärkning") followed by two or more sample headings, and a later
+/// line with values under at least two of them.
+pub fn find_sample_columns(lines: &[&str], labels: &[&str]) -> Option<SampleColumns> {
+    lines.iter().enumerate().find_map(|(i, line)| {
+        let cells = cells(line);
+        let (first, rest) = cells.split_first()?;
+        let label = first.text.trim_end_matches(':').trim().to_lowercase();
+        // "Provnummer: 001    Provmärkning: PG1" is a single sample's header.
+        if !labels.contains(&label.as_str()) || rest.iter().any(|c| c.text.contains(':')) {
+            return None;
+        }
+
+        let mut samples = 0;
+        let headings: Vec<(usize, Option<usize>)> = rest
+            .iter()
+            .map(|cell| {
+                let sample = names_sample(cell.text).then(|| {
+                    samples += 1;
+                    samples - 1
+                });
+                (cell.centre2(), sample)
+            })
+            .collect();
+        if samples < 2 {
+            return None;
+        }
+        let sample_centres: Vec<usize> = headings
+            .iter()
+            .filter(|(_, sample)| sample.is_some())
+            .map(|(centre2, _)| *centre2)
+            .collect();
+        let (first_centre, last_centre) = (sample_centres[0], sample_centres[samples - 1]);
+        let pitch2 = (last_centre - first_centre) / (samples - 1);
+
+        let table = SampleColumns {
+            label_line: i,
+            headings,
+            samples,
+            table_end2: last_centre + pitch2 / 2,
+        };
+        lines[i + 1..]
+            .iter()
+            .any(|l| table.values_on(l) >= 2)
+            .then_some(table)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LABELS: &[&str] = &["provmärkning"];
+
+    #[test]
+    fn test_split_line_by_heading_position() {
+        let lines = [
+            "Provmärkning        PG1 0-0,5     PG2 0-0,5     PG3 0,5-1     Enhet",
+            "Bly (Pb)            12            <5            48            mg/kg TS",
+            "Kadmium (Cd)        0,30                        1,2           mg/kg TS",
+        ];
+        let table = find_sample_columns(&lines, LABELS).unwrap();
+        assert_eq!(table.label_line, 0);
+        assert_eq!(table.samples(), 3);
+
+        let ids = table.split_line(lines[0]);
+        assert_eq!(ids[1].as_deref(), Some("Provmärkning   PG2 0-0,5   Enhet"));
+
+        let cadmium = table.split_line(lines[2]);
+        assert_eq!(
+            cadmium,
+            [
+                Some("Kadmium (Cd)   0,30   mg/kg TS".to_string()),
+                None,
+                Some("Kadmium (Cd)   1,2   mg/kg TS".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_limit_and_unit_columns_are_not_samples() {
+        let lines = [
+            "Provmärkning        LOQ       PG1 0-0,5     PG2 0-0,5     Metod/ref      Enhet",
+            "Bly (Pb)            0,5       12            48            SS 028311      mg/kg TS",
+        ];
+        let table = find_sample_columns(&lines, LABELS).unwrap();
+        assert_eq!(table.samples(), 2);
+        assert_eq!(
+            table.split_line(lines[1]),
+            [
+                Some("Bly (Pb)   12   0,5   SS 028311   mg/kg TS".to_string()),
+                Some("Bly (Pb)   48   0,5   SS 028311   mg/kg TS".to_string()),
+            ]
+        );
+
+        assert!(!names_sample("Rapp. gräns"));
+        assert!(!names_sample("LOQ (mg/kg)"));
+        assert!(!names_sample("mg/kg TS"));
+        assert!(names_sample("PG1 0-0,5"));
+        assert!(names_sample("Lodet 1"));
+    }
+
+    #[test]
+    fn test_single_sample_header_is_not_a_table() {
+        let lines = [
+            "Provmärkning: PG1          Provnummer: 177-001",
+            "Bly (Pb)            12            mg/kg TS     ± 3",
+        ];
+        assert!(find_sample_columns(&lines, LABELS).is_none());
+
+        // Two headings, but no line has values under both.
+        let lines = [
+            "Provmärkning        PG1           PG2           Metod",
+            "Bly (Pb)            12                          SS-EN ISO 11885",
+        ];
+        assert!(find_sample_columns(&lines, LABELS).is_none());
+    }
+}
@@ -0,0 +1,418 @@
+//! Eurofins Environment Testing Sweden.
+//!
+//! One "Analysrapport" section per sample, labelled with Provmärkning
+//! and/or Provnummer. Summary reports instead list several samples side by
+//! side, one column each, under a Provmärkning line.
+
+use super::columns::{find_sample_columns, SampleColumns};
+use super::header::{
+    extract_after_label, is_supersedes_line, read_revision_fields, read_sampling_fields,
+};
+use super::{
+    collect_sections, parse_table_rows, split_by_whitespace_gaps, try_parse_row, Detection,
+    ParsedReports, ReportParser, SkippedLine,
+};
+use crate::error::SondaError;
+use crate::model::{AnalysisReport, AnalysisRow, Matrix, ReportHeader};
+
+/// Labels of the line naming a section's sample(s).
+const SAMPLE_LABELS: &[&str] = &["provmärkning", "provnummer"];
+
+pub struct Eurofins;
+
+impl ReportParser for Eurofins {
+    fn name(&self) -> &'static str {
+        "eurofins"
+    }
+
+    fn detect(&self, lines: &[&str]) -> Detection {
+        if lines.iter().any(|l| l.to_lowercase().contains("eurofins")) {
+            Detection::Match(100)
+        } else {
+            Detection::declined("no 'Eurofins' in the report text")
+        }
+    }
+
+    fn parse(&self, lines: &[&str]) -> Result<ParsedReports, SondaError> {
+        // Split into sections on "Analysrapport" boundaries
+        let sections = split_into_sections(lines);
+
+        collect_sections(&sections, parse_section, |section| {
+            let header_lines: Vec<&str> = section.iter().take(30).copied().collect();
+            let header = parse_header(&header_lines);
+            header.sample_id.or(header.lab_report_id)
+        })
+    }
+}
+
+/// Split lines into sections, each starting at an "Analysrapport" heading
+/// (also "ANALYSRAPPORT   Sida 2 (3)"). A section that names no sample and
+/// no other report is a continuation page and joins the previous section.
+/// If no heading is found, treat the whole document as one section.
+fn split_into_sections<'a>(lines: &[&'a str]) -> Vec<Vec<&'a str>> {
+    let mut sections: Vec<Vec<&str>> = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+
+    for &line in lines {
+        if is_section_start(line) && current.iter().any(|l| is_section_start(l)) {
+            push_section(&mut sections, std::mem::take(&mut current));
+        }
+        current.push(line);
+    }
+
+    if !current.is_empty() {
+        push_section(&mut sections, current);
+    }
+
+    sections
+}
+
+fn is_section_start(line: &str) -> bool {
+    split_by_whitespace_gaps(line.trim())
+        .first()
+        .is_some_and(|first| first.eq_ignore_ascii_case("analysrapport"))
+}
+
+fn push_section<'a>(sections: &mut Vec<Vec<&'a str>>, section: Vec<&'a str>) {
+    let Some(previous) = sections.last_mut() else {
+        sections.push(section);
+        return;
+    };
+    let names_sample = section.iter().any(|line| {
+        let lower = line.trim().to_lowercase();
+        SAMPLE_LABELS.iter().any(|label| lower.starts_with(label))
+    });
+    let report_id = |lines: &[&str]| parse_header(&lines[..lines.len().min(30)]).lab_report_id;
+    let other_report = report_id(&section).is_some_and(|id| report_id(previous) != Some(id));
+    if names_sample || other_report {
+        sections.push(section);
+    } else {
+        previous.extend(section);
+    }
+}
+
+/// Parse a single section into an AnalysisReport per sample: one, or one
+/// per column of a column-per-sample table.
+fn parse_section(lines: &[&str]) -> Result<Vec<(AnalysisReport, Vec<SkippedLine>)>, SondaError> {
+    if let Some(table) = find_sample_columns(lines, SAMPLE_LABELS) {
+        return parse_sample_columns(lines, &table);
+    }
+
+    // Parse header from the first ~30 lines of this section
+    let header_lines: Vec<&str> = lines.iter().take(30).copied().collect();
+    let mut header = parse_header(&header_lines);
+    // Only the first section carries the letterhead.
+    header.lab = Some("Eurofins".to_string());
+
+    // Find and parse table rows
+    let (rows, skipped) = parse_table_rows(lines)?;
+
+    if rows.is_empty() {
+        return Err(SondaError::ParseError(
+            "no analysis values found in section".into(),
+        ));
+    }
+
+    Ok(vec![(AnalysisReport { header, rows }, skipped)])
+}
+
+/// One report per sample column. Lines above the table are shared; lines
+/// from the sample headings down to the first analysis row (Provnummer,
+/// Provtagningsdatum, ...) are read per column. A column without values
+/// gives a report without rows, which `collect_sections` turns into a
+/// warning.
+fn parse_sample_columns(
+    lines: &[&str],
+    table: &SampleColumns,
+) -> Result<Vec<(AnalysisReport, Vec<SkippedLine>)>, SondaError> {
+    let shared: Vec<&str> = lines[..table.label_line].iter().take(30).copied().collect();
+    let table_lines = &lines[table.label_line..];
+    let split: Vec<Vec<Option<String>>> = table_lines.iter().map(|l| table.split_line(l)).collect();
+    let first_row = split
+        .iter()
+        .position(|columns| {
+            columns
+                .iter()
+                .flatten()
+                .any(|line| matches!(try_parse_row(line), Ok(Some(_))))
+        })
+        .unwrap_or(split.len());
+
+    let mut reports = Vec::new();
+    for sample in 0..table.samples() {
+        let header_lines: Vec<&str> = shared
+            .iter()
+            .copied()
+            .chain(
+                split[..first_row]
+                    .iter()
+                    .filter_map(|c| c[sample].as_deref()),
+            )
+            .collect();
+        let mut header = parse_header(&header_lines);
+        header.lab = Some("Eurofins".to_string());
+
+        let mut rows = Vec::new();
+        let mut skipped = Vec::new();
+        for (line, columns) in table_lines.iter().zip(&split).skip(first_row) {
+            let Some(cell_line) = &columns[sample] else {
+                continue;
+            };
+            // Keep the printed line, all columns, as the source.
+            match try_parse_row(cell_line) {
+                Ok(Some(row)) => rows.push(AnalysisRow {
+                    source_line: Some(line.trim().to_string()),
+                    ..row
+                }),
+                Ok(None) => {}
+                Err(skip) => skipped.push(SkippedLine {
+                    line_text: line.trim().to_string(),
+                    ..skip
+                }),
+            }
+        }
+
+        reports.push((AnalysisReport { header, rows }, skipped));
+    }
+
+    Ok(reports)
+}
+
+/// Extract report header information from text lines.
+pub fn parse_header(lines: &[&str]) -> ReportHeader {
+    let mut header = ReportHeader::default();
+
+    for line in lines {
+        let line = line.trim();
+
+        // Try to extract lab report ID (Rapport-/LaboratorieID patterns),
+        // not "Sida 2 (3)" from an "Analysrapport" heading
+        if header.lab_report_id.is_none() && !is_supersedes_line(line) && !is_section_start(line) {
+            if let Some(id) = extract_after_label(line, "rapport") {
+                header.lab_report_id = Some(id);
+            } else if let Some(id) = extract_after_label(line, "laboratorienummer") {
+                header.lab_report_id = Some(id);
+            }
+        }
+
+        // Try to extract sample ID
+        // Prefer Provmärkning (human label like "Väg 115 P1") over Provnummer (lab number)
+        if let Some(id) = extract_after_label(line, "provmärkning") {
+            header.sample_id = Some(id);
+        } else if header.sample_id.is_none() {
+            if let Some(id) = extract_after_label(line, "provnummer") {
+                header.sample_id = Some(id);
+            }
+        }
+
+        // Matrix detection
+        if header.matrix.is_none() {
+            if let Some(val) = extract_after_label(line, "matris") {
+                header.matrix = Matrix::from_str_loose(&val);
+            } else if let Some(val) = extract_after_label(line, "provtyp") {
+                header.matrix = Matrix::from_str_loose(&val);
+            }
+            // Also check for matrix keywords anywhere in header lines
+            if header.matrix.is_none() {
+                let lower = line.to_lowercase();
+                if lower.contains("matris") && lower.contains("jord") {
+                    header.matrix = Some(Matrix::Jord);
+                } else if lower.contains("matris") && lower.contains("asfalt") {
+                    header.matrix = Some(Matrix::Asfalt);
+                }
+            }
+        }
+
+        // Project
+        if header.project.is_none() {
+            if let Some(val) = extract_after_label(line, "projekt") {
+                header.project = Some(val);
+            } else if let Some(val) = extract_after_label(line, "uppdrag") {
+                header.project = Some(val);
+            }
+        }
+
+        read_sampling_fields(line, &mut header);
+        read_revision_fields(line, &mut header);
+    }
+
+    header
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Date;
+
+    #[test]
+    fn test_split_into_sections_single() {
+        let lines = vec!["Header", "Analysrapport", "Data line 1", "Data line 2"];
+        let sections = split_into_sections(&lines);
+        assert_eq!(sections.len(), 1);
+    }
+
+    #[test]
+    fn test_split_into_sections_multi() {
+        let lines = vec![
+            "Header",
+            "Analysrapport",
+            "Provnummer: 001",
+            "Data 1",
+            "Analysrapport",
+            "Provnummer: 002",
+            "Data 2",
+        ];
+        let sections = split_into_sections(&lines);
+        assert_eq!(sections.len(), 2);
+        assert!(sections[0].contains(&"Provnummer: 001"));
+        assert!(sections[1].contains(&"Provnummer: 002"));
+    }
+
+    #[test]
+    fn test_split_into_sections_no_header() {
+        let lines = vec!["Line 1", "Line 2", "Line 3"];
+        let sections = split_into_sections(&lines);
+        assert_eq!(sections.len(), 1);
+    }
+
+    #[test]
+    fn test_split_into_sections_headings_with_page_numbers() {
+        let lines = vec![
+            "ANALYSRAPPORT          Sida 1 (3)",
+            "Rapport: AR-001",
+            "Provnummer: 001",
+            "Data 1",
+            "Analysrapport          Sida 2 (3)",
+            "Rapport: AR-001",
+            "Data 1, continued",
+            "Analysrapport          Sida 3 (3)",
+            "Provnummer: 002",
+            "Data 2",
+        ];
+        let sections = split_into_sections(&lines);
+        assert_eq!(sections.len(), 2);
+        assert!(sections[0].contains(&"Data 1, continued"));
+        assert!(sections[1].contains(&"Provnummer: 002"));
+    }
+
+    #[test]
+    fn test_parse_header_basic() {
+        let lines = [
+            "Rapport: ABC123",
+            "Provnummer: P001",
+            "Matris: Jord",
+            "Projekt: Test",
+        ];
+        let h = parse_header(&lines);
+        assert_eq!(h.lab_report_id.as_deref(), Some("ABC123"));
+        assert_eq!(h.sample_id.as_deref(), Some("P001"));
+        assert_eq!(h.matrix, Some(Matrix::Jord));
+        assert_eq!(h.project.as_deref(), Some("Test"));
+    }
+
+    #[test]
+    fn test_parse_header_sampling_metadata() {
+        let lines = [
+            "Provmärkning: PG3 0,5-1,0",
+            "Provtagningsdatum    2024-05-02         Ankomstdatum    2024-05-06",
+            "Analysperiod: 2024-05-06 - 2024-05-14",
+            "Provtagningsdjup: 0,5-1,0 m",
+            "Provtagare: Anna Berg",
+            "Provplats: Provgrop 3",
+            "Koordinater SWEREF 99 TM   N 6580123   E 674321",
+            "Ordernummer: 4500123",
+        ];
+        let h = parse_header(&lines);
+        assert_eq!(h.date, Date::new(2024, 5, 2));
+        assert_eq!(h.received, Date::new(2024, 5, 6));
+        let period = h.analysis_period.unwrap();
+        assert_eq!(Some(period.end), Date::new(2024, 5, 14));
+        assert_eq!(h.depth.unwrap().to_string(), "0.5–1 m");
+        assert_eq!(h.sampler.as_deref(), Some("Anna Berg"));
+        assert_eq!(h.location.as_deref(), Some("Provgrop 3"));
+        assert_eq!(
+            h.coordinates.unwrap().to_string(),
+            "N 6580123 E 674321 (SWEREF 99 TM)"
+        );
+        assert_eq!(h.order_number.as_deref(), Some("4500123"));
+    }
+
+    #[test]
+    fn test_parse_header_revision() {
+        let lines = [
+            "Ersätter rapport AR-24-SE-011870, utfärdad 2024-05-10",
+            "Rapport: AR-24-SE-012004    Revision 2",
+            "Provnummer: P001",
+        ];
+        let h = parse_header(&lines);
+        assert_eq!(h.lab_report_id.as_deref(), Some("AR-24-SE-012004"));
+        assert_eq!(h.supersedes.as_deref(), Some("AR-24-SE-011870"));
+        assert_eq!(h.revision, Some(2));
+    }
+
+    #[test]
+    fn test_parse_sample_columns() {
+        let lines = [
+            "Analysrapport",
+            "Rapport: AR-24-SE-013377",
+            "Matris: Jord",
+            "Provmärkning        PG1 0-0,5     PG2 0-0,5     PG3 0,5-1     Enhet",
+            "Provnummer          177-001       177-002       177-003",
+            "Provtagningsdatum   2024-05-02    2024-05-02    2024-05-03",
+            "",
+            "Bly (Pb)            12            <5            48            mg/kg TS",
+            "Kadmium (Cd)        0,30                        1,2           mg/kg TS",
+            "Zink (Zn)           85            n.b.          140           mg/kg TS",
+        ];
+        let parsed = Eurofins.parse(&lines).unwrap();
+        assert_eq!(parsed.reports.len(), 3);
+
+        let ids: Vec<_> = parsed
+            .reports
+            .iter()
+            .map(|r| r.header.sample_id.as_deref().unwrap())
+            .collect();
+        assert_eq!(ids, ["PG1 0-0,5", "PG2 0-0,5", "PG3 0,5-1"]);
+        let pg3 = &parsed.reports[2];
+        assert_eq!(pg3.header.lab_report_id.as_deref(), Some("AR-24-SE-013377"));
+        assert_eq!(pg3.header.matrix, Some(Matrix::Jord));
+        assert_eq!(pg3.header.date, Date::new(2024, 5, 3));
+        assert_eq!(pg3.rows.len(), 3);
+        assert_eq!(pg3.rows[1].normalized_name, "kadmium");
+
+        // PG2 has no cadmium, and its zinc cell is unreadable.
+        assert_eq!(parsed.reports[1].rows.len(), 1);
+        assert_eq!(parsed.skipped_lines.len(), 1);
+        assert_eq!(parsed.skipped_lines[0].report_index, Some(1));
+        assert!(parsed.skipped_lines[0].line_text.starts_with("Zink (Zn)"));
+    }
+
+    #[test]
+    fn test_sample_column_without_values_is_a_warning() {
+        let lines = [
+            "Analysrapport",
+            "Provmärkning        PG1           PG2           PG3           Enhet",
+            "Bly (Pb)            12            n.b.          48            mg/kg TS",
+            "Zink (Zn)           85            n.b.          140           mg/kg TS",
+        ];
+        let parsed = Eurofins.parse(&lines).unwrap();
+
+        assert_eq!(parsed.reports.len(), 2);
+        assert_eq!(parsed.reports[1].header.sample_id.as_deref(), Some("PG3"));
+        assert_eq!(parsed.warnings.len(), 1);
+        assert_eq!(parsed.warnings[0].sample_id.as_deref(), Some("PG2"));
+        assert_eq!(parsed.warnings[0].section_index, 1);
+        assert_eq!(parsed.skipped_lines.len(), 2);
+        assert!(parsed
+            .skipped_lines
+            .iter()
+            .all(|s| s.report_index.is_none()));
+    }
+
+    #[test]
+    fn test_matrix_asfalt() {
+        let lines = ["Matris: Asfalt"];
+        let h = parse_header(&lines);
+        assert_eq!(h.matrix, Some(Matrix::Asfalt));
+    }
+}
@@ -1,75 +1,12 @@
-use crate::model::{Matrix, ReportHeader};
+//! Helpers for reading labelled fields from report headers.
 
-/// Extract report header information from text lines.
-pub fn parse_header(lines: &[&str]) -> ReportHeader {
-    let mut header = ReportHeader::default();
-
-    for line in lines {
-        let line = line.trim();
-
-        // Detect laboratory
-        if header.lab.is_none() {
-            let lower = line.to_lowercase();
-            if lower.contains("eurofins") {
-                header.lab = Some("Eurofins".to_string());
-            }
-        }
-
-        // Try to extract lab report ID (Rapport-/LaboratorieID patterns)
-        if header.lab_report_id.is_none() {
-            if let Some(id) = extract_after_label(line, "rapport") {
-                header.lab_report_id = Some(id);
-            } else if let Some(id) = extract_after_label(line, "laboratorienummer") {
-                header.lab_report_id = Some(id);
-            }
-        }
-
-        // Try to extract sample ID
-        // Prefer Provmärkning (human label like "Väg 115 P1") over Provnummer (lab number)
-        if let Some(id) = extract_after_label(line, "provmärkning") {
-            header.sample_id = Some(id);
-        } else if header.sample_id.is_none() {
-            if let Some(id) = extract_after_label(line, "provnummer") {
-                header.sample_id = Some(id);
-            }
-        }
-
-        // Matrix detection
-        if header.matrix.is_none() {
-            if let Some(val) = extract_after_label(line, "matris") {
-                header.matrix = Matrix::from_str_loose(&val);
-            } else if let Some(val) = extract_after_label(line, "provtyp") {
-                header.matrix = Matrix::from_str_loose(&val);
-            }
-            // Also check for matrix keywords anywhere in header lines
-            if header.matrix.is_none() {
-                let lower = line.to_lowercase();
-                if lower.contains("matris") && lower.contains("jord") {
-                    header.matrix = Some(Matrix::Jord);
-                } else if lower.contains("matris") && lower.contains("asfalt") {
-                    header.matrix = Some(Matrix::Asfalt);
-                }
-            }
-        }
-
-        // Project
-        if header.project.is_none() {
-            if let Some(val) = extract_after_label(line, "projekt") {
-                header.project = Some(val);
-            } else if let Some(val) = extract_after_label(line, "uppdrag") {
-                header.project = Some(val);
-            }
-        }
-    }
-
-    header
-}
+use crate::model::{Coordinates, Date, DateRange, DepthInterval, ReportHeader};
 
 /// Extract a value appearing after a label (case-insensitive).
 /// Handles patterns like "Label: value" or "Label    value" (tab/space separated).
 /// Truncates at the next large whitespace gap (3+ spaces) to avoid capturing
 /// trailing fields from pdftotext -layout output.
-fn extract_after_label(line: &str, label: &str) -> Option<String> {
+pub fn extract_after_label(line: &str, label: &str) -> Option<String> {
     let lower = line.to_lowercase();
     if let Some(idx) = lower.find(label) {
         let after = &line[idx + label.len()..];
@@ -94,29 +31,119 @@ fn extract_after_label(line: &str, label: &str) -> Option<String> {
     }
 }
 
+/// Fill the sampling metadata a header line may carry: sampling, arrival and
+/// analysis dates, depth, sampler, sampling point, coordinates and order
+/// number. Fields that are already set are kept.
+pub fn read_sampling_fields(line: &str, header: &mut ReportHeader) {
+    let find = |labels: &[&str]| {
+        labels
+            .iter()
+            .find_map(|label| extract_after_label(line, label))
+    };
+
+    if header.date.is_none() {
+        header.date =
+            find(&["provtagningsdatum", "provtagningsdag"]).and_then(|v| Date::from_str_loose(&v));
+    }
+    if header.received.is_none() {
+        header.received =
+            find(&["ankomstdatum", "mottagningsdatum"]).and_then(|v| Date::from_str_loose(&v));
+    }
+    if header.analysis_period.is_none() {
+        header.analysis_period =
+            find(&["analysperiod", "analysdatum"]).and_then(|v| DateRange::from_str_loose(&v));
+    }
+    if header.depth.is_none() {
+        header.depth =
+            find(&["provtagningsdjup", "djup"]).and_then(|v| DepthInterval::from_str_loose(&v));
+    }
+    if header.sampler.is_none() {
+        header.sampler = find(&["provtagare", "provtagen av"]);
+    }
+    if header.location.is_none() {
+        header.location = find(&["provtagningsplats", "provplats", "provpunkt"]);
+    }
+    // Northing and easting are often spread over columns, so read the whole line.
+    if header.coordinates.is_none() && line.to_lowercase().contains("koordinat") {
+        header.coordinates = Coordinates::from_str_loose(line);
+    }
+    if header.order_number.is_none() {
+        header.order_number = find(&["ordernummer", "ordernr", "beställningsnummer"]);
+    }
+}
+
+/// Words between a "replaces" label and the replaced report's number.
+const SUPERSEDES_FILLER: &[&str] = &["rapport", "analysrapport", "report", "nr", "nr.", "nummer"];
+
+/// Whether a line names the report this one replaces, rather than its own number.
+pub fn is_supersedes_line(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    lower.contains("ersätter") || lower.contains("replaces")
+}
+
+/// Read a reissued report's revision ("Revision 2", "Version 3") and the
+/// number of the report it replaces ("Ersätter rapport AR-24-001"). Fields
+/// that are already set are kept.
+pub fn read_revision_fields(line: &str, header: &mut ReportHeader) {
+    if header.supersedes.is_none() {
+        header.supersedes = ["ersätter", "replaces"]
+            .iter()
+            .find_map(|label| extract_after_label(line, label))
+            .and_then(|rest| {
+                rest.split_whitespace()
+                    .map(|t| t.trim_end_matches([',', ';', ':']))
+                    .find(|t| !SUPERSEDES_FILLER.contains(&t.to_lowercase().as_str()))
+                    .map(str::to_string)
+            });
+    }
+    if header.revision.is_none() {
+        header.revision = read_revision(line);
+    }
+}
+
+/// The number after a revision label. The label must start the line, or a
+/// column of the line naming the report ("Rapport: AR-24-001    Revision 2"),
+/// so "Metod version 3" in a method table is not read as a revision.
+fn read_revision(line: &str) -> Option<u32> {
+    let lower = line.to_lowercase();
+    let names_report = lower.contains("rapport") || lower.contains("report");
+    ["revision", "rev.", "version", "utgåva"]
+        .iter()
+        .find_map(|label| {
+            let idx = lower.find(label)?;
+            let before = &lower[..idx];
+            let starts_field = before.trim().is_empty() || (names_report && before.ends_with("  "));
+            starts_field
+                .then(|| extract_after_label(&line[idx..], label))
+                .flatten()
+        })
+        .and_then(|rest| {
+            rest.split_whitespace()
+                .next()?
+                .trim_end_matches([',', '.', ')'])
+                .parse()
+                .ok()
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_header_basic() {
-        let lines = [
-            "Rapport: ABC123",
-            "Provnummer: P001",
-            "Matris: Jord",
-            "Projekt: Test",
-        ];
-        let h = parse_header(&lines);
-        assert_eq!(h.lab_report_id.as_deref(), Some("ABC123"));
-        assert_eq!(h.sample_id.as_deref(), Some("P001"));
-        assert_eq!(h.matrix, Some(Matrix::Jord));
-        assert_eq!(h.project.as_deref(), Some("Test"));
-    }
+    fn test_revision_label_must_start_a_field() {
+        assert_eq!(read_revision("Revision 2"), Some(2));
+        assert_eq!(read_revision("  Utgåva: 3"), Some(3));
+        assert_eq!(
+            read_revision("Rapport: AR-24-SE-012004    Version 2"),
+            Some(2)
+        );
 
-    #[test]
-    fn test_matrix_asfalt() {
-        let lines = ["Matris: Asfalt"];
-        let h = parse_header(&lines);
-        assert_eq!(h.matrix, Some(Matrix::Asfalt));
+        assert_eq!(read_revision("Metod version 3"), None);
+        assert_eq!(
+            read_revision("Bly (Pb)   12   mg/kg TS   Metod version 3"),
+            None
+        );
+        assert_eq!(read_revision("Kommentar: se revision 4 av metoden"), None);
     }
 }
@@ -1,19 +1,78 @@
+pub mod als;
+pub mod columns;
+pub mod eurofins;
 pub mod header;
 pub mod normalize;
+pub mod synlab;
 pub mod values;
 
 use crate::error::SondaError;
-use crate::extraction::PageContent;
-use crate::model::{AnalysisReport, AnalysisRow, Unit};
-use header::parse_header;
+use crate::extraction::{PageContent, LOW_CONFIDENCE};
+use crate::i18n::Locale;
+use crate::model::{AnalysisReport, AnalysisRow, AnalysisValue, ReportHeader, Unit};
 use normalize::normalize_substance;
-use values::parse_value;
+use values::{parse_uncertainty, parse_value};
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ParseWarning {
     pub section_index: usize,
     pub sample_id: Option<String>,
     pub reason: String,
+    /// What the warning is about; a skipped section unless set.
+    #[serde(default, skip_serializing_if = "ParseWarningKind::is_skipped_section")]
+    pub kind: ParseWarningKind,
+}
+
+/// Kind of [`ParseWarning`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum ParseWarningKind {
+    /// A section or sample that yielded no report; `reason` says why.
+    #[default]
+    SkippedSection,
+    /// An OCR'd line the recogniser was unsure of; `reason` is the line.
+    LowOcrConfidence { page: usize, confidence: f32 },
+    /// A header date in pre-parsed JSON that isn't a date and was read as
+    /// missing; `reason` is the text.
+    UnreadableDate,
+}
+
+impl ParseWarningKind {
+    fn is_skipped_section(&self) -> bool {
+        *self == ParseWarningKind::SkippedSection
+    }
+}
+
+impl ParseWarning {
+    /// The warning as shown to users.
+    pub fn message(&self, locale: Locale) -> String {
+        match (self.kind, &self.sample_id) {
+            (ParseWarningKind::LowOcrConfidence { page, confidence }, _) => locale.format(
+                "warning.low_ocr_confidence",
+                &[
+                    ("confidence", &confidence.round()),
+                    ("page", &page),
+                    ("line", &self.reason),
+                ],
+            ),
+            (ParseWarningKind::UnreadableDate, _) => locale.format(
+                "warning.unreadable_date",
+                &[("section", &self.section_index), ("text", &self.reason)],
+            ),
+            (ParseWarningKind::SkippedSection, Some(id)) => locale.format(
+                "warning.skipped_sample",
+                &[
+                    ("sample", id),
+                    ("section", &self.section_index),
+                    ("reason", &self.reason),
+                ],
+            ),
+            (ParseWarningKind::SkippedSection, None) => locale.format(
+                "warning.skipped_section",
+                &[("section", &self.section_index), ("reason", &self.reason)],
+            ),
+        }
+    }
 }
 
 /// A line that looked like substance data but could not be fully parsed.
@@ -21,6 +80,9 @@ pub struct ParseWarning {
 pub struct SkippedLine {
     pub line_text: String,
     pub reason: String,
+    /// Index into `ParsedReports::reports` of the sample the line belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report_index: Option<usize>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -30,10 +92,44 @@ pub struct ParsedReports {
     pub skipped_lines: Vec<SkippedLine>,
 }
 
+/// A laboratory's report layout.
+///
+/// Each lab has one implementation in its own module, listed in [`PARSERS`].
+/// `parse_reports` asks every parser to [`detect`](ReportParser::detect) the
+/// document and uses the one with the highest score.
+pub trait ReportParser: Send + Sync {
+    /// Short lowercase name used in messages, e.g. "eurofins".
+    fn name(&self) -> &'static str;
+
+    /// How well the document's lines match this layout.
+    fn detect(&self, lines: &[&str]) -> Detection;
+
+    /// Parse the whole document, one report per sample.
+    fn parse(&self, lines: &[&str]) -> Result<ParsedReports, SondaError>;
+}
+
+/// Result of [`ReportParser::detect`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Detection {
+    /// The layout matches; higher scores win.
+    Match(u32),
+    /// Not this lab's layout, and why.
+    Declined(String),
+}
+
+impl Detection {
+    pub fn declined(reason: &str) -> Detection {
+        Detection::Declined(reason.to_string())
+    }
+}
+
+/// All report parsers, tried in order. Ties go to the earlier entry.
+pub static PARSERS: &[&dyn ReportParser] = &[&eurofins::Eurofins, &als::Als, &synlab::Synlab];
+
 /// Parse extracted page content into one or more AnalysisReports.
 ///
-/// Multi-sample PDFs (where multiple "Analysrapport" sections appear)
-/// are split into separate reports, each classified independently.
+/// The parser is chosen by [`select_parser`]. Multi-sample PDFs are split
+/// into separate reports, each classified independently.
 pub fn parse_reports(pages: &[PageContent]) -> Result<ParsedReports, SondaError> {
     let all_lines: Vec<&str> = pages
         .iter()
@@ -46,97 +142,141 @@ pub fn parse_reports(pages: &[PageContent]) -> Result<ParsedReports, SondaError>
         ));
     }
 
-    // Split into sections on "Analysrapport" boundaries
-    let sections = split_into_sections(&all_lines);
+    let mut parsed = select_parser(&all_lines)?.parse(&all_lines)?;
+    parsed.warnings.extend(low_confidence_warnings(pages));
+    Ok(parsed)
+}
+
+/// Flag OCR'd lines the recogniser was unsure of, so they get a second look.
+fn low_confidence_warnings(pages: &[PageContent]) -> Vec<ParseWarning> {
+    pages
+        .iter()
+        .flat_map(|p| &p.line_spans)
+        .filter(|span| !span.text.trim().is_empty())
+        .filter_map(|span| {
+            let confidence = span.confidence.filter(|c| *c < LOW_CONFIDENCE)?;
+            Some(ParseWarning {
+                section_index: 0,
+                sample_id: None,
+                reason: span.text.trim().to_string(),
+                kind: ParseWarningKind::LowOcrConfidence {
+                    page: span.page_number,
+                    confidence,
+                },
+            })
+        })
+        .collect()
+}
 
-    let mut reports = Vec::new();
-    let mut warnings = Vec::new();
-    let mut skipped_lines: Vec<SkippedLine> = Vec::new();
+/// The best-matching parser for a document.
+///
+/// Fails with `UnsupportedReport` listing every parser and why it declined.
+pub fn select_parser(lines: &[&str]) -> Result<&'static dyn ReportParser, SondaError> {
+    let mut best: Option<(u32, &'static dyn ReportParser)> = None;
+    let mut declined = Vec::new();
+    for &parser in PARSERS {
+        match parser.detect(lines) {
+            Detection::Match(score) => {
+                if best.is_none_or(|(best_score, _)| score > best_score) {
+                    best = Some((score, parser));
+                }
+            }
+            Detection::Declined(reason) => declined.push(format!("{}: {}", parser.name(), reason)),
+        }
+    }
+    best.map(|(_, parser)| parser).ok_or_else(|| {
+        SondaError::UnsupportedReport(format!(
+            "no parser recognised the report ({})",
+            declined.join("; ")
+        ))
+    })
+}
+
+/// Parse each section of a document into its sample reports, usually one.
+///
+/// A section that fails to parse becomes a warning naming it by
+/// `sample_id` (if found), and parsing continues with the next one. So does
+/// a sample of a multi-sample section that has no values, keeping its
+/// skipped lines. Fails only if no section yields a report.
+fn collect_sections(
+    sections: &[Vec<&str>],
+    parse_section: impl Fn(&[&str]) -> Result<Vec<(AnalysisReport, Vec<SkippedLine>)>, SondaError>,
+    sample_id: impl Fn(&[&str]) -> Option<String>,
+) -> Result<ParsedReports, SondaError> {
+    let mut parsed = ParsedReports {
+        reports: Vec::new(),
+        warnings: Vec::new(),
+        skipped_lines: Vec::new(),
+    };
     for (idx, section) in sections.iter().enumerate() {
         match parse_section(section) {
-            Ok((report, section_skipped)) => {
-                skipped_lines.extend(section_skipped);
-                reports.push(report);
+            Ok(section_reports) => {
+                for (report, skipped) in section_reports {
+                    push_sample(
+                        &mut parsed,
+                        idx,
+                        report.header,
+                        report.rows,
+                        skipped,
+                        "no analysis values found for sample",
+                    );
+                }
             }
             Err(err) => {
                 // Keep parsing remaining sections and surface explicit warnings.
-                let header_lines: Vec<&str> = section.iter().take(30).copied().collect();
-                let header = parse_header(&header_lines);
-                let sample_id = header.sample_id.or(header.lab_report_id);
                 let reason = match err {
                     SondaError::ParseError(msg) => msg,
                     other => other.to_string(),
                 };
-                warnings.push(ParseWarning {
+                parsed.warnings.push(ParseWarning {
                     section_index: idx + 1,
-                    sample_id,
+                    sample_id: sample_id(section),
                     reason,
+                    kind: ParseWarningKind::SkippedSection,
                 });
-                continue;
             }
         }
     }
 
-    if reports.is_empty() {
+    if parsed.reports.is_empty() {
         return Err(SondaError::ParseError(
             "no analysis values found in report".into(),
         ));
     }
 
-    Ok(ParsedReports {
-        reports,
-        warnings,
-        skipped_lines,
-    })
-}
-
-/// Split lines into sections, each starting at an "Analysrapport" header.
-/// If no "Analysrapport" header is found, treat the whole document as one section.
-fn split_into_sections<'a>(lines: &[&'a str]) -> Vec<Vec<&'a str>> {
-    let mut sections = Vec::new();
-    let mut current: Vec<&str> = Vec::new();
-    let mut found_header = false;
-
-    for &line in lines {
-        let trimmed = line.trim();
-        if trimmed == "Analysrapport" {
-            if found_header && !current.is_empty() {
-                sections.push(current);
-                current = Vec::new();
-            }
-            found_header = true;
-        }
-        current.push(line);
-    }
-
-    if !current.is_empty() {
-        if found_header {
-            sections.push(current);
-        } else {
-            // No "Analysrapport" header found — treat as single section
-            sections.push(current);
-        }
-    }
-
-    sections
+    Ok(parsed)
 }
 
-/// Parse a single section (one sample) into an AnalysisReport.
-fn parse_section(lines: &[&str]) -> Result<(AnalysisReport, Vec<SkippedLine>), SondaError> {
-    // Parse header from the first ~30 lines of this section
-    let header_lines: Vec<&str> = lines.iter().take(30).copied().collect();
-    let header = parse_header(&header_lines);
-
-    // Find and parse table rows
-    let (rows, skipped) = parse_table_rows(lines)?;
-
-    if rows.is_empty() {
-        return Err(SondaError::ParseError(
-            "no analysis values found in section".into(),
-        ));
-    }
-
-    Ok((AnalysisReport { header, rows }, skipped))
+/// Add one sample to `parsed`: a report if it has rows, otherwise a warning
+/// with `empty_reason`. Its skipped lines are kept either way, pointing at the
+/// report or, when there is none, at no report. `index` is the 0-based
+/// position of the sample, or of the section holding it, in the document.
+pub(crate) fn push_sample(
+    parsed: &mut ParsedReports,
+    index: usize,
+    header: ReportHeader,
+    rows: Vec<AnalysisRow>,
+    skipped: Vec<SkippedLine>,
+    empty_reason: &str,
+) {
+    let report_index = if rows.is_empty() {
+        parsed.warnings.push(ParseWarning {
+            section_index: index + 1,
+            sample_id: header.sample_id,
+            reason: empty_reason.to_string(),
+            kind: ParseWarningKind::SkippedSection,
+        });
+        None
+    } else {
+        parsed.reports.push(AnalysisReport { header, rows });
+        Some(parsed.reports.len() - 1)
+    };
+    parsed
+        .skipped_lines
+        .extend(skipped.into_iter().map(|skip| SkippedLine {
+            report_index,
+            ..skip
+        }));
 }
 
 /// Parse table rows from text lines.
@@ -212,11 +352,21 @@ fn try_parse_row(line: &str) -> Result<Option<AnalysisRow>, SkippedLine> {
                     })
                     .unwrap_or_default();
 
+                let uncertainty = match value {
+                    AnalysisValue::Measured(v) => {
+                        segments.iter().find_map(|s| parse_uncertainty(s, v))
+                    }
+                    AnalysisValue::BelowDetection(_) => None,
+                };
+
                 return Ok(Some(AnalysisRow {
                     raw_name: name.to_string(),
                     normalized_name: normalized,
                     value,
                     unit,
+                    uncertainty,
+                    loq: None,
+                    source_line: Some(line.trim().to_string()),
                 }));
             }
             Ok(None) => {}
@@ -230,6 +380,7 @@ fn try_parse_row(line: &str) -> Result<Option<AnalysisRow>, SkippedLine> {
     Err(SkippedLine {
         line_text: line.to_string(),
         reason,
+        report_index: None,
     })
 }
 
@@ -294,6 +445,54 @@ mod tests {
     use crate::model::AnalysisValue;
     use rust_decimal_macros::dec;
 
+    #[test]
+    fn test_push_sample_warns_about_empty_samples() {
+        let mut parsed = ParsedReports {
+            reports: vec![],
+            warnings: vec![],
+            skipped_lines: vec![],
+        };
+        let skip = |text: &str| SkippedLine {
+            line_text: text.into(),
+            reason: "n.b.".into(),
+            report_index: None,
+        };
+        let header = |id: &str| ReportHeader {
+            sample_id: Some(id.into()),
+            ..Default::default()
+        };
+        let row = try_parse_row("Bly (Pb)     12     mg/kg TS")
+            .unwrap()
+            .unwrap();
+
+        push_sample(
+            &mut parsed,
+            0,
+            header("P1"),
+            vec![],
+            vec![skip("a")],
+            "empty",
+        );
+        push_sample(
+            &mut parsed,
+            1,
+            header("P2"),
+            vec![row],
+            vec![skip("b")],
+            "empty",
+        );
+
+        assert_eq!(parsed.reports.len(), 1);
+        assert_eq!(parsed.warnings[0].section_index, 1);
+        assert_eq!(parsed.warnings[0].sample_id.as_deref(), Some("P1"));
+        let indices: Vec<_> = parsed
+            .skipped_lines
+            .iter()
+            .map(|s| s.report_index)
+            .collect();
+        assert_eq!(indices, [None, Some(0)]);
+    }
+
     #[test]
     fn test_split_by_whitespace_gaps() {
         let segments = split_by_whitespace_gaps("Arsenik (As)     68     mg/kg TS");
@@ -308,6 +507,10 @@ mod tests {
             .unwrap();
         assert_eq!(row.normalized_name, "arsenik");
         assert_eq!(row.value, AnalysisValue::Measured(dec!(68)));
+        assert_eq!(
+            row.source_line.as_deref(),
+            Some("Arsenik (As)     68     mg/kg TS")
+        );
     }
 
     #[test]
@@ -319,6 +522,15 @@ mod tests {
         assert_eq!(row.value, AnalysisValue::BelowDetection(dec!(0.030)));
     }
 
+    #[test]
+    fn test_try_parse_row_with_uncertainty() {
+        let row = try_parse_row("Bly (Pb)     170     mg/kg TS     ± 34")
+            .unwrap()
+            .unwrap();
+        assert_eq!(row.value, AnalysisValue::Measured(dec!(170)));
+        assert_eq!(row.uncertainty, Some(dec!(34)));
+    }
+
     #[test]
     fn test_header_line_skipped() {
         assert!(try_parse_row("Analys     Resultat     Enhet")
@@ -335,33 +547,15 @@ mod tests {
     }
 
     #[test]
-    fn test_split_into_sections_single() {
-        let lines = vec!["Header", "Analysrapport", "Data line 1", "Data line 2"];
-        let sections = split_into_sections(&lines);
-        assert_eq!(sections.len(), 1);
-    }
-
-    #[test]
-    fn test_split_into_sections_multi() {
-        let lines = vec![
-            "Header",
-            "Analysrapport",
-            "Provnummer: 001",
-            "Data 1",
-            "Analysrapport",
-            "Provnummer: 002",
-            "Data 2",
-        ];
-        let sections = split_into_sections(&lines);
-        assert_eq!(sections.len(), 2);
-        assert!(sections[0].contains(&"Provnummer: 001"));
-        assert!(sections[1].contains(&"Provnummer: 002"));
-    }
-
-    #[test]
-    fn test_split_into_sections_no_header() {
-        let lines = vec!["Line 1", "Line 2", "Line 3"];
-        let sections = split_into_sections(&lines);
-        assert_eq!(sections.len(), 1);
+    fn test_unknown_lab_lists_declined_parsers() {
+        let pages = vec![PageContent {
+            page_number: 1,
+            lines: vec!["Okänt Lab AB".into(), "Bly (Pb)     12     mg/kg TS".into()],
+            line_spans: vec![],
+        }];
+        let err = parse_reports(&pages).unwrap_err();
+        assert!(matches!(err, SondaError::UnsupportedReport(_)));
+        assert!(err.to_string().contains("eurofins: no 'Eurofins'"));
+        assert!(err.to_string().contains("als: no 'ALS Scandinavia'"));
     }
 }
@@ -217,10 +217,27 @@ static ALIASES: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(||
     m.insert("indeno_123cd_pyren", "indeno_1_2_3_cd_pyren");
     m.insert("indeno_123_cd_pyren", "indeno_1_2_3_cd_pyren");
 
+    // ALS spellings: "bens(a)pyren", "dibens(ah)antracen", "PAH, summa L"
+    m.insert("bens_a_antracen", "benso_a_antracen");
+    m.insert("bens_b_fluoranten", "benso_b_fluoranten");
+    m.insert("bens_k_fluoranten", "benso_k_fluoranten");
+    m.insert("bens_b_k_fluoranten", "benso_b_k_fluoranten");
+    m.insert("bens_a_pyren", "benso_a_pyren");
+    m.insert("dibens_ah_antracen", "dibenso_a_h_antracen");
+    m.insert("dibens_a_h_antracen", "dibenso_a_h_antracen");
+    m.insert("bens_ghi_perylen", "benso_ghi_perylen");
+    m.insert("bens_g_h_i_perylen", "benso_ghi_perylen");
+    m.insert("pah_summa_l", "pah_l");
+    m.insert("pah_summa_m", "pah_m");
+    m.insert("pah_summa_h", "pah_h");
+    m.insert("pah_summa_16", "pah_16");
+    m.insert("summa_xylener", "xylener");
+
     // Dry substance
     m.insert("ts", "ts");
     m.insert("torrsubstans", "ts");
     m.insert("ts_halt", "ts");
+    m.insert("ts_105_c", "ts");
 
     m
 });
@@ -301,6 +318,18 @@ mod tests {
         assert_eq!(normalize_substance("Aromater >C16-C35"), "aromater_c16_c35");
     }
 
+    #[test]
+    fn test_als_spellings() {
+        assert_eq!(normalize_substance("Pb"), "bly");
+        assert_eq!(normalize_substance("PAH, summa M"), "pah_m");
+        assert_eq!(normalize_substance("bens(a)pyren"), "benso_a_pyren");
+        assert_eq!(
+            normalize_substance("dibens(ah)antracen"),
+            "dibenso_a_h_antracen"
+        );
+        assert_eq!(normalize_substance("TS_105°C"), "ts");
+    }
+
     #[test]
     fn test_whitespace_handling() {
         assert_eq!(normalize_substance("  Bly  "), "bly");
@@ -0,0 +1,210 @@
+//! SYNLAB Analytics & Services (formerly Bergteknik).
+//!
+//! One block per sample starting at "Provnr", with the customer's label as
+//! "Kundens provbeteckning". Results follow a "Komponent  Resultat
+//! Rapp.gräns  Enhet" table header. Values not detected are written
+//! "ej påv." and take their detection limit from the Rapp.gräns column.
+
+use super::header::{
+    extract_after_label, is_supersedes_line, read_revision_fields, read_sampling_fields,
+};
+use super::normalize::normalize_substance;
+use super::values::parse_value;
+use super::{
+    collect_sections, split_by_whitespace_gaps, try_parse_row, Detection, ParsedReports,
+    ReportParser, SkippedLine,
+};
+use crate::error::SondaError;
+use crate::model::{AnalysisReport, AnalysisRow, AnalysisValue, Matrix, ReportHeader, Unit};
+
+pub struct Synlab;
+
+/// Result cell of a value below the reporting limit.
+const NOT_DETECTED: &[&str] = &["ej påv.", "ej påv", "ej påvisad", "n.d."];
+
+impl ReportParser for Synlab {
+    fn name(&self) -> &'static str {
+        "synlab"
+    }
+
+    fn detect(&self, lines: &[&str]) -> Detection {
+        if lines.iter().any(|l| {
+            let lower = l.to_lowercase();
+            lower.contains("synlab") || lower.contains("bergteknik")
+        }) {
+            Detection::Match(100)
+        } else {
+            Detection::declined("no 'SYNLAB' or 'Bergteknik' in the report text")
+        }
+    }
+
+    fn parse(&self, lines: &[&str]) -> Result<ParsedReports, SondaError> {
+        let start = lines
+            .iter()
+            .position(|l| is_sample_start(l))
+            .ok_or_else(|| SondaError::ParseError("no 'Provnr' sample blocks".into()))?;
+        let shared = parse_report_header(&lines[..start]);
+
+        let mut blocks: Vec<Vec<&str>> = Vec::new();
+        for &line in &lines[start..] {
+            if is_sample_start(line) {
+                blocks.push(Vec::new());
+            }
+            if let Some(block) = blocks.last_mut() {
+                block.push(line);
+            }
+        }
+
+        collect_sections(
+            &blocks,
+            |block| parse_block(block, &shared).map(|report| vec![report]),
+            |block| parse_sample_header(block, &shared).sample_id,
+        )
+    }
+}
+
+fn is_sample_start(line: &str) -> bool {
+    let lower = line.trim().to_lowercase();
+    lower.starts_with("provnr") || lower.starts_with("provnummer")
+}
+
+/// Fields shared by every sample: report number, revision, project and lab.
+fn parse_report_header(lines: &[&str]) -> ReportHeader {
+    let mut header = ReportHeader {
+        lab: Some("Synlab".to_string()),
+        ..Default::default()
+    };
+    for line in lines {
+        if header.lab_report_id.is_none() && !is_supersedes_line(line) {
+            header.lab_report_id = extract_after_label(line, "rapportnummer");
+        }
+        read_revision_fields(line, &mut header);
+        if header.project.is_none() {
+            header.project = extract_after_label(line, "projekt");
+        }
+    }
+    header
+}
+
+/// Header of one sample block, falling back to the report header.
+fn parse_sample_header(block: &[&str], shared: &ReportHeader) -> ReportHeader {
+    let mut header = shared.clone();
+    let mut customer_label = None;
+    let mut lab_number = None;
+    for line in block.iter().take_while(|l| !is_table_header(l)) {
+        if customer_label.is_none() {
+            customer_label = extract_after_label(line, "kundens provbeteckning");
+        }
+        if lab_number.is_none() {
+            lab_number = extract_after_label(line, "provnr")
+                .or_else(|| extract_after_label(line, "provnummer"));
+        }
+        read_sampling_fields(line, &mut header);
+        if header.matrix.is_none() {
+            header.matrix =
+                extract_after_label(line, "provtyp").and_then(|val| Matrix::from_str_loose(&val));
+        }
+    }
+    header.sample_id = customer_label.or(lab_number);
+    header
+}
+
+/// The "Komponent  Resultat  ..." line above each results table.
+fn is_table_header(line: &str) -> bool {
+    let lower = line.trim().to_lowercase();
+    lower.starts_with("komponent") && lower.contains("resultat")
+}
+
+fn parse_block(
+    block: &[&str],
+    shared: &ReportHeader,
+) -> Result<(AnalysisReport, Vec<SkippedLine>), SondaError> {
+    let header = parse_sample_header(block, shared);
+
+    let table_start = block
+        .iter()
+        .position(|l| is_table_header(l))
+        .ok_or_else(|| SondaError::ParseError("no results table in sample block".into()))?;
+
+    let mut rows = Vec::new();
+    let mut skipped = Vec::new();
+    for line in &block[table_start + 1..] {
+        let line = line.trim();
+        // Table headers and letterheads repeat after page breaks.
+        if line.len() < 3 || is_table_header(line) || line.to_lowercase().contains("synlab") {
+            continue;
+        }
+        let parsed = match not_detected_row(line) {
+            Some(row) => Ok(Some(row)),
+            None => try_parse_row(line),
+        };
+        match parsed {
+            Ok(Some(row)) => rows.push(row),
+            Ok(None) => {}
+            Err(skip) => skipped.push(skip),
+        }
+    }
+
+    if rows.is_empty() {
+        return Err(SondaError::ParseError(
+            "no analysis values found in section".into(),
+        ));
+    }
+
+    Ok((AnalysisReport { header, rows }, skipped))
+}
+
+/// A row whose result is "ej påv.", as below the Rapp.gräns value.
+fn not_detected_row(line: &str) -> Option<AnalysisRow> {
+    let segments = split_by_whitespace_gaps(line);
+    let result = segments.get(1)?.trim().to_lowercase();
+    if !NOT_DETECTED.contains(&result.as_str()) {
+        return None;
+    }
+    let limit = match parse_value(segments.get(2)?) {
+        Ok(Some(value)) => value.numeric(),
+        _ => return None,
+    };
+    let name = segments[0].trim();
+    Some(AnalysisRow {
+        raw_name: name.to_string(),
+        normalized_name: normalize_substance(name),
+        value: AnalysisValue::BelowDetection(limit),
+        unit: segments
+            .iter()
+            .find(|s| s.to_lowercase().contains("mg/kg"))
+            .map(|s| Unit::from_str_loose(s))
+            .unwrap_or_default(),
+        uncertainty: None,
+        loq: None,
+        source_line: Some(line.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_not_detected_uses_reporting_limit() {
+        let row = not_detected_row("Kadmium, Cd        ej påv.     0,20     mg/kg TS").unwrap();
+        assert_eq!(row.normalized_name, "kadmium");
+        assert_eq!(row.value, AnalysisValue::BelowDetection(dec!(0.20)));
+
+        assert!(not_detected_row("Bly, Pb        35     5,0     mg/kg TS").is_none());
+    }
+
+    #[test]
+    fn test_customer_label_preferred_over_lab_number() {
+        let header = parse_sample_header(
+            &[
+                "Provnr: 24-118532-01          Kundens provbeteckning: P3 0-0,3",
+                "Provtyp: Asfalt",
+            ],
+            &ReportHeader::default(),
+        );
+        assert_eq!(header.sample_id.as_deref(), Some("P3 0-0,3"));
+        assert_eq!(header.matrix, Some(Matrix::Asfalt));
+    }
+}
@@ -30,6 +30,20 @@ pub fn parse_value(s: &str) -> Result<Option<AnalysisValue>, SondaError> {
     Ok(Some(AnalysisValue::Measured(decimal)))
 }
 
+/// Parse a measurement uncertainty such as "± 24" or "±20%".
+///
+/// Relative uncertainties are converted to absolute using `value`. Returns
+/// `None` for anything that isn't a ± marker followed by a number.
+pub fn parse_uncertainty(s: &str, value: Decimal) -> Option<Decimal> {
+    let rest = s.trim().strip_prefix('±')?.trim();
+    match rest.strip_suffix('%') {
+        Some(pct) => parse_decimal(pct)
+            .ok()
+            .map(|p| value * p / Decimal::ONE_HUNDRED),
+        None => parse_decimal(rest).ok(),
+    }
+}
+
 /// Parse a decimal value, handling Swedish comma notation.
 fn parse_decimal(s: &str) -> Result<Decimal, SondaError> {
     let s = s.trim();
@@ -105,4 +119,12 @@ mod tests {
     fn test_invalid_returns_error() {
         assert!(parse_value("abc").is_err());
     }
+
+    #[test]
+    fn test_uncertainty_absolute_and_relative() {
+        assert_eq!(parse_uncertainty("± 24", dec!(120)), Some(dec!(24)));
+        assert_eq!(parse_uncertainty("±20%", dec!(120)), Some(dec!(24)));
+        assert_eq!(parse_uncertainty("± 2,5", dec!(10)), Some(dec!(2.5)));
+        assert_eq!(parse_uncertainty("24", dec!(120)), None);
+    }
 }
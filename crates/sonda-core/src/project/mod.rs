@@ -0,0 +1,564 @@
+pub mod schema;
+
+use crate::classify::outcome::{ClassificationResult, ParseWarning};
+use crate::disposal::schema::SampleQuantities;
+use crate::disposal::{self, CostEstimate};
+use crate::error::SondaError;
+use crate::extraction::PdfExtractor;
+use crate::input::{self, InputFormat};
+use crate::model::Coordinates;
+use crate::parsing::ParsedReports;
+use crate::revision::{find_superseded, superseded_message, ReportVersion};
+use crate::rules::builtin;
+use crate::rules::schema::RuleSetDef;
+use crate::ClassifyOptions;
+use rust_decimal::Decimal;
+use schema::ProjectManifest;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Parsed reports cached per input file, keyed by content hash.
+///
+/// Re-running a project only re-parses inputs whose content changed, or all
+/// inputs if the cache was written by a different sonda version.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParseCache {
+    pub sonda_version: String,
+    /// Input path (as written in the manifest) -> cached parse.
+    pub entries: BTreeMap<String, CacheEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// SHA-256 of the input file content (hex).
+    pub sha256: String,
+    pub format: InputFormat,
+    pub parsed: ParsedReports,
+}
+
+impl Default for ParseCache {
+    fn default() -> Self {
+        Self {
+            sonda_version: env!("CARGO_PKG_VERSION").to_string(),
+            entries: BTreeMap::new(),
+        }
+    }
+}
+
+impl ParseCache {
+    /// Load a cache file, returning an empty cache if it is missing,
+    /// unreadable, or from another sonda version.
+    pub fn load(path: &Path) -> ParseCache {
+        std::fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<ParseCache>(&bytes).ok())
+            .filter(|c| c.sonda_version == env!("CARGO_PKG_VERSION"))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SondaError> {
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+/// What happened to one input during a project run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputState {
+    /// Parsed because it was new or its content changed.
+    Parsed,
+    /// Unchanged since the last run; cached reports were reused.
+    Cached,
+    /// Could not be read or parsed.
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputStatus {
+    pub path: PathBuf,
+    pub state: InputState,
+    /// Sample IDs found in this input.
+    pub sample_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Sampling metadata attached to a classified sample.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SampleInfo {
+    pub sample_id: String,
+    /// Input file the sample was read from.
+    pub source: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lot: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth_from_m: Option<Decimal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth_to_m: Option<Decimal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coordinates: Option<Coordinates>,
+}
+
+/// Combined result of a project run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectResult {
+    pub name: String,
+    pub inputs: Vec<InputStatus>,
+    pub sample_info: Vec<SampleInfo>,
+    pub classification: ClassificationResult,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<CostEstimate>,
+}
+
+/// Load a project manifest from a JSON file.
+pub fn load_manifest(path: &Path) -> Result<ProjectManifest, SondaError> {
+    let content = std::fs::read_to_string(path)?;
+    let manifest: ProjectManifest = serde_json::from_str(&content)
+        .map_err(|e| SondaError::ProjectInvalid(format!("{}: {}", path.display(), e)))?;
+    validate_manifest(&manifest)?;
+    Ok(manifest)
+}
+
+/// Validate that a manifest is well-formed.
+pub fn validate_manifest(manifest: &ProjectManifest) -> Result<(), SondaError> {
+    if manifest.inputs.is_empty() {
+        return Err(SondaError::ProjectInvalid(
+            "inputs must not be empty".into(),
+        ));
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    for sample in &manifest.samples {
+        if !seen.insert(sample.sample_id.as_str()) {
+            return Err(SondaError::ProjectInvalid(format!(
+                "sample '{}' is listed more than once",
+                sample.sample_id
+            )));
+        }
+        if let Some(ref lot) = sample.lot {
+            if !manifest.lots.is_empty() && !manifest.lots.iter().any(|l| l.id == *lot) {
+                return Err(SondaError::ProjectInvalid(format!(
+                    "sample '{}' references unknown lot '{}'",
+                    sample.sample_id, lot
+                )));
+            }
+        }
+        if let (Some(from), Some(to)) = (sample.depth_from_m, sample.depth_to_m) {
+            if from > to {
+                return Err(SondaError::ProjectInvalid(format!(
+                    "sample '{}' has depth_from_m greater than depth_to_m",
+                    sample.sample_id
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Default cache location for a manifest: `<manifest>.cache.json`.
+pub fn default_cache_path(manifest_path: &Path) -> PathBuf {
+    let mut name = manifest_path
+        .file_stem()
+        .map(|s| s.to_os_string())
+        .unwrap_or_default();
+    name.push(".cache.json");
+    manifest_path.with_file_name(name)
+}
+
+/// Load the manifest's presets and rule files.
+pub fn load_rulesets(
+    manifest: &ProjectManifest,
+    base_dir: &Path,
+) -> Result<(Vec<RuleSetDef>, ClassifyOptions), SondaError> {
+    let mut rulesets = Vec::new();
+    let mut options = ClassifyOptions {
+        locale: manifest.locale,
+        ..Default::default()
+    };
+
+    let presets: Vec<String> = if manifest.presets.is_empty() && manifest.rules.is_empty() {
+        builtin::PRESETS.iter().map(|s| s.to_string()).collect()
+    } else {
+        manifest.presets.clone()
+    };
+
+    for preset in &presets {
+        if builtin::is_hp_preset(preset) {
+            options.include_hp = true;
+        } else {
+            rulesets.push(builtin::load_preset(preset)?);
+        }
+    }
+    for path in &manifest.rules {
+        rulesets.push(crate::rules::load_ruleset(&base_dir.join(path))?);
+    }
+
+    Ok((rulesets, options))
+}
+
+/// Parse every input (reusing cached parses of unchanged files), classify all
+/// samples against the project's rulesets and attach sampling metadata.
+///
+/// Inputs that fail to parse are reported in `inputs` and as warnings; the
+/// remaining inputs are still classified.
+pub fn run_project(
+    manifest: &ProjectManifest,
+    base_dir: &Path,
+    extractor: &dyn PdfExtractor,
+    cache: &mut ParseCache,
+) -> Result<ProjectResult, SondaError> {
+    let (rulesets, options) = load_rulesets(manifest, base_dir)?;
+
+    let mut inputs = Vec::new();
+    let mut warnings = Vec::new();
+    let mut reports = Vec::new();
+    let mut sources = Vec::new();
+
+    for path in &manifest.inputs {
+        let key = path.to_string_lossy().to_string();
+        match parse_cached(&base_dir.join(path), &key, extractor, cache) {
+            Ok((parsed, state)) => {
+                for w in &parsed.warnings {
+                    warnings.push(ParseWarning {
+                        sample_id: w.sample_id.clone(),
+                        message: format!("{}: {}", path.display(), w.message(manifest.locale)),
+                    });
+                }
+                let sample_ids = parsed.reports.iter().map(report_sample_id).collect();
+                for report in &parsed.reports {
+                    reports.push(report.clone());
+                    sources.push(path.clone());
+                }
+                inputs.push(InputStatus {
+                    path: path.clone(),
+                    state,
+                    sample_ids,
+                    error: None,
+                });
+            }
+            Err(e) => {
+                cache.entries.remove(&key);
+                warnings.push(ParseWarning {
+                    sample_id: None,
+                    message: format!("{}: {}", path.display(), e),
+                });
+                inputs.push(InputStatus {
+                    path: path.clone(),
+                    state: InputState::Failed,
+                    sample_ids: vec![],
+                    error: Some(e.to_string()),
+                });
+            }
+        }
+    }
+
+    // Classify only the newest version of a reissued report.
+    let versions: Vec<ReportVersion> = reports.iter().map(ReportVersion::of_report).collect();
+    let superseded = find_superseded(&versions);
+    for s in &superseded {
+        warnings.push(ParseWarning {
+            sample_id: Some(s.sample_id.clone()),
+            message: format!(
+                "{}: {}",
+                sources[s.dropped].display(),
+                superseded_message(&versions, s, manifest.locale)
+            ),
+        });
+    }
+    let (reports, sources): (Vec<_>, Vec<_>) = reports
+        .into_iter()
+        .zip(sources)
+        .enumerate()
+        .filter(|(i, _)| !superseded.iter().any(|s| s.dropped == *i))
+        .map(|(_, pair)| pair)
+        .unzip();
+
+    let mut classification = crate::classify_reports(&reports, &rulesets, &options)?;
+    warnings.append(&mut classification.warnings);
+
+    let sample_info: Vec<SampleInfo> = classification
+        .samples
+        .iter()
+        .zip(reports.iter().zip(&sources))
+        .map(|(sample, (report, source))| {
+            let meta = manifest
+                .samples
+                .iter()
+                .find(|s| s.sample_id == sample.sample_id);
+            // The manifest wins over what the lab wrote on the report.
+            let depth = report.header.depth.as_ref();
+            SampleInfo {
+                sample_id: sample.sample_id.clone(),
+                source: source.clone(),
+                lot: meta.and_then(|m| m.lot.clone()),
+                depth_from_m: meta.and_then(|m| m.depth_from_m).or(depth.map(|d| d.from)),
+                depth_to_m: meta.and_then(|m| m.depth_to_m).or(depth.map(|d| d.to)),
+                coordinates: meta
+                    .and_then(|m| m.coordinates.clone())
+                    .or_else(|| report.header.coordinates.clone()),
+            }
+        })
+        .collect();
+
+    for meta in &manifest.samples {
+        if !sample_info.iter().any(|s| s.sample_id == meta.sample_id) {
+            warnings.push(ParseWarning {
+                sample_id: Some(meta.sample_id.clone()),
+                message: manifest.locale.format(
+                    "warning.manifest_sample_missing",
+                    &[("sample", &meta.sample_id)],
+                ),
+            });
+        }
+    }
+    classification.warnings = warnings;
+
+    let cost = match manifest.prices {
+        Some(ref prices_path) => {
+            let prices = disposal::load_price_list(&base_dir.join(prices_path))?;
+            let quantities = SampleQuantities {
+                default_density: manifest.default_density,
+                samples: manifest
+                    .samples
+                    .iter()
+                    .filter(|s| s.quantity.tonnes.is_some() || s.quantity.volume_m3.is_some())
+                    .map(|s| (s.sample_id.clone(), s.quantity.clone()))
+                    .collect(),
+            };
+            Some(disposal::estimate_costs(
+                &classification,
+                &quantities,
+                &prices,
+            )?)
+        }
+        None => None,
+    };
+
+    Ok(ProjectResult {
+        name: manifest.name.clone(),
+        inputs,
+        sample_info,
+        classification,
+        cost,
+    })
+}
+
+/// Report which inputs have changed since the cache was written, without parsing.
+pub fn input_states(
+    manifest: &ProjectManifest,
+    base_dir: &Path,
+    cache: &ParseCache,
+) -> Vec<InputStatus> {
+    manifest
+        .inputs
+        .iter()
+        .map(|path| {
+            let key = path.to_string_lossy().to_string();
+            match std::fs::read(base_dir.join(path)) {
+                Ok(bytes) => {
+                    let cached = cache
+                        .entries
+                        .get(&key)
+                        .filter(|e| e.sha256 == sha256_hex(&bytes));
+                    InputStatus {
+                        path: path.clone(),
+                        state: if cached.is_some() {
+                            InputState::Cached
+                        } else {
+                            InputState::Parsed
+                        },
+                        sample_ids: cached
+                            .map(|e| e.parsed.reports.iter().map(report_sample_id).collect())
+                            .unwrap_or_default(),
+                        error: None,
+                    }
+                }
+                Err(e) => InputStatus {
+                    path: path.clone(),
+                    state: InputState::Failed,
+                    sample_ids: vec![],
+                    error: Some(e.to_string()),
+                },
+            }
+        })
+        .collect()
+}
+
+/// Parse an input, reusing the cached result if its content is unchanged.
+fn parse_cached(
+    path: &Path,
+    key: &str,
+    extractor: &dyn PdfExtractor,
+    cache: &mut ParseCache,
+) -> Result<(ParsedReports, InputState), SondaError> {
+    let bytes = std::fs::read(path)?;
+    let hash = sha256_hex(&bytes);
+
+    if let Some(entry) = cache.entries.get(key) {
+        if entry.sha256 == hash {
+            return Ok((entry.parsed.clone(), InputState::Cached));
+        }
+    }
+
+    let format = InputFormat::detect(path, &bytes);
+    let parsed = input::parse_input(format, &bytes, extractor)?;
+    cache.entries.insert(
+        key.to_string(),
+        CacheEntry {
+            sha256: hash,
+            format,
+            parsed: parsed.clone(),
+        },
+    );
+    Ok((parsed, InputState::Parsed))
+}
+
+fn report_sample_id(report: &crate::model::AnalysisReport) -> String {
+    report
+        .header
+        .sample_id
+        .clone()
+        .or_else(|| report.header.lab_report_id.clone())
+        .unwrap_or_else(|| "unknown".into())
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extraction::PageContent;
+
+    struct NoPdf;
+
+    impl PdfExtractor for NoPdf {
+        fn extract_pages(&self, _pdf_bytes: &[u8]) -> Result<Vec<PageContent>, SondaError> {
+            Err(SondaError::Extraction("not available in tests".into()))
+        }
+
+        fn backend_name(&self) -> &str {
+            "none"
+        }
+    }
+
+    const REPORTS_JSON: &str = r#"[
+        {
+            "header": { "lab": "Eurofins", "sample_id": "P1", "matrix": "jord" },
+            "rows": [
+                { "raw_name": "Bly (Pb)", "normalized_name": "bly",
+                  "value": { "Measured": "120" }, "unit": "mg/kg TS" }
+            ]
+        }
+    ]"#;
+
+    fn manifest(json: &str) -> ProjectManifest {
+        let m: ProjectManifest = serde_json::from_str(json).unwrap();
+        validate_manifest(&m).unwrap();
+        m
+    }
+
+    #[test]
+    fn test_run_project_reuses_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("p1.json"), REPORTS_JSON).unwrap();
+        let m = manifest(
+            r#"{
+                "name": "Site X",
+                "inputs": ["p1.json"],
+                "presets": ["nv"],
+                "lots": [{ "id": "A" }],
+                "samples": [{ "sample_id": "P1", "lot": "A", "depth_from_m": "0", "depth_to_m": "0.5" }]
+            }"#,
+        );
+        let mut cache = ParseCache::default();
+
+        let first = run_project(&m, dir.path(), &NoPdf, &mut cache).unwrap();
+        assert!(matches!(first.inputs[0].state, InputState::Parsed));
+        assert_eq!(first.sample_info[0].lot.as_deref(), Some("A"));
+        assert_eq!(
+            first.classification.samples[0].ruleset_results[0].overall_category,
+            "MKM"
+        );
+
+        let second = run_project(&m, dir.path(), &NoPdf, &mut cache).unwrap();
+        assert!(matches!(second.inputs[0].state, InputState::Cached));
+
+        std::fs::write(
+            dir.path().join("p1.json"),
+            REPORTS_JSON.replace("120", "20"),
+        )
+        .unwrap();
+        let third = run_project(&m, dir.path(), &NoPdf, &mut cache).unwrap();
+        assert!(matches!(third.inputs[0].state, InputState::Parsed));
+        assert_eq!(
+            third.classification.samples[0].ruleset_results[0].overall_category,
+            "KM"
+        );
+    }
+
+    #[test]
+    fn test_failed_input_does_not_abort() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("p1.json"), REPORTS_JSON).unwrap();
+        let m = manifest(r#"{ "name": "Site X", "inputs": ["p1.json", "missing.pdf"] }"#);
+        let result = run_project(&m, dir.path(), &NoPdf, &mut ParseCache::default()).unwrap();
+        assert!(matches!(result.inputs[1].state, InputState::Failed));
+        assert_eq!(result.classification.samples.len(), 1);
+        assert!(!result.classification.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_reissued_report_replaces_original() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = REPORTS_JSON.replace(
+            r#""sample_id": "P1","#,
+            r#""sample_id": "P1", "lab_report_id": "AR-001","#,
+        );
+        let reissued = original
+            .replace(r#""AR-001","#, r#""AR-007", "supersedes": "AR-001","#)
+            .replace("120", "20");
+        std::fs::write(dir.path().join("old.json"), original).unwrap();
+        std::fs::write(dir.path().join("new.json"), reissued).unwrap();
+        let m = manifest(r#"{ "name": "Site X", "inputs": ["old.json", "new.json"] }"#);
+
+        let result = run_project(&m, dir.path(), &NoPdf, &mut ParseCache::default()).unwrap();
+        let samples = &result.classification.samples;
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].lab_report_id.as_deref(), Some("AR-007"));
+        assert_eq!(result.sample_info[0].source, PathBuf::from("new.json"));
+        let warning = &result.classification.warnings[0];
+        assert!(warning.message.starts_with("old.json: "));
+        assert!(warning.message.contains("AR-007"));
+    }
+
+    #[test]
+    fn test_unknown_lot_rejected() {
+        let m: ProjectManifest = serde_json::from_str(
+            r#"{
+                "name": "Site X",
+                "inputs": ["a.pdf"],
+                "lots": [{ "id": "A" }],
+                "samples": [{ "sample_id": "P1", "lot": "B" }]
+            }"#,
+        )
+        .unwrap();
+        assert!(validate_manifest(&m).is_err());
+    }
+
+    #[test]
+    fn test_default_cache_path() {
+        assert_eq!(
+            default_cache_path(Path::new("/x/site.json")),
+            PathBuf::from("/x/site.cache.json")
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use crate::disposal::schema::SampleQuantity;
+use crate::i18n::Locale;
+use crate::model::Coordinates;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// A project manifest: every report, sample and ruleset of one site.
+///
+/// Relative paths are resolved against the directory containing the manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectManifest {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Report files (PDF, Sweco XLSX, Eurofins export or pre-parsed JSON).
+    pub inputs: Vec<PathBuf>,
+    /// Predefined rulesets to apply. Defaults to all presets if neither
+    /// `presets` nor `rules` is given.
+    #[serde(default)]
+    pub presets: Vec<String>,
+    /// Custom JSON rule files.
+    #[serde(default)]
+    pub rules: Vec<PathBuf>,
+    /// Sampling metadata, matched to reports by sample ID.
+    #[serde(default)]
+    pub samples: Vec<ProjectSample>,
+    /// Excavation lots the samples belong to.
+    #[serde(default)]
+    pub lots: Vec<ProjectLot>,
+    /// Price list for disposal cost estimation (optional).
+    #[serde(default)]
+    pub prices: Option<PathBuf>,
+    /// Density in tonnes/m³ for samples that only give a volume.
+    #[serde(default)]
+    pub default_density: Option<Decimal>,
+    /// Where to write the combined JSON result.
+    #[serde(default)]
+    pub output: Option<PathBuf>,
+    /// Parse cache location. Default: `<manifest>.cache.json` next to the manifest.
+    #[serde(default)]
+    pub cache: Option<PathBuf>,
+    /// Language of reason strings and warnings ("en" or "sv"). Default: "en".
+    #[serde(default)]
+    pub locale: Locale,
+}
+
+/// Sampling metadata for one sample.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectSample {
+    /// Sample ID as it appears in the report (Provmärkning or Provnummer).
+    pub sample_id: String,
+    /// Excavation lot this sample represents.
+    #[serde(default)]
+    pub lot: Option<String>,
+    /// Top of the sampled interval in metres below ground.
+    #[serde(default)]
+    pub depth_from_m: Option<Decimal>,
+    /// Bottom of the sampled interval in metres below ground.
+    #[serde(default)]
+    pub depth_to_m: Option<Decimal>,
+    #[serde(default)]
+    pub coordinates: Option<Coordinates>,
+    /// Mass or volume represented by the sample.
+    #[serde(flatten)]
+    pub quantity: SampleQuantity,
+}
+
+/// An excavation lot (schakt-/massaetapp).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectLot {
+    pub id: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
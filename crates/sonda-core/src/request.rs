@@ -0,0 +1,98 @@
+//! Classification request as a single JSON document.
+//!
+//! Used by front ends that cannot call [`crate::classify_reports`] with Rust
+//! values: `sonda serve` and the wasm bindings.
+
+use crate::adjust::Adjustments;
+use crate::classify::outcome::ClassificationResult;
+use crate::classify::sensitivity::Variation;
+use crate::error::SondaError;
+use crate::i18n::Locale;
+use crate::model::AnalysisReport;
+use crate::rules::schema::RuleSetDef;
+use crate::rules::{builtin, validate_ruleset};
+use crate::ClassifyOptions;
+use serde::Deserialize;
+
+/// Pre-parsed reports plus everything needed to classify them.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClassifyRequest {
+    pub reports: Vec<AnalysisReport>,
+    /// Predefined rulesets (nv, asfalt, fa). All presets if empty and no `rules`.
+    #[serde(default)]
+    pub presets: Vec<String>,
+    /// Custom rulesets, in the same format as rule files.
+    #[serde(default)]
+    pub rules: Vec<RuleSetDef>,
+    #[serde(default)]
+    pub lang: Option<Locale>,
+    /// A percentage ("10%") or "uncertainty", as for `--sensitivity`.
+    #[serde(default)]
+    pub sensitivity: Option<String>,
+    #[serde(default)]
+    pub adjustments: Adjustments,
+}
+
+impl ClassifyRequest {
+    /// Validate the request and classify its reports. `locale` applies when
+    /// the request has no `lang`.
+    pub fn classify(self, locale: Locale) -> Result<ClassificationResult, SondaError> {
+        for rs in &self.rules {
+            validate_ruleset(rs)?;
+        }
+        self.adjustments.validate()?;
+        let (rulesets, include_hp) = builtin::resolve(&self.presets, self.rules)?;
+        let options = ClassifyOptions {
+            include_hp,
+            locale: self.lang.unwrap_or(locale),
+            sensitivity: self
+                .sensitivity
+                .map(|s| s.parse::<Variation>())
+                .transpose()?,
+            adjustments: self.adjustments,
+            ..Default::default()
+        };
+        crate::classify_reports(&self.reports, &rulesets, &options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_with_presets_and_language() {
+        let request: ClassifyRequest = serde_json::from_str(
+            r#"{
+                "reports": [{
+                    "header": { "sample_id": "P1", "matrix": "jord" },
+                    "rows": [{
+                        "raw_name": "Bly (Pb)", "normalized_name": "bly",
+                        "value": { "Measured": "120" }, "unit": "mg/kg TS"
+                    }]
+                }],
+                "presets": ["nv"],
+                "lang": "sv",
+                "sensitivity": "10%"
+            }"#,
+        )
+        .unwrap();
+        let result = request.classify(Locale::En).unwrap();
+        let rs = &result.samples[0].ruleset_results[0];
+        assert_eq!(rs.overall_category, "MKM");
+        assert!(rs.overall_reason.starts_with("Avgörs av"));
+        assert!(rs.sensitivity.is_some());
+    }
+
+    #[test]
+    fn rejects_invalid_custom_ruleset() {
+        let request: ClassifyRequest = serde_json::from_str(
+            r#"{
+                "reports": [],
+                "rules": [{ "name": "x", "version": "1", "categories": [], "rules": [] }]
+            }"#,
+        )
+        .unwrap();
+        assert!(request.classify(Locale::En).is_err());
+    }
+}
@@ -0,0 +1,210 @@
+//! Reissued lab reports.
+//!
+//! A lab that corrects a report reissues it, either as a new revision under
+//! the same number or under a new number that names the report it replaces
+//! ("Ersätter rapport ..."). When both versions are among the inputs, only
+//! the newest should be classified. Reports of the same sample that don't
+//! refer to each other (e.g. metals and PAH ordered separately) are kept.
+
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+
+use crate::classify::outcome::SampleResult;
+use crate::i18n::Locale;
+use crate::model::AnalysisReport;
+
+/// The parts of a report header that tell its versions apart.
+#[derive(Debug, Clone, Copy)]
+pub struct ReportVersion<'a> {
+    pub sample_id: &'a str,
+    pub lab_report_id: Option<&'a str>,
+    pub revision: Option<u32>,
+    pub supersedes: Option<&'a str>,
+}
+
+impl<'a> ReportVersion<'a> {
+    pub fn of_report(report: &'a AnalysisReport) -> Self {
+        let header = &report.header;
+        ReportVersion {
+            sample_id: header
+                .sample_id
+                .as_deref()
+                .or(header.lab_report_id.as_deref())
+                .unwrap_or_default(),
+            lab_report_id: header.lab_report_id.as_deref(),
+            revision: header.revision,
+            supersedes: header.supersedes.as_deref(),
+        }
+    }
+
+    pub fn of_sample(sample: &'a SampleResult) -> Self {
+        ReportVersion {
+            sample_id: &sample.sample_id,
+            lab_report_id: sample.lab_report_id.as_deref(),
+            revision: sample.revision,
+            supersedes: sample.supersedes.as_deref(),
+        }
+    }
+
+    /// "AR-24-001" or "AR-24-001 rev. 2".
+    fn label(&self) -> String {
+        let id = self.lab_report_id.unwrap_or("?");
+        match self.revision {
+            Some(revision) => format!("{id} rev. {revision}"),
+            None => id.to_string(),
+        }
+    }
+}
+
+/// A sample's report replaced by a newer one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Superseded {
+    pub sample_id: String,
+    /// Index of the report that is kept.
+    pub kept: usize,
+    /// Index of the outdated report.
+    pub dropped: usize,
+    /// The two are the same report version, delivered twice.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub duplicate: bool,
+}
+
+/// Find reports replaced by a newer report of the same sample, in input
+/// order of the outdated report. A report is replaced when a later one
+/// names it as superseded, or reissues the same report number at a higher
+/// revision. The same number and revision twice keeps the first.
+pub fn find_superseded(versions: &[ReportVersion]) -> Vec<Superseded> {
+    let mut found: Vec<Superseded> = Vec::new();
+    for (i, a) in versions.iter().enumerate() {
+        for (j, b) in versions.iter().enumerate().skip(i + 1) {
+            if a.sample_id.is_empty() || !same_id(a.sample_id, b.sample_id) {
+                continue;
+            }
+            let (kept, dropped, duplicate) = if replaces(b, a) {
+                (j, i, false)
+            } else if replaces(a, b) {
+                (i, j, false)
+            } else if same_opt_id(a.lab_report_id, b.lab_report_id) {
+                match b.revision.unwrap_or(0).cmp(&a.revision.unwrap_or(0)) {
+                    Ordering::Greater => (j, i, false),
+                    Ordering::Less => (i, j, false),
+                    Ordering::Equal => (i, j, true),
+                }
+            } else {
+                continue;
+            };
+            if !found.iter().any(|s| s.dropped == dropped) {
+                found.push(Superseded {
+                    sample_id: a.sample_id.to_string(),
+                    kept,
+                    dropped,
+                    duplicate,
+                });
+            }
+        }
+    }
+    found.sort_by_key(|s| s.dropped);
+    found
+}
+
+/// Warning naming the report that replaced the dropped one.
+pub fn superseded_message(
+    versions: &[ReportVersion],
+    superseded: &Superseded,
+    locale: Locale,
+) -> String {
+    let old = versions[superseded.dropped].label();
+    if superseded.duplicate {
+        locale.format(
+            "warning.duplicate_report",
+            &[("sample", &superseded.sample_id), ("report", &old)],
+        )
+    } else {
+        locale.format(
+            "warning.superseded_report",
+            &[
+                ("sample", &superseded.sample_id),
+                ("old", &old),
+                ("new", &versions[superseded.kept].label()),
+            ],
+        )
+    }
+}
+
+/// Whether `newer` names `older` as the report it replaces.
+fn replaces(newer: &ReportVersion, older: &ReportVersion) -> bool {
+    same_opt_id(newer.supersedes, older.lab_report_id)
+}
+
+fn same_opt_id(a: Option<&str>, b: Option<&str>) -> bool {
+    matches!((a, b), (Some(a), Some(b)) if same_id(a, b))
+}
+
+/// Report ids and sample names compare without case or spaces.
+pub(crate) fn same_id(a: &str, b: &str) -> bool {
+    let key = |s: &str| {
+        s.chars()
+            .filter(|c| !c.is_whitespace())
+            .flat_map(char::to_lowercase)
+            .collect::<String>()
+    };
+    key(a) == key(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version<'a>(
+        sample_id: &'a str,
+        lab_report_id: &'a str,
+        revision: Option<u32>,
+        supersedes: Option<&'a str>,
+    ) -> ReportVersion<'a> {
+        ReportVersion {
+            sample_id,
+            lab_report_id: Some(lab_report_id),
+            revision,
+            supersedes,
+        }
+    }
+
+    #[test]
+    fn test_newer_report_replaces_named_report() {
+        let versions = [
+            version("P1", "AR-002", None, Some("AR-001")),
+            version("P1", "AR-001", None, None),
+            version("P2", "AR-001", None, None),
+        ];
+        let found = find_superseded(&versions);
+        assert_eq!(found.len(), 1);
+        assert_eq!((found[0].kept, found[0].dropped), (0, 1));
+        assert!(superseded_message(&versions, &found[0], Locale::En).contains("AR-002"));
+    }
+
+    #[test]
+    fn test_higher_revision_wins_and_duplicates_keep_first() {
+        let versions = [
+            version("P1", "AR-001", Some(2), None),
+            version("p1", "ar-001", None, None),
+            version("P2", "AR-001", Some(2), None),
+            version("P2", "AR-001", Some(2), None),
+        ];
+        let found = find_superseded(&versions);
+        assert_eq!(found.len(), 2);
+        assert_eq!((found[0].kept, found[0].dropped), (0, 1));
+        assert_eq!((found[1].kept, found[1].dropped), (2, 3));
+        assert!(found[1].duplicate);
+    }
+
+    #[test]
+    fn test_unrelated_reports_of_same_sample_are_kept() {
+        // Metals and PAH ordered separately for the same sample.
+        let versions = [
+            version("P1", "AR-001", None, None),
+            version("P1", "AR-014", None, None),
+        ];
+        assert!(find_superseded(&versions).is_empty());
+    }
+}
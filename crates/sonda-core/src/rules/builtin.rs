@@ -37,6 +37,39 @@ pub fn load_preset(name: &str) -> Result<RuleSetDef, SondaError> {
     }
 }
 
+/// Load threshold presets by name and combine them with custom rulesets.
+///
+/// Returns the rulesets and whether the HP engine ("fa") was requested.
+/// With no presets and no custom rulesets, every preset is used.
+pub fn resolve(
+    presets: &[String],
+    custom: Vec<RuleSetDef>,
+) -> Result<(Vec<RuleSetDef>, bool), SondaError> {
+    let defaults: Vec<String>;
+    let presets = if presets.is_empty() && custom.is_empty() {
+        defaults = PRESETS.iter().map(|s| s.to_string()).collect();
+        &defaults
+    } else {
+        presets
+    };
+
+    let mut rulesets = Vec::new();
+    let mut include_hp = false;
+    for preset in presets {
+        if is_hp_preset(preset) {
+            include_hp = true;
+        } else {
+            rulesets.push(load_preset(preset)?);
+        }
+    }
+    rulesets.extend(custom);
+
+    if rulesets.is_empty() && !include_hp {
+        return Err(SondaError::RulesetInvalid("no rulesets specified".into()));
+    }
+    Ok((rulesets, include_hp))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -48,6 +81,20 @@ mod tests {
         assert!(!rs.rules.is_empty());
     }
 
+    #[test]
+    fn test_presets_have_english_descriptions() {
+        use crate::i18n::Locale;
+
+        for name in ["nv", "asfalt"] {
+            let rs = load_preset(name).unwrap();
+            for cat in &rs.categories {
+                let sv = rs.category_description_in(cat, Locale::Sv);
+                let en = rs.category_description_in(cat, Locale::En);
+                assert!(sv.is_some() && en.is_some() && sv != en, "{name}: {cat}");
+            }
+        }
+    }
+
     #[test]
     fn test_unknown_preset() {
         assert!(load_preset("xyz").is_err());
@@ -64,4 +111,18 @@ mod tests {
     fn test_fa_load_returns_error() {
         assert!(load_preset("fa").is_err());
     }
+
+    #[test]
+    fn test_resolve_presets() {
+        let (rulesets, include_hp) = resolve(&[], vec![]).unwrap();
+        assert_eq!(rulesets.len(), 2);
+        assert!(include_hp);
+
+        let custom = load_preset("asfalt").unwrap();
+        let (rulesets, include_hp) = resolve(&["nv".into()], vec![custom]).unwrap();
+        assert_eq!(rulesets.len(), 2);
+        assert!(!include_hp);
+
+        assert!(resolve(&["xyz".into()], vec![]).is_err());
+    }
 }
@@ -3,6 +3,7 @@ pub mod schema;
 
 use crate::error::SondaError;
 use schema::RuleSetDef;
+use serde::Serialize;
 use std::path::Path;
 
 /// Load a ruleset from a JSON file.
@@ -90,6 +91,49 @@ pub fn validate_ruleset(ruleset: &RuleSetDef) -> Result<(), SondaError> {
     Ok(())
 }
 
+/// Potential issues in a valid ruleset (warnings, not errors).
+pub fn ruleset_warnings(rs: &RuleSetDef) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for rule in &rs.rules {
+        // Warn if not all categories have thresholds
+        for cat in &rs.categories {
+            if !rule.thresholds.contains_key(cat) {
+                warnings.push(format!(
+                    "substance '{}' has no threshold for category '{}'",
+                    rule.substance, cat
+                ));
+            }
+        }
+    }
+    warnings
+}
+
+/// Summary of a ruleset that passed validation, as returned by the web API
+/// and the wasm bindings.
+#[derive(Debug, Clone, Serialize)]
+pub struct RulesetValidation {
+    pub valid: bool,
+    pub name: String,
+    pub version: String,
+    pub categories: Vec<String>,
+    /// Number of rules.
+    pub rules: usize,
+    pub warnings: Vec<String>,
+}
+
+/// Parse and validate a ruleset, and summarise it with its warnings.
+pub fn validation_summary(json: &str) -> Result<RulesetValidation, SondaError> {
+    let rs = parse_ruleset_str(json)?;
+    Ok(RulesetValidation {
+        valid: true,
+        warnings: ruleset_warnings(&rs),
+        rules: rs.rules.len(),
+        name: rs.name,
+        version: rs.version,
+        categories: rs.categories,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,4 +192,19 @@ mod tests {
         }"#;
         assert!(parse_ruleset_str(json).is_err());
     }
+
+    #[test]
+    fn test_validation_summary_lists_warnings() {
+        let json = r#"{
+            "name": "Test",
+            "version": "1.0",
+            "categories": ["A", "B"],
+            "rules": [{ "substance": "bly", "thresholds": { "A": "50" } }]
+        }"#;
+        let summary = validation_summary(json).unwrap();
+        assert!(summary.valid);
+        assert_eq!(summary.rules, 1);
+        assert_eq!(summary.warnings.len(), 1);
+        assert!(validation_summary("{}").is_err());
+    }
 }
@@ -1,3 +1,4 @@
+use crate::i18n::Locale;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -17,6 +18,38 @@ pub struct RuleSetDef {
     #[serde(default)]
     pub category_descriptions: BTreeMap<String, String>,
     pub rules: Vec<SubstanceRuleDef>,
+    /// Description texts in other languages, keyed by locale. The base
+    /// `description` and `category_descriptions` are used when a locale is missing.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub translations: BTreeMap<Locale, RuleSetTranslation>,
+}
+
+/// Translated description texts for a ruleset.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RuleSetTranslation {
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub category_descriptions: BTreeMap<String, String>,
+}
+
+impl RuleSetDef {
+    /// Ruleset description in `locale`, falling back to the base description.
+    pub fn description_in(&self, locale: Locale) -> Option<&str> {
+        self.translations
+            .get(&locale)
+            .and_then(|t| t.description.as_deref())
+            .or(self.description.as_deref())
+    }
+
+    /// Category description in `locale`, falling back to the base description.
+    pub fn category_description_in(&self, category: &str, locale: Locale) -> Option<&str> {
+        self.translations
+            .get(&locale)
+            .and_then(|t| t.category_descriptions.get(category))
+            .or_else(|| self.category_descriptions.get(category))
+            .map(String::as_str)
+    }
 }
 
 /// A single substance rule within a ruleset.
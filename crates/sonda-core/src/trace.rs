@@ -1,5 +1,6 @@
-use crate::classify::outcome::RuleSetResult;
+use crate::classify::outcome::{Explanation, RuleSetResult};
 use crate::extraction::PageContent;
+use crate::i18n::Locale;
 use crate::model::{AnalysisRow, AnalysisValue};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -31,12 +32,16 @@ pub enum TraceStepType {
     ThresholdCompare,
     OverallDecision,
     HpCriterion,
+    ManualAdjustment,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TraceStep {
     pub step_type: TraceStepType,
     pub message: String,
+    /// Structured data behind `message` for decision steps.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<Explanation>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -130,6 +135,7 @@ pub fn build_entry_trace(
     row: &AnalysisRow,
     unit: &str,
     pages: &[PageContent],
+    locale: Locale,
 ) -> TraceEntry {
     let (value_kind, numeric_value) = match row.value {
         AnalysisValue::Measured(v) => (TraceValueKind::Measured, v),
@@ -149,11 +155,19 @@ pub fn build_entry_trace(
         steps: vec![
             TraceStep {
                 step_type: TraceStepType::NormalizeSubstance,
-                message: format!("Normalized '{}' -> '{}'", row.raw_name, row.normalized_name),
+                message: locale.format(
+                    "trace.normalized",
+                    &[("raw", &row.raw_name), ("normalized", &row.normalized_name)],
+                ),
+                explanation: None,
             },
             TraceStep {
                 step_type: TraceStepType::ParseValue,
-                message: format!("Parsed value '{}' as {}", row.value, numeric_value),
+                message: locale.format(
+                    "trace.parsed_value",
+                    &[("raw", &row.value), ("value", &numeric_value)],
+                ),
+                explanation: None,
             },
         ],
     }
@@ -263,6 +277,7 @@ pub fn build_ruleset_decisions(
     sample_id: &str,
     ruleset_idx: usize,
     rs: &RuleSetResult,
+    locale: Locale,
 ) -> Vec<TraceDecision> {
     let mut decisions = Vec::new();
 
@@ -278,15 +293,21 @@ pub fn build_ruleset_decisions(
         visibility: TraceVisibility::Always,
         steps: vec![TraceStep {
             step_type: TraceStepType::OverallDecision,
-            message: format!(
-                "Overall category '{}' determined by: {}",
-                rs.overall_category,
-                if rs.determining_substances.is_empty() {
-                    "none".to_string()
-                } else {
-                    rs.determining_substances.join(", ")
-                }
+            message: locale.format(
+                "trace.overall",
+                &[
+                    ("category", &rs.overall_category),
+                    (
+                        "substances",
+                        &if rs.determining_substances.is_empty() {
+                            locale.text("trace.none").to_string()
+                        } else {
+                            rs.determining_substances.join(", ")
+                        },
+                    ),
+                ],
             ),
+            explanation: rs.overall_explanation.clone(),
         }],
     });
 
@@ -312,6 +333,7 @@ pub fn build_ruleset_decisions(
                     TraceStepType::ThresholdCompare
                 },
                 message: sr.reason.clone(),
+                explanation: sr.explanation.clone(),
             }],
         });
     }
@@ -0,0 +1,71 @@
+//! JavaScript bindings for the `wasm` feature.
+//!
+//! Values cross the boundary as JSON strings in the same shapes as the CLI's
+//! `--output json`, so the viewer can use `JSON.parse` on every result.
+//! Build with `wasm-pack build crates/sonda-core --target web --
+//! --no-default-features --features wasm`.
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::error::SondaError;
+use crate::extraction::mapping;
+use crate::i18n::Locale;
+use crate::request::ClassifyRequest;
+use crate::rules::{self, builtin};
+
+/// Names of the built-in presets as a JSON array.
+#[wasm_bindgen(js_name = listPresets)]
+pub fn list_presets() -> String {
+    serde_json::to_string(builtin::PRESETS).expect("preset names serialize")
+}
+
+/// A built-in preset's ruleset as JSON.
+#[wasm_bindgen(js_name = loadPreset)]
+pub fn load_preset(name: &str) -> Result<String, JsError> {
+    to_json(&builtin::load_preset(name)?)
+}
+
+/// Parse and validate a ruleset, returning it normalized.
+#[wasm_bindgen(js_name = parseRuleset)]
+pub fn parse_ruleset(json: &str) -> Result<String, JsError> {
+    to_json(&rules::parse_ruleset_str(json)?)
+}
+
+/// Validate a ruleset and list warnings, as `sonda rules validate` does.
+#[wasm_bindgen(js_name = validateRuleset)]
+pub fn validate_ruleset(json: &str) -> Result<String, JsError> {
+    to_json(&rules::validation_summary(json)?)
+}
+
+/// Parse a Sweco xlsx file into `ParsedReports` JSON.
+#[wasm_bindgen(js_name = parseSwecoXlsx)]
+pub fn parse_sweco_xlsx(bytes: &[u8]) -> Result<String, JsError> {
+    to_json(&crate::parse_sweco_xlsx(bytes)?)
+}
+
+/// Parse a Eurofins CSV or XLSX export into `ParsedReports` JSON.
+#[wasm_bindgen(js_name = parseEurofinsExport)]
+pub fn parse_eurofins_export(bytes: &[u8]) -> Result<String, JsError> {
+    to_json(&crate::parse_eurofins_export(bytes)?)
+}
+
+/// Parse an xlsx workbook with a column mapping (JSON) into `ParsedReports`
+/// JSON.
+#[wasm_bindgen(js_name = parseMappedXlsx)]
+pub fn parse_mapped_xlsx(bytes: &[u8], mapping: &str) -> Result<String, JsError> {
+    let mapping = mapping::parse_mapping_str(mapping)?;
+    to_json(&crate::parse_mapped_xlsx(bytes, &mapping)?)
+}
+
+/// Classify pre-parsed reports. Takes a [`ClassifyRequest`] as JSON and
+/// returns the `ClassificationResult` JSON.
+#[wasm_bindgen(js_name = classifyReports)]
+pub fn classify_reports(request: &str) -> Result<String, JsError> {
+    let request: ClassifyRequest = serde_json::from_str(request).map_err(SondaError::Json)?;
+    to_json(&request.classify(Locale::default())?)
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, JsError> {
+    Ok(serde_json::to_string(value).map_err(SondaError::Json)?)
+}
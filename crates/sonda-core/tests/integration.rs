@@ -7,7 +7,7 @@
 use sonda_core::classify_pdf;
 use sonda_core::classify_reports;
 use sonda_core::error::SondaError;
-use sonda_core::extraction::{PageContent, PdfExtractor};
+use sonda_core::extraction::{BBox, LineSpan, PageContent, PdfExtractor};
 use sonda_core::parse_pdf;
 use sonda_core::rules::builtin::load_preset;
 use sonda_core::ClassifyOptions;
@@ -204,7 +204,10 @@ fn hp_classification_arsenic_triggers_fa() {
         )],
     };
 
-    let opts = ClassifyOptions { include_hp: true };
+    let opts = ClassifyOptions {
+        include_hp: true,
+        ..Default::default()
+    };
     let result = classify_pdf(&[], &extractor, &[], &opts).unwrap();
 
     assert_eq!(result.samples.len(), 1);
@@ -256,16 +259,16 @@ fn unparseable_rows_generate_trace_warnings() {
 }
 
 // ---------------------------------------------------------------------------
-// Test 7: Non-Eurofins report returns UnsupportedReport error
+// Test 7: Report from an unknown lab returns UnsupportedReport error
 // ---------------------------------------------------------------------------
 #[test]
-fn non_eurofins_report_returns_unsupported_error() {
+fn unknown_lab_report_returns_unsupported_error() {
     let nv = load_preset("nv").unwrap();
     let extractor = MockExtractor {
         pages: vec![page(
             1,
             &[
-                "ALS Scandinavia AB",
+                "Miljölab Norr AB",
                 "Analysrapport",
                 "Provnummer: P001",
                 "Matris: Jord",
@@ -277,7 +280,13 @@ fn non_eurofins_report_returns_unsupported_error() {
 
     let result = classify_pdf(&[], &extractor, &[nv], &ClassifyOptions::default());
 
-    assert!(matches!(result, Err(SondaError::UnsupportedReport(_))));
+    let err = result.unwrap_err();
+    assert!(matches!(err, SondaError::UnsupportedReport(_)));
+    // Every parser is listed with its reason for declining
+    let message = err.to_string();
+    assert!(
+        message.contains("eurofins:") && message.contains("als:") && message.contains("synlab:")
+    );
 }
 
 // ---------------------------------------------------------------------------
@@ -344,4 +353,433 @@ fn two_step_parse_then_classify() {
     let rs = &result.samples[0].ruleset_results[0];
     // As 15 > KM(10), Pb 60 > KM(50) → MKM
     assert_eq!(rs.overall_category, "MKM");
+
+    // Trace is recorded without PDF pages, just without evidence spans
+    assert_eq!(result.trace.entries.len(), 2);
+    assert!(result.trace.entries[0].evidence_spans.is_empty());
+    assert!(!result.trace.decisions.is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// Test 10: Manual overrides and exclusions are applied and audited
+// ---------------------------------------------------------------------------
+#[test]
+fn manual_adjustments_applied_before_classification() {
+    use sonda_core::adjust::{parse_exclusion, parse_override, AdjustmentAction, Adjustments};
+
+    let nv = load_preset("nv").unwrap();
+    let extractor = MockExtractor {
+        pages: vec![page(
+            1,
+            &[
+                "Eurofins Environment Testing Sweden AB",
+                "Analysrapport",
+                "Provnummer: P001",
+                "Matris: Jord",
+                "",
+                "  Arsenik (As)          15          mg/kg TS",
+                "  Bly (Pb)              600         mg/kg TS",
+            ],
+        )],
+    };
+    let options = ClassifyOptions {
+        adjustments: Adjustments {
+            overrides: vec![
+                parse_override("P001:bly=45", "Re-analysis AR-2024-007").unwrap(),
+                // Mistyped sample: matches no report
+                parse_override("P01:arsenik=5", "Re-analysis AR-2024-007").unwrap(),
+            ],
+            exclusions: vec![parse_exclusion("arsenik", "Contaminated vial").unwrap()],
+            author: Some("anna".into()),
+        },
+        ..Default::default()
+    };
+
+    let result = classify_pdf(&[], &extractor, &[nv], &options).unwrap();
+
+    // Pb 45 <= KM(50) and arsenic excluded → KM
+    let rs = &result.samples[0].ruleset_results[0];
+    assert_eq!(rs.overall_category, "KM");
+    assert_eq!(rs.substance_results.len(), 1);
+
+    assert_eq!(result.adjustments.len(), 2);
+    assert_eq!(result.adjustments[0].action, AdjustmentAction::Override);
+    assert_eq!(result.adjustments[0].author.as_deref(), Some("anna"));
+
+    // Override is a step on the parsed entry; the exclusion is a trace warning
+    let bly = result
+        .trace
+        .entries
+        .iter()
+        .find(|e| e.normalized_name == "bly")
+        .unwrap();
+    let step = bly.steps.last().unwrap();
+    assert!(step.message.contains("600") && step.message.contains("Re-analysis"));
+    assert!(result
+        .trace
+        .warnings
+        .iter()
+        .any(|w| w.message.contains("Contaminated vial")));
+
+    // The override for the unknown sample is reported, not silently dropped
+    assert_eq!(result.warnings.len(), 1);
+    assert_eq!(result.warnings[0].sample_id.as_deref(), Some("P01"));
+    assert!(result
+        .trace
+        .warnings
+        .iter()
+        .any(|w| w.message.contains("P01") && w.message.contains("arsenik")));
+}
+
+// ---------------------------------------------------------------------------
+// ALS Scandinavia reports
+// ---------------------------------------------------------------------------
+
+fn als_pages() -> Vec<PageContent> {
+    vec![
+        page(
+            1,
+            &[
+                "ALS Scandinavia AB                                        Rapport",
+                "Sida 1 (2)",
+                "Rapport              T2412345",
+                "Registrerad          2024-05-13 10:20",
+                "Utfärdad             2024-05-20 14:02",
+                "",
+                "Projekt              Kv. Björken",
+                "Bestnr               4711",
+                "",
+                "Analys av fast prov",
+                "Er beteckning        P1 0-0,5",
+                "Provtagningsdatum    2024-05-10",
+                "Provtyp              Jord",
+                "Labnummer            O11456789",
+                "Parameter                Resultat   Osäkerhet (±)   Enhet      Metod  Utf  Sign",
+                "TS_105°C                 85.2       5.13            %          1      V    ANNI",
+                "As                       12.1       3.6             mg/kg TS   2      H    ANNI",
+                "Cd                       <0.10                      mg/kg TS   2      H    ANNI",
+                "Cr                       31.0       6.2             mg/kg TS   2      H    ANNI",
+                "Pb                       48.3       9.7             mg/kg TS   2      H    ANNI",
+                "Hg                       <0.20                      mg/kg TS   2      H    ANNI",
+                "PAH, summa L             <0.15                      mg/kg TS   3      J    STGR",
+                "PAH, summa M             1.9        0.57            mg/kg TS   3      J    STGR",
+                "PAH, summa H             2.4        0.72            mg/kg TS   3      J    STGR",
+                "bens(a)pyren             0.41       0.12            mg/kg TS   3      J    STGR",
+            ],
+        ),
+        page(
+            2,
+            &[
+                "ALS Scandinavia AB",
+                "Sida 2 (2)",
+                "Er beteckning        P2 0,5-1",
+                "Provtagningsdatum    2024-05-10",
+                "Provtyp              Jord",
+                "Labnummer            O11456790",
+                "Parameter                Resultat   Osäkerhet (±)   Enhet      Metod  Utf  Sign",
+                "TS_105°C                 88.0       5.28            %          1      V    ANNI",
+                "As                       6.3        1.9             mg/kg TS   2      H    ANNI",
+                "Pb                       22.0       4.4             mg/kg TS   2      H    ANNI",
+                "Hg                       <0.20                      mg/kg TS   2      H    ANNI",
+            ],
+        ),
+    ]
+}
+
+// ---------------------------------------------------------------------------
+// Test 11: ALS report parses into the shared model
+// ---------------------------------------------------------------------------
+#[test]
+fn als_report_parses_headers_rows_and_uncertainty() {
+    use rust_decimal_macros::dec;
+    use sonda_core::model::{AnalysisValue, Date, Matrix};
+
+    let extractor = MockExtractor { pages: als_pages() };
+    let parsed = parse_pdf(&[], &extractor).unwrap();
+
+    assert_eq!(parsed.reports.len(), 2);
+    assert!(parsed.warnings.is_empty());
+    assert!(parsed.skipped_lines.is_empty());
+
+    let p1 = &parsed.reports[0];
+    assert_eq!(p1.header.lab.as_deref(), Some("ALS"));
+    assert_eq!(p1.header.sample_id.as_deref(), Some("P1 0-0,5"));
+    assert_eq!(p1.header.lab_report_id.as_deref(), Some("T2412345"));
+    assert_eq!(p1.header.project.as_deref(), Some("Kv. Björken"));
+    assert_eq!(p1.header.date, Date::new(2024, 5, 10));
+    assert_eq!(p1.header.matrix, Some(Matrix::Jord));
+
+    let names: Vec<&str> = p1.rows.iter().map(|r| r.normalized_name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "ts",
+            "arsenik",
+            "kadmium",
+            "krom_total",
+            "bly",
+            "kvicksilver",
+            "pah_l",
+            "pah_m",
+            "pah_h",
+            "benso_a_pyren"
+        ]
+    );
+    let arsenik = &p1.rows[1];
+    assert_eq!(arsenik.value, AnalysisValue::Measured(dec!(12.1)));
+    assert_eq!(arsenik.uncertainty, Some(dec!(3.6)));
+    let kadmium = &p1.rows[2];
+    assert_eq!(kadmium.value, AnalysisValue::BelowDetection(dec!(0.10)));
+    assert_eq!(kadmium.uncertainty, None);
+
+    // The second sample shares the report number and project
+    let p2 = &parsed.reports[1];
+    assert_eq!(p2.header.sample_id.as_deref(), Some("P2 0,5-1"));
+    assert_eq!(p2.header.lab_report_id.as_deref(), Some("T2412345"));
+    assert_eq!(p2.rows.len(), 4);
+}
+
+// ---------------------------------------------------------------------------
+// Test 12: ALS report classifies with the built-in presets
+// ---------------------------------------------------------------------------
+#[test]
+fn als_report_classifies_with_presets() {
+    let nv = load_preset("nv").unwrap();
+    let extractor = MockExtractor { pages: als_pages() };
+
+    let result = classify_pdf(&[], &extractor, &[nv], &ClassifyOptions::default()).unwrap();
+
+    assert_eq!(result.samples.len(), 2);
+    // P1: As 12.1 > KM(10), PAH-H 2.4 > KM(1) → MKM
+    let p1 = &result.samples[0];
+    assert_eq!(p1.sample_id, "P1 0-0,5");
+    assert_eq!(p1.ruleset_results[0].overall_category, "MKM");
+    // P2: As 6.3, Pb 22 → KM
+    assert_eq!(result.samples[1].ruleset_results[0].overall_category, "KM");
+}
+
+// ---------------------------------------------------------------------------
+// SYNLAB reports
+// ---------------------------------------------------------------------------
+
+fn synlab_pages() -> Vec<PageContent> {
+    vec![
+        page(
+            1,
+            &[
+                "SYNLAB Analytics & Services Sweden AB                 Sida 1 (2)",
+                "ANALYSRAPPORT",
+                "Rapportnummer: 24-118532",
+                "Uppdragsgivare: Sweco Sverige AB",
+                "Projekt: Väg 222 Skurubron",
+                "",
+                "Provnr: 24-118532-01          Kundens provbeteckning: S1 0-0,5",
+                "Provtyp: Jord",
+                "Provtagningsdatum: 2024-06-03      Ankomstdatum: 2024-06-05",
+                "",
+                "Komponent               Resultat    Rapp.gräns   Enhet       Mätosäkerhet",
+                "Arsenik, As             1400        0,50         mg/kg TS    ±20%",
+                "Bly, Pb                 35          1,0          mg/kg TS    ±25%",
+                "Kadmium, Cd             ej påv.     0,20         mg/kg TS",
+                "Kvicksilver, Hg         < 0,040     0,040        mg/kg TS",
+            ],
+        ),
+        page(
+            2,
+            &[
+                "SYNLAB Analytics & Services Sweden AB                 Sida 2 (2)",
+                "Provnr: 24-118532-02          Kundens provbeteckning: S2 0,5-1",
+                "Provtyp: Jord",
+                "Provtagningsdatum: 2024-06-03",
+                "",
+                "Komponent               Resultat    Rapp.gräns   Enhet       Mätosäkerhet",
+                "Arsenik, As             4,2         0,50         mg/kg TS    ±20%",
+                "Bly, Pb                 ej påv.     1,0          mg/kg TS",
+                "PAH-H, summa            0,35        0,10         mg/kg TS    ±30%",
+            ],
+        ),
+    ]
+}
+
+// ---------------------------------------------------------------------------
+// Test 13: SYNLAB report parses into the shared model
+// ---------------------------------------------------------------------------
+#[test]
+fn synlab_report_parses_headers_and_not_detected_values() {
+    use rust_decimal_macros::dec;
+    use sonda_core::model::{AnalysisValue, Date, Matrix};
+
+    let extractor = MockExtractor {
+        pages: synlab_pages(),
+    };
+    let parsed = parse_pdf(&[], &extractor).unwrap();
+
+    assert_eq!(parsed.reports.len(), 2);
+    assert!(parsed.skipped_lines.is_empty());
+
+    let s1 = &parsed.reports[0];
+    assert_eq!(s1.header.lab.as_deref(), Some("Synlab"));
+    assert_eq!(s1.header.sample_id.as_deref(), Some("S1 0-0,5"));
+    assert_eq!(s1.header.lab_report_id.as_deref(), Some("24-118532"));
+    assert_eq!(s1.header.project.as_deref(), Some("Väg 222 Skurubron"));
+    assert_eq!(s1.header.date, Date::new(2024, 6, 3));
+    assert_eq!(s1.header.matrix, Some(Matrix::Jord));
+
+    let arsenik = &s1.rows[0];
+    assert_eq!(arsenik.normalized_name, "arsenik");
+    assert_eq!(arsenik.value, AnalysisValue::Measured(dec!(1400)));
+    assert_eq!(arsenik.uncertainty, Some(dec!(280)));
+    // "ej påv." takes the reporting limit
+    assert_eq!(s1.rows[2].normalized_name, "kadmium");
+    assert_eq!(s1.rows[2].value, AnalysisValue::BelowDetection(dec!(0.20)));
+    assert_eq!(s1.rows[3].value, AnalysisValue::BelowDetection(dec!(0.040)));
+
+    let s2 = &parsed.reports[1];
+    assert_eq!(s2.header.sample_id.as_deref(), Some("S2 0,5-1"));
+    assert_eq!(s2.rows[1].value, AnalysisValue::BelowDetection(dec!(1.0)));
+    assert_eq!(s2.rows[2].normalized_name, "pah_h");
+}
+
+// ---------------------------------------------------------------------------
+// Test 14: SYNLAB report runs through the presets and the HP engine
+// ---------------------------------------------------------------------------
+#[test]
+fn synlab_report_classifies_with_presets_and_hp() {
+    let nv = load_preset("nv").unwrap();
+    let extractor = MockExtractor {
+        pages: synlab_pages(),
+    };
+    let opts = ClassifyOptions {
+        include_hp: true,
+        ..Default::default()
+    };
+
+    let result = classify_pdf(&[], &extractor, &[nv], &opts).unwrap();
+
+    assert_eq!(result.samples.len(), 2);
+    let s1 = &result.samples[0];
+    // As 1400 > MKM(25)
+    assert_eq!(s1.ruleset_results[0].overall_category, "> MKM");
+    // As 1400 mg/kg as As2O3 >= HP7 threshold → FA
+    let hp = s1
+        .ruleset_results
+        .iter()
+        .find(|rs| rs.hp_details.is_some())
+        .unwrap();
+    assert_eq!(hp.overall_category, "FA");
+
+    // S2: As 4,2, Pb below 1,0, PAH-H 0,35 → KM
+    assert_eq!(result.samples[1].ruleset_results[0].overall_category, "KM");
+}
+
+// ---------------------------------------------------------------------------
+// Test 15: OCR'd lines with low recognition confidence are flagged in the trace
+// ---------------------------------------------------------------------------
+#[test]
+fn low_confidence_ocr_lines_generate_trace_warnings() {
+    let nv = load_preset("nv").unwrap();
+    let lines = [
+        ("Eurofins Environment Testing Sweden AB", 93.0),
+        ("Analysrapport", 95.0),
+        ("Provnummer: OCR001", 91.0),
+        ("Matris: Jord", 94.0),
+        ("  Arsenik (As)          8           mg/kg TS", 89.0),
+        ("  Bly (Pb)              45          mg/kg TS", 42.0),
+    ];
+    let mut ocr_page = page(1, &lines.map(|(text, _)| text));
+    ocr_page.line_spans = lines
+        .iter()
+        .enumerate()
+        .map(|(i, (text, confidence))| LineSpan {
+            page_number: 1,
+            line_index: i,
+            text: text.to_string(),
+            bbox: BBox {
+                x_min: 50.0,
+                y_min: 100.0 + 12.0 * i as f32,
+                x_max: 400.0,
+                y_max: 110.0 + 12.0 * i as f32,
+            },
+            confidence: Some(*confidence),
+        })
+        .collect();
+    let extractor = MockExtractor {
+        pages: vec![ocr_page],
+    };
+
+    let result = classify_pdf(&[], &extractor, &[nv], &ClassifyOptions::default()).unwrap();
+
+    assert_eq!(result.samples.len(), 1);
+    let ocr_warnings: Vec<_> = result
+        .trace
+        .warnings
+        .iter()
+        .filter(|w| w.message.contains("OCR"))
+        .collect();
+    assert_eq!(ocr_warnings.len(), 1);
+    assert!(ocr_warnings[0].message.contains("42%"));
+    assert!(ocr_warnings[0].message.contains("Bly (Pb)"));
+
+    // Parsing alone reports them too, for paths that classify later
+    let parsed = parse_pdf(&[], &extractor).unwrap();
+    assert_eq!(parsed.warnings.len(), 1);
+    assert_eq!(
+        parsed.warnings[0].reason,
+        "Bly (Pb)              45          mg/kg TS"
+    );
+    assert!(parsed.warnings[0]
+        .message(sonda_core::i18n::Locale::En)
+        .contains("page 1"));
+}
+
+// ---------------------------------------------------------------------------
+// Test 16: Summary report with one column per sample classifies each sample
+// ---------------------------------------------------------------------------
+#[test]
+fn column_per_sample_report_classifies_each_column() {
+    let nv = load_preset("nv").unwrap();
+    let extractor = MockExtractor {
+        pages: vec![page(
+            1,
+            &[
+                "Eurofins Environment Testing Sweden AB",
+                "ANALYSRAPPORT                                            Sida 1 (1)",
+                "Rapport: AR-24-SE-013377",
+                "Matris: Jord",
+                "",
+                "Provmärkning        PG1 0-0,5     PG2 0-0,5     PG3 0,5-1     Enhet",
+                "Provnummer          177-001       177-002       177-003",
+                "",
+                "Arsenik (As)        8             15            4             mg/kg TS",
+                "Bly (Pb)            45            90            <5            mg/kg TS",
+                "Kadmium (Cd)        0,30          0,8           0,10          mg/kg TS",
+            ],
+        )],
+    };
+
+    let result = classify_pdf(&[], &extractor, &[nv], &ClassifyOptions::default()).unwrap();
+
+    let samples: Vec<(&str, &str)> = result
+        .samples
+        .iter()
+        .map(|s| {
+            (
+                s.sample_id.as_str(),
+                s.ruleset_results[0].overall_category.as_str(),
+            )
+        })
+        .collect();
+    // As 15 > KM(10) in PG2 only
+    assert_eq!(
+        samples,
+        [
+            ("PG1 0-0,5", "KM"),
+            ("PG2 0-0,5", "MKM"),
+            ("PG3 0,5-1", "KM")
+        ]
+    );
+    assert!(result
+        .samples
+        .iter()
+        .all(|s| s.lab_report_id.as_deref() == Some("AR-24-SE-013377")));
 }
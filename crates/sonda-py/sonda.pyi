@@ -0,0 +1,40 @@
+"""Waste classification of contaminated soil and asphalt lab reports.
+
+Results are dicts with the same keys as `sonda ... --output json`; measured
+values, thresholds and margins are `decimal.Decimal`.
+"""
+
+from os import PathLike
+from typing import Any, Optional, Sequence, Union
+
+class SondaError(Exception):
+    """Invalid input, unsupported report or invalid ruleset."""
+
+def presets() -> list[str]:
+    """Names of the built-in presets."""
+
+def load_preset(name: str) -> dict[str, Any]:
+    """A built-in preset's ruleset as a dict."""
+
+def load_ruleset(path: Union[str, PathLike[str]]) -> dict[str, Any]:
+    """Load and validate a ruleset JSON file."""
+
+def parse_ruleset(json: str) -> dict[str, Any]:
+    """Parse and validate a ruleset from a JSON string."""
+
+def parse(path: Union[str, PathLike[str]]) -> dict[str, Any]:
+    """Parse a PDF, Sweco xlsx, Eurofins export or reports JSON file without classifying."""
+
+def parse_bytes(data: bytes, filename: str = "report.pdf") -> dict[str, Any]:
+    """Like `parse`, for file contents already in memory."""
+
+def classify(
+    reports: Union[Sequence[dict[str, Any]], dict[str, Any]],
+    presets: Optional[Sequence[str]] = None,
+    rules: Optional[Sequence[dict[str, Any]]] = None,
+    lang: Optional[str] = None,
+    sensitivity: Optional[str] = None,
+    adjustments: Optional[dict[str, Any]] = None,
+) -> dict[str, Any]:
+    """Classify parsed reports. Uses all presets when neither `presets` nor
+    `rules` is given."""
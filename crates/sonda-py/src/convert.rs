@@ -0,0 +1,428 @@
+//! Conversion between sonda-core's serde types and Python objects.
+//!
+//! Results become the same dicts and lists as `json.loads` of the CLI's JSON
+//! output, except that `Decimal` values become `decimal.Decimal` instead of
+//! strings. Inputs go the other way through `json.dumps`.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyList};
+use pyo3::IntoPyObjectExt;
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde::ser::{self, Serialize};
+use std::any::type_name;
+use std::fmt;
+
+/// Convert a serializable value into Python objects.
+pub fn to_python<'py, T: Serialize + ?Sized>(
+    py: Python<'py>,
+    value: &T,
+) -> PyResult<Bound<'py, PyAny>> {
+    let serializer = Pythonizer {
+        py,
+        decimal: py.import("decimal")?.getattr("Decimal")?,
+    };
+    serializer.value(value).map_err(|e| e.0)
+}
+
+/// Convert Python dicts and lists into a deserializable value. `Decimal`s
+/// are passed as strings, which is how sonda-core reads them from JSON.
+pub fn from_python<T: DeserializeOwned>(obj: &Bound<'_, PyAny>) -> PyResult<T> {
+    let py = obj.py();
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("default", py.get_type::<pyo3::types::PyString>())?;
+    let json: String = py
+        .import("json")?
+        .call_method("dumps", (obj,), Some(&kwargs))?
+        .extract()?;
+    serde_json::from_str(&json).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// A Python error raised while serializing.
+#[derive(Debug)]
+pub struct Error(PyErr);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(PyValueError::new_err(msg.to_string()))
+    }
+}
+
+impl From<PyErr> for Error {
+    fn from(e: PyErr) -> Self {
+        Error(e)
+    }
+}
+
+struct Pythonizer<'py> {
+    py: Python<'py>,
+    decimal: Bound<'py, PyAny>,
+}
+
+impl<'py> Pythonizer<'py> {
+    /// Serialize a child value. `Decimal` serializes as a string, so its type
+    /// is what tells it apart from text fields. Map and sequence items arrive
+    /// as `&Decimal`.
+    fn value<T: Serialize + ?Sized>(&self, value: &T) -> Result<Bound<'py, PyAny>, Error> {
+        let obj = value.serialize(self)?;
+        if type_name::<T>().trim_start_matches('&') == type_name::<Decimal>() {
+            Ok(self.decimal.call1((obj,))?)
+        } else {
+            Ok(obj)
+        }
+    }
+
+    fn object<T: IntoPyObject<'py>>(&self, value: T) -> Result<Bound<'py, PyAny>, Error> {
+        Ok(value.into_bound_py_any(self.py)?)
+    }
+
+    /// `{variant: value}`, as serde_json writes non-unit enum variants.
+    fn variant(
+        &self,
+        variant: &'static str,
+        value: Bound<'py, PyAny>,
+    ) -> Result<Bound<'py, PyAny>, Error> {
+        let dict = PyDict::new(self.py);
+        dict.set_item(variant, value)?;
+        Ok(dict.into_any())
+    }
+}
+
+impl<'a, 'py> ser::Serializer for &'a Pythonizer<'py> {
+    type Ok = Bound<'py, PyAny>;
+    type Error = Error;
+    type SerializeSeq = Seq<'a, 'py>;
+    type SerializeTuple = Seq<'a, 'py>;
+    type SerializeTupleStruct = Seq<'a, 'py>;
+    type SerializeTupleVariant = Seq<'a, 'py>;
+    type SerializeMap = Map<'a, 'py>;
+    type SerializeStruct = Map<'a, 'py>;
+    type SerializeStructVariant = Map<'a, 'py>;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Error> {
+        self.object(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Error> {
+        self.object(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Error> {
+        self.object(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Error> {
+        self.object(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Error> {
+        self.object(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Error> {
+        self.object(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Error> {
+        self.object(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Error> {
+        self.object(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Error> {
+        self.object(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Error> {
+        self.object(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Error> {
+        self.object(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Error> {
+        self.object(v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Error> {
+        self.object(v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Error> {
+        Ok(PyBytes::new(self.py, v).into_any())
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Error> {
+        Ok(self.py.None().into_bound(self.py))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok, Error> {
+        self.value(value)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Error> {
+        self.serialize_none()
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Error> {
+        self.serialize_none()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Error> {
+        self.object(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Error> {
+        self.value(value)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Error> {
+        let value = self.value(value)?;
+        self.variant(variant, value)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Ok(Seq {
+            ser: self,
+            variant: None,
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Ok(Seq {
+            ser: self,
+            variant: Some(variant),
+            items: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Ok(Map {
+            ser: self,
+            variant: None,
+            dict: PyDict::new(self.py),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Ok(Map {
+            ser: self,
+            variant: Some(variant),
+            dict: PyDict::new(self.py),
+            key: None,
+        })
+    }
+}
+
+pub struct Seq<'a, 'py> {
+    ser: &'a Pythonizer<'py>,
+    variant: Option<&'static str>,
+    items: Vec<Bound<'py, PyAny>>,
+}
+
+impl<'py> Seq<'_, 'py> {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.items.push(self.ser.value(value)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Bound<'py, PyAny>, Error> {
+        let list = PyList::new(self.ser.py, self.items)?.into_any();
+        match self.variant {
+            Some(variant) => self.ser.variant(variant, list),
+            None => Ok(list),
+        }
+    }
+}
+
+impl<'py> ser::SerializeSeq for Seq<'_, 'py> {
+    type Ok = Bound<'py, PyAny>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        self.finish()
+    }
+}
+
+impl<'py> ser::SerializeTuple for Seq<'_, 'py> {
+    type Ok = Bound<'py, PyAny>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        self.finish()
+    }
+}
+
+impl<'py> ser::SerializeTupleStruct for Seq<'_, 'py> {
+    type Ok = Bound<'py, PyAny>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        self.finish()
+    }
+}
+
+impl<'py> ser::SerializeTupleVariant for Seq<'_, 'py> {
+    type Ok = Bound<'py, PyAny>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        self.finish()
+    }
+}
+
+pub struct Map<'a, 'py> {
+    ser: &'a Pythonizer<'py>,
+    variant: Option<&'static str>,
+    dict: Bound<'py, PyDict>,
+    key: Option<Bound<'py, PyAny>>,
+}
+
+impl<'py> Map<'_, 'py> {
+    fn insert<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), Error> {
+        self.dict.set_item(key, self.ser.value(value)?)?;
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Bound<'py, PyAny>, Error> {
+        let dict = self.dict.into_any();
+        match self.variant {
+            Some(variant) => self.ser.variant(variant, dict),
+            None => Ok(dict),
+        }
+    }
+}
+
+impl<'py> ser::SerializeMap for Map<'_, 'py> {
+    type Ok = Bound<'py, PyAny>;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(self.ser.value(key)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| <Error as ser::Error>::custom("map value without a key"))?;
+        self.dict.set_item(key, self.ser.value(value)?)?;
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        self.finish()
+    }
+}
+
+impl<'py> ser::SerializeStruct for Map<'_, 'py> {
+    type Ok = Bound<'py, PyAny>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        self.finish()
+    }
+}
+
+impl<'py> ser::SerializeStructVariant for Map<'_, 'py> {
+    type Ok = Bound<'py, PyAny>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        self.finish()
+    }
+}
@@ -0,0 +1,198 @@
+//! Python extension module `sonda` over sonda-core.
+//!
+//! Results are plain dicts and lists with the same keys as the CLI's JSON
+//! output; measured values, thresholds and margins are `decimal.Decimal`.
+//! Build a wheel with `maturin build --release` in this directory.
+
+mod convert;
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use sonda_core::adjust::Adjustments;
+use sonda_core::extraction::ocr::AutoExtractor;
+use sonda_core::i18n::Locale;
+use sonda_core::input::{self, InputFormat};
+use sonda_core::model::AnalysisReport;
+use sonda_core::request::ClassifyRequest;
+use sonda_core::rules::{self, builtin};
+use std::path::{Path, PathBuf};
+
+use convert::{from_python, to_python};
+
+create_exception!(
+    sonda,
+    SondaError,
+    PyException,
+    "Invalid input, unsupported report or invalid ruleset."
+);
+
+fn error(e: sonda_core::error::SondaError) -> PyErr {
+    SondaError::new_err(e.to_string())
+}
+
+/// Names of the built-in presets.
+#[pyfunction]
+fn presets() -> Vec<&'static str> {
+    builtin::PRESETS.to_vec()
+}
+
+/// A built-in preset's ruleset as a dict.
+#[pyfunction]
+fn load_preset<'py>(py: Python<'py>, name: &str) -> PyResult<Bound<'py, PyAny>> {
+    to_python(py, &builtin::load_preset(name).map_err(error)?)
+}
+
+/// Load and validate a ruleset JSON file.
+#[pyfunction]
+fn load_ruleset(py: Python<'_>, path: PathBuf) -> PyResult<Bound<'_, PyAny>> {
+    to_python(py, &rules::load_ruleset(&path).map_err(error)?)
+}
+
+/// Parse and validate a ruleset from a JSON string.
+#[pyfunction]
+fn parse_ruleset<'py>(py: Python<'py>, json: &str) -> PyResult<Bound<'py, PyAny>> {
+    to_python(py, &rules::parse_ruleset_str(json).map_err(error)?)
+}
+
+/// Parse a PDF, Sweco xlsx, Eurofins export or reports JSON file without classifying.
+#[pyfunction]
+fn parse(py: Python<'_>, path: PathBuf) -> PyResult<Bound<'_, PyAny>> {
+    let bytes = std::fs::read(&path).map_err(|e| error(e.into()))?;
+    parse_input(py, &path, &bytes)
+}
+
+/// Like `parse`, for file contents already in memory. `filename` is only
+/// used to detect the format.
+#[pyfunction]
+#[pyo3(signature = (data, filename = "report.pdf"))]
+fn parse_bytes<'py>(py: Python<'py>, data: &[u8], filename: &str) -> PyResult<Bound<'py, PyAny>> {
+    parse_input(py, Path::new(filename), data)
+}
+
+fn parse_input<'py>(py: Python<'py>, name: &Path, bytes: &[u8]) -> PyResult<Bound<'py, PyAny>> {
+    let format = InputFormat::detect(name, bytes);
+    let parsed = py
+        .detach(|| input::parse_input(format, bytes, &AutoExtractor::default()))
+        .map_err(error)?;
+    to_python(py, &parsed)
+}
+
+/// Classify parsed reports.
+///
+/// `reports` is a list of report dicts or the dict returned by `parse`.
+/// Uses all presets when neither `presets` nor `rules` is given.
+#[pyfunction]
+#[pyo3(signature = (reports, presets = None, rules = None, lang = None, sensitivity = None, adjustments = None))]
+fn classify<'py>(
+    py: Python<'py>,
+    reports: &Bound<'py, PyAny>,
+    presets: Option<Vec<String>>,
+    rules: Option<&Bound<'py, PyAny>>,
+    lang: Option<&str>,
+    sensitivity: Option<String>,
+    adjustments: Option<&Bound<'py, PyAny>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let reports: Vec<AnalysisReport> = match reports.cast::<PyDict>() {
+        Ok(parsed) => match parsed.get_item("reports")? {
+            Some(reports) => from_python(&reports)?,
+            None => from_python(parsed)?,
+        },
+        Err(_) => from_python(reports)?,
+    };
+    let request = ClassifyRequest {
+        reports,
+        presets: presets.unwrap_or_default(),
+        rules: rules.map(from_python).transpose()?.unwrap_or_default(),
+        lang: lang.map(str::parse::<Locale>).transpose().map_err(error)?,
+        sensitivity,
+        adjustments: adjustments
+            .map(from_python::<Adjustments>)
+            .transpose()?
+            .unwrap_or_default(),
+    };
+    let result = py
+        .detach(|| request.classify(Locale::default()))
+        .map_err(error)?;
+    to_python(py, &result)
+}
+
+#[pymodule]
+fn sonda(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("SondaError", m.py().get_type::<SondaError>())?;
+    m.add_function(wrap_pyfunction!(presets, m)?)?;
+    m.add_function(wrap_pyfunction!(load_preset, m)?)?;
+    m.add_function(wrap_pyfunction!(load_ruleset, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_ruleset, m)?)?;
+    m.add_function(wrap_pyfunction!(parse, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_bytes, m)?)?;
+    m.add_function(wrap_pyfunction!(classify, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::types::PyList;
+
+    const REPORTS: &[u8] = br#"[{
+        "header": { "sample_id": "P1", "matrix": "jord" },
+        "rows": [{
+            "raw_name": "Bly (Pb)", "normalized_name": "bly",
+            "value": { "Measured": "120" }, "unit": "mg/kg TS"
+        }]
+    }]"#;
+
+    #[test]
+    fn classifies_parsed_reports_with_decimal_values() {
+        Python::initialize();
+        Python::attach(|py| {
+            let parsed = parse_bytes(py, REPORTS, "reports.json").unwrap();
+            let presets = Some(vec!["nv".to_string()]);
+            let result = classify(py, &parsed, presets, None, Some("sv"), None, None).unwrap();
+
+            let rs = result
+                .get_item("samples")
+                .and_then(|s| s.get_item(0))
+                .and_then(|s| s.get_item("ruleset_results"))
+                .and_then(|r| r.get_item(0))
+                .unwrap();
+            let category: String = rs.get_item("overall_category").unwrap().extract().unwrap();
+            assert_eq!(category, "MKM");
+
+            let value = rs
+                .get_item("substance_results")
+                .and_then(|s| s.get_item(0))
+                .and_then(|s| s.get_item("value"))
+                .and_then(|v| v.get_item("Measured"))
+                .unwrap();
+            let decimal = py.import("decimal").unwrap().getattr("Decimal").unwrap();
+            assert!(value.is_instance(&decimal).unwrap());
+            assert_eq!(value.str().unwrap().to_string(), "120");
+        });
+    }
+
+    #[test]
+    fn round_trips_rulesets_and_reports_errors() {
+        Python::initialize();
+        Python::attach(|py| {
+            let nv = load_preset(py, "nv").unwrap();
+            let km = nv
+                .get_item("rules")
+                .and_then(|r| r.get_item(0))
+                .and_then(|r| r.get_item("thresholds"))
+                .and_then(|t| t.get_item("KM"))
+                .unwrap();
+            assert_eq!(km.get_type().name().unwrap().to_string(), "Decimal");
+            let rules = PyList::new(py, [nv]).unwrap();
+            let reports = parse_bytes(py, REPORTS, "reports.json").unwrap();
+            let result = classify(py, &reports, None, Some(rules.as_any()), None, None, None);
+            assert!(result.is_ok());
+
+            let err = load_preset(py, "xyz").unwrap_err();
+            assert!(err.is_instance_of::<SondaError>(py));
+            assert!(parse_ruleset(py, "{}").is_err());
+        });
+    }
+}